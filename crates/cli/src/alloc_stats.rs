@@ -0,0 +1,306 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fs,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use advent_of_code::solution::{Observer, Phase};
+use serde::{Deserialize, Serialize};
+
+/// The global allocator when built with the `alloc-stats` feature.
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Bytes allocated and not yet freed.
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+/// The value of `CURRENT` at the last [`reset`].
+static BASELINE: AtomicU64 = AtomicU64::new(0);
+/// The highest `CURRENT` has been since the last [`reset`].
+static PEAK: AtomicU64 = AtomicU64::new(0);
+/// Bytes allocated since the last [`reset`], whether freed or not.
+static TOTAL: AtomicU64 = AtomicU64::new(0);
+/// Allocations since the last [`reset`].
+static COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// The ticket of the one worker thread whose allocations are counted, or 0
+/// for none, see [`start_worker`].
+static COUNTED_WORKER: AtomicU64 = AtomicU64::new(0);
+/// The ticket [`start_worker`] hands out next.
+static NEXT_WORKER: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// The ticket of the worker running on this thread, or 0 if it isn't one.
+    static WORKER: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Whether `aoc` was built with the `alloc-stats` feature, without which
+/// nothing is counted.
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+/// The system allocator, counting every allocation it makes.
+///
+/// The counters are shared by every thread, so they only describe a phase
+/// when nothing else is allocating at the same time. Worker threads are the
+/// exception: only the latest one of [`start_worker`] is counted, so that one
+/// left running after a timeout doesn't add to the phases after it.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "alloc-stats"), allow(dead_code))]
+pub struct Counting;
+
+// SAFETY: every call is forwarded to `System` unchanged; the counters are only
+// updated on the side.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        freed(layout.size());
+    }
+
+    /// Counted as freeing the old block and allocating the new one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether this thread's allocations are counted, see [`start_worker`].
+#[cfg_attr(not(feature = "alloc-stats"), allow(dead_code))]
+fn counted() -> bool {
+    // The ticket can't be read while the thread is being torn down, when
+    // it's no longer running a phase anyway.
+    WORKER
+        .try_with(Cell::get)
+        .is_ok_and(|worker| worker == 0 || worker == COUNTED_WORKER.load(Ordering::Relaxed))
+}
+
+#[cfg_attr(not(feature = "alloc-stats"), allow(dead_code))]
+fn allocated(size: usize) {
+    if !counted() {
+        return;
+    }
+    let size = size as u64;
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+    TOTAL.fetch_add(size, Ordering::Relaxed);
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg_attr(not(feature = "alloc-stats"), allow(dead_code))]
+fn freed(size: usize) {
+    if !counted() {
+        return;
+    }
+    CURRENT.fetch_sub(size as u64, Ordering::Relaxed);
+}
+
+/// What a phase allocated, as counted by [`Counting`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// The most memory held at once on top of what was already allocated
+    /// when the phase started, in bytes.
+    pub peak_bytes: u64,
+    /// Every allocation's size added up, in bytes.
+    pub total_bytes: u64,
+    /// How many allocations were made.
+    pub allocations: u64,
+    /// The most memory the whole process held in RAM during the phase, in
+    /// bytes, or since it started where that can't be reset. Only known on
+    /// Linux.
+    pub peak_rss_bytes: Option<u64>,
+}
+
+/// Starts counting anew from what is allocated right now.
+pub fn reset() {
+    // Writing 5 resets the peak resident set size to the current one; older
+    // kernels don't support it, and then the peak is the process's.
+    let _ = fs::write("/proc/self/clear_refs", "5");
+
+    let current = CURRENT.load(Ordering::Relaxed);
+    BASELINE.store(current, Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    TOTAL.store(0, Ordering::Relaxed);
+    COUNT.store(0, Ordering::Relaxed);
+}
+
+/// What was allocated since the last [`reset`].
+pub fn snapshot() -> AllocStats {
+    AllocStats {
+        peak_bytes: PEAK
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE.load(Ordering::Relaxed)),
+        total_bytes: TOTAL.load(Ordering::Relaxed),
+        allocations: COUNT.load(Ordering::Relaxed),
+        peak_rss_bytes: fs::read_to_string("/proc/self/status")
+            .ok()
+            .and_then(|status| peak_rss(&status)),
+    }
+}
+
+/// The peak resident set size in a `/proc/<pid>/status` file, in bytes.
+fn peak_rss(status: &str) -> Option<u64> {
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// `stats` on one line, such as `peak 1.5 KiB, 3.0 KiB in 2 allocations`.
+pub fn describe(stats: &AllocStats) -> String {
+    let mut line = format!(
+        "peak {}, {} in {} allocations",
+        format_bytes(stats.peak_bytes),
+        format_bytes(stats.total_bytes),
+        stats.allocations,
+    );
+    if let Some(rss) = stats.peak_rss_bytes {
+        line += &format!(", {} resident", format_bytes(rss));
+    }
+    line
+}
+
+/// Hands out the ticket of a new worker thread, which is then the only worker
+/// whose allocations are counted. The worker passes it to [`enter_worker`].
+pub fn start_worker() -> u64 {
+    let ticket = NEXT_WORKER.fetch_add(1, Ordering::Relaxed);
+    COUNTED_WORKER.store(ticket, Ordering::Relaxed);
+    ticket
+}
+
+/// Marks this thread as the worker of `ticket`, see [`start_worker`].
+pub fn enter_worker(ticket: u64) {
+    WORKER.set(ticket);
+}
+
+/// Stops counting the worker of the last [`start_worker`], such as one that
+/// was given up on but can't be stopped.
+pub fn abandon_worker() {
+    COUNTED_WORKER.store(0, Ordering::Relaxed);
+}
+
+/// Records what each phase of a run allocated, in the order they ended.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    pub phases: Vec<(Phase, AllocStats)>,
+}
+
+impl Recorder {
+    /// What `phase` allocated, or nothing if it didn't run.
+    pub fn get(&self, phase: Phase) -> AllocStats {
+        self.phases
+            .iter()
+            .find(|(recorded, _)| *recorded == phase)
+            .map(|&(_, stats)| stats)
+            .unwrap_or_default()
+    }
+}
+
+impl Observer for Recorder {
+    fn start(&mut self, _: Phase) {
+        reset();
+    }
+
+    fn end(&mut self, phase: Phase) {
+        let stats = snapshot();
+        self.phases.push((phase, stats));
+    }
+}
+
+/// Formats `bytes` with a binary unit, such as `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(format_bytes(u64::MAX), "16777216.0 TiB");
+    }
+
+    #[test]
+    fn test_describe() {
+        let status = "Name:\taoc\nVmPeak:\t   20000 kB\nVmHWM:\t    6144 kB\nVmRSS:\t    5120 kB\n";
+        let mut stats = AllocStats {
+            peak_bytes: 1536,
+            total_bytes: 3072,
+            allocations: 2,
+            peak_rss_bytes: peak_rss(status),
+        };
+        assert_eq!(
+            describe(&stats),
+            "peak 1.5 KiB, 3.0 KiB in 2 allocations, 6.0 MiB resident"
+        );
+
+        stats.peak_rss_bytes = peak_rss("Name:\taoc\n");
+        assert_eq!(describe(&stats), "peak 1.5 KiB, 3.0 KiB in 2 allocations");
+    }
+
+    // With the feature, every other test allocates through the same counters.
+    #[test]
+    #[cfg(not(feature = "alloc-stats"))]
+    fn test_counting() {
+        let layout = Layout::array::<u64>(16).unwrap();
+        reset();
+        // SAFETY: `layout` isn't zero-sized, and the blocks are freed with
+        // the layouts they were allocated with.
+        unsafe {
+            let ptr = Counting.alloc(layout);
+            let ptr = Counting.realloc(ptr, layout, 256);
+            Counting.dealloc(ptr, Layout::from_size_align(256, layout.align()).unwrap());
+        }
+
+        let stats = snapshot();
+        assert_eq!(
+            stats,
+            AllocStats {
+                peak_bytes: 256,
+                total_bytes: 128 + 256,
+                allocations: 2,
+                peak_rss_bytes: stats.peak_rss_bytes,
+            }
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use advent_of_code::Answer;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+/// The answers file used when `--answers` isn't given.
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// The answers file to use: `path` if given, and otherwise the config file's
+/// `answers` or [`DEFAULT_PATH`].
+pub fn path(path: Option<&Path>, config: &Config) -> PathBuf {
+    path.map(Path::to_path_buf)
+        .or_else(|| config.answers.clone())
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_PATH))
+}
+
+/// Known answers, keyed by year, day and part.
+///
+/// The file is TOML, or JSON if its extension is `.json`, with one table per
+/// day:
+///
+/// ```toml
+/// [2015.1]
+/// part1 = 138
+/// part2 = 1771
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers(BTreeMap<(u16, u8, u8), Expected>);
+
+/// An answer as written in the answers file: a number or a string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Expected {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Day {
+    part1: Option<Expected>,
+    part2: Option<Expected>,
+}
+
+/// Why the answers file couldn't be loaded.
+#[derive(Debug)]
+pub enum AnswersError {
+    Io { path: PathBuf, source: io::Error },
+    Parse { path: PathBuf, message: String },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "failed to read {}: {source}", path.display()),
+            Self::Parse { path, message } => {
+                write!(f, "failed to parse {}: {message}", path.display())
+            }
+        }
+    }
+}
+
+impl Answers {
+    /// Reads the answers file at `path`.
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let contents = fs::read_to_string(path).map_err(|source| AnswersError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension == "json");
+
+        Self::parse(&contents, is_json).map_err(|message| AnswersError::Parse {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    fn parse(contents: &str, is_json: bool) -> Result<Self, String> {
+        let years: BTreeMap<String, BTreeMap<String, Day>> = if is_json {
+            serde_json::from_str(contents).map_err(|error| error.to_string())?
+        } else {
+            toml::from_str(contents).map_err(|error| error.to_string())?
+        };
+
+        let mut answers = BTreeMap::new();
+        for (year, days) in years {
+            let year = year
+                .parse::<u16>()
+                .map_err(|_| format!("`{year}` is not a year"))?;
+            for (day, parts) in days {
+                let day = day
+                    .parse::<u8>()
+                    .map_err(|_| format!("`{day}` of {year} is not a day"))?;
+                for (part, expected) in [(1, parts.part1), (2, parts.part2)] {
+                    if let Some(expected) = expected {
+                        answers.insert((year, day, part), expected);
+                    }
+                }
+            }
+        }
+
+        Ok(Self(answers))
+    }
+
+    /// The known answer to `part` of `day` of `year`, if there is one.
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&Expected> {
+        self.0.get(&(year, day, part))
+    }
+}
+
+impl Expected {
+    /// Whether `answer` is this answer.
+    ///
+    /// Numbers compare by value whichever way they're written, and art
+    /// ignores trailing whitespace and surrounding blank lines.
+    pub fn matches(&self, answer: &Answer) -> bool {
+        match (self, answer) {
+            (Self::Signed(expected), _) => answer == expected,
+            (Self::Unsigned(expected), Answer::Unsigned(value)) => value == expected,
+            (Self::Unsigned(_), _) => false,
+            (Self::Text(expected), Answer::Art(art)) => trim_art(expected) == trim_art(art),
+            (Self::Text(expected), _) => answer == expected.as_str(),
+        }
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signed(value) => value.fmt(f),
+            Self::Unsigned(value) => value.fmt(f),
+            Self::Text(value) => value.fmt(f),
+        }
+    }
+}
+
+fn trim_art(art: &str) -> Vec<&str> {
+    art.trim_matches('\n').lines().map(str::trim_end).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let toml = r#"
+            [2015.1]
+            part1 = 138
+            part2 = 1771
+
+            [2016.5]
+            part1 = "f97c354d"
+        "#;
+        let json = r#"{ "2015": { "1": { "part1": 138, "part2": 1771 } }, "2016": { "5": { "part1": "f97c354d" } } }"#;
+
+        for answers in [Answers::parse(toml, false), Answers::parse(json, true)] {
+            let answers = answers.unwrap();
+            assert_eq!(answers.get(2015, 1, 1), Some(&Expected::Signed(138)));
+            assert_eq!(answers.get(2015, 1, 2), Some(&Expected::Signed(1771)));
+            assert_eq!(
+                answers.get(2016, 5, 1),
+                Some(&Expected::Text("f97c354d".to_string()))
+            );
+            assert_eq!(answers.get(2016, 5, 2), None);
+        }
+
+        assert!(Answers::parse("[2015.x]\npart1 = 1", false).is_err());
+        assert!(Answers::parse("[2015.1]\npart3 = 1", false).is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(Expected::Signed(138).matches(&Answer::Unsigned(138)));
+        assert!(Expected::Signed(-1).matches(&Answer::Signed(-1)));
+        assert!(!Expected::Signed(138).matches(&Answer::Unsigned(139)));
+        assert!(Expected::Text("138".to_string()).matches(&Answer::Unsigned(138)));
+        assert!(Expected::Text("abc".to_string()).matches(&Answer::from("abc")));
+        assert!(!Expected::Signed(1).matches(&Answer::from("1")));
+        assert!(Expected::Text("\n#..#  \n.##.\n".to_string())
+            .matches(&Answer::Art("#..#\n.##.".to_string())));
+    }
+}
@@ -0,0 +1,471 @@
+use std::{
+    fs,
+    hint::black_box,
+    io,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use advent_of_code::{solution::Entry, SolveError};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    input,
+    report::{self, Format},
+    run_all::{self, Selection},
+    scaffold,
+};
+
+/// The directory results are saved in when `--results-dir` isn't given,
+/// relative to the root of the workspace.
+pub const DEFAULT_RESULTS_DIR: &str = "bench-results";
+
+/// Options for `aoc bench`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// How many times to run each day before timing it.
+    #[arg(long, default_value_t = 1)]
+    pub warmup: u32,
+
+    /// How many timed runs of each day the statistics are taken over.
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    pub samples: u32,
+
+    /// Where results are saved, as one `<commit>.json` per commit. Defaults
+    /// to `bench-results` at the root of the workspace.
+    #[arg(long, env = "AOC_BENCH_DIR")]
+    pub results_dir: Option<PathBuf>,
+
+    /// The commit whose results to compare against. Defaults to the most
+    /// recent results of any other commit.
+    #[arg(long)]
+    pub baseline: Option<String>,
+
+    /// How much slower the median of a phase may get, in percent, before it
+    /// counts as a regression.
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
+
+    /// Compare against the baseline without saving the results.
+    #[arg(long)]
+    pub no_save: bool,
+}
+
+/// The saved results of one run of `aoc bench`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Results {
+    /// The abbreviated hash of the commit, with a `-dirty` suffix if the
+    /// working tree had uncommitted changes.
+    pub commit: String,
+    /// When the results were taken, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub warmup: u32,
+    pub samples: u32,
+    pub measurements: Vec<Measurement>,
+}
+
+/// What a [`Measurement`] timed: parsing a day's input, or solving one of
+/// its parts once the input was parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    const ALL: [Self; 3] = [Self::Parse, Self::Part1, Self::Part2];
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::Part1 => "part 1",
+            Self::Part2 => "part 2",
+        }
+    }
+}
+
+/// The timings of one phase of a day, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub phase: Phase,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub max_ns: u64,
+    pub mean_ns: u64,
+}
+
+/// A measurement compared against the baseline, as printed with `--format`.
+#[derive(Debug, Serialize)]
+struct BenchRecord {
+    title: &'static str,
+    #[serde(flatten)]
+    measurement: Measurement,
+    baseline_median_ns: Option<u64>,
+    /// How much the median changed against the baseline, in percent.
+    change: Option<f64>,
+    regression: bool,
+}
+
+pub fn run(args: &Args, format: Format, config: &Config) -> ExitCode {
+    let entries = match run_all::select(&args.selection, config) {
+        Ok(entries) => entries,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let dir = args.results_dir.clone().unwrap_or_else(|| {
+        scaffold::find_root()
+            .unwrap_or_default()
+            .join(DEFAULT_RESULTS_DIR)
+    });
+    let commit = commit();
+    let baseline = match load_baseline(&dir, args.baseline.as_deref(), &commit) {
+        Ok(baseline) => baseline,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if format == Format::Text {
+        println!(
+            "{:>4} {:>3}  {:<6}  {:>10}  {:>10}  {:>10}  {:>10}  Change",
+            "Year", "Day", "Phase", "Median", "P95", "Min", "Max"
+        );
+    }
+
+    let inputs = input::provider(args.selection.input.as_deref(), config);
+    let mut failed = false;
+    let mut records = Vec::new();
+    for entry in entries {
+        let input = match inputs.load(entry.year, entry.day) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("error: {error}");
+                failed = true;
+                continue;
+            }
+        };
+
+        let measurements = match measure(entry, &input, args.warmup, args.samples) {
+            Ok(measurements) => measurements,
+            Err(error) => {
+                eprintln!("error: {} day {}: {error}", entry.year, entry.day);
+                failed = true;
+                continue;
+            }
+        };
+
+        for measurement in measurements {
+            let before = baseline.as_ref().and_then(|baseline| {
+                baseline.measurements.iter().find(|before| {
+                    (before.year, before.day, before.phase)
+                        == (measurement.year, measurement.day, measurement.phase)
+                })
+            });
+            let change = before.map(|before| change(before, &measurement));
+            let regression = change.is_some_and(|change| change > args.threshold);
+            if format == Format::Text {
+                print_row(&measurement, change, regression);
+            }
+            records.push(BenchRecord {
+                title: entry.title,
+                baseline_median_ns: before.map(|before| before.median_ns),
+                measurement,
+                change,
+                regression,
+            });
+        }
+    }
+
+    let regressions = records.iter().filter(|record| record.regression).count();
+    match format {
+        Format::Text => {}
+        Format::Json => report::print_json(&records),
+        Format::Ndjson => report::print_ndjson(&records),
+    }
+
+    let results = Results {
+        commit,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        warmup: args.warmup,
+        samples: args.samples,
+        measurements: records
+            .into_iter()
+            .map(|record| record.measurement)
+            .collect(),
+    };
+    if !args.no_save {
+        match save(&dir, &results) {
+            Ok(path) => eprintln!("saved {}", path.display()),
+            Err(error) => {
+                eprintln!(
+                    "error: failed to save results in {}: {error}",
+                    dir.display()
+                );
+                failed = true;
+            }
+        }
+    }
+    match &baseline {
+        Some(baseline) => eprintln!(
+            "compared against {}: {regressions} regressions above {}%",
+            baseline.commit, args.threshold
+        ),
+        None => eprintln!("no baseline to compare against in {}", dir.display()),
+    }
+
+    if failed || regressions > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Runs both parts of `entry` against `input` `warmup` times untimed, then
+/// `samples` times timed, and measures parsing the input and solving each
+/// part separately.
+///
+/// # Errors
+///
+/// Returns the first error parsing or either part reports.
+pub fn measure(
+    entry: &Entry,
+    input: &str,
+    warmup: u32,
+    samples: u32,
+) -> Result<Vec<Measurement>, SolveError> {
+    let run = || -> Result<[Duration; 3], SolveError> {
+        let timed = entry.run(black_box(input), &[1, 2]);
+        let mut timings = [timed.parse, Duration::ZERO, Duration::ZERO];
+        for (timing, part) in timings[1..].iter_mut().zip(timed.parts) {
+            *timing = part.elapsed;
+            black_box(part.result?);
+        }
+        Ok(timings)
+    };
+
+    for _ in 0..warmup {
+        run()?;
+    }
+
+    let mut timings = Phase::ALL.map(|_| Vec::with_capacity(samples as usize));
+    for _ in 0..samples {
+        for (timings, timing) in timings.iter_mut().zip(run()?) {
+            timings.push(timing);
+        }
+    }
+
+    Ok(Phase::ALL
+        .into_iter()
+        .zip(&mut timings)
+        .map(|(phase, timings)| Measurement::new((entry.year, entry.day, phase), timings))
+        .collect())
+}
+
+impl Measurement {
+    /// Summarizes `timings`, which must not be empty. The 95th percentile is
+    /// taken with the nearest-rank method.
+    pub fn new((year, day, phase): (u16, u8, Phase), timings: &mut [Duration]) -> Self {
+        timings.sort_unstable();
+        let nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let len = timings.len();
+
+        let median = if len.is_multiple_of(2) {
+            (timings[len / 2 - 1] + timings[len / 2]) / 2
+        } else {
+            timings[len / 2]
+        };
+        let p95 = timings[(len * 95).div_ceil(100) - 1];
+        let total = timings.iter().sum::<Duration>();
+
+        Self {
+            year,
+            day,
+            phase,
+            min_ns: nanos(timings[0]),
+            median_ns: nanos(median),
+            p95_ns: nanos(p95),
+            max_ns: nanos(timings[len - 1]),
+            mean_ns: nanos(total / u32::try_from(len).unwrap_or(u32::MAX)),
+        }
+    }
+}
+
+/// How much the median of `after` changed against `before`, in percent.
+fn change(before: &Measurement, after: &Measurement) -> f64 {
+    if before.median_ns == 0 {
+        return 0.0;
+    }
+    (after.median_ns as f64 / before.median_ns as f64 - 1.0) * 100.0
+}
+
+fn print_row(measurement: &Measurement, change: Option<f64>, regression: bool) {
+    let time = |nanos| format!("{:.2?}", Duration::from_nanos(nanos));
+    let change = match change {
+        Some(change) if regression => format!("{change:+.1}%  REGRESSION"),
+        Some(change) => format!("{change:+.1}%"),
+        None => "-".to_string(),
+    };
+
+    println!(
+        "{:>4} {:>3}  {:<6}  {:>10}  {:>10}  {:>10}  {:>10}  {change}",
+        measurement.year,
+        measurement.day,
+        measurement.phase.label(),
+        time(measurement.median_ns),
+        time(measurement.p95_ns),
+        time(measurement.min_ns),
+        time(measurement.max_ns),
+    );
+}
+
+/// The abbreviated hash of `HEAD`, with a `-dirty` suffix if tracked files
+/// have uncommitted changes, or `unknown` outside a git repository.
+fn commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let Some(hash) = git(&["rev-parse", "--short", "HEAD"]) else {
+        return "unknown".to_string();
+    };
+    match git(&["status", "--porcelain", "--untracked-files=no"]) {
+        Some(status) if !status.is_empty() => format!("{hash}-dirty"),
+        _ => hash,
+    }
+}
+
+/// Loads the results of `commit` from `dir`, or without one, the most recent
+/// results of a commit other than `current`. Files that can't be parsed are
+/// ignored when looking for the most recent ones.
+fn load_baseline(
+    dir: &Path,
+    commit: Option<&str>,
+    current: &str,
+) -> Result<Option<Results>, String> {
+    let read = |path: &Path| -> Result<Results, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("failed to read {}: {error}", path.display()))?;
+        serde_json::from_str(&contents)
+            .map_err(|error| format!("failed to parse {}: {error}", path.display()))
+    };
+
+    if let Some(commit) = commit {
+        return read(&dir.join(format!("{commit}.json"))).map(Some);
+    }
+
+    let Ok(files) = fs::read_dir(dir) else {
+        return Ok(None);
+    };
+    Ok(files
+        .filter_map(|file| file.ok().map(|file| file.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .filter_map(|path| read(&path).ok())
+        .filter(|results| results.commit != current)
+        .max_by_key(|results| results.timestamp))
+}
+
+/// Writes `results` to `<dir>/<commit>.json`, replacing earlier results of
+/// the same commit.
+fn save(dir: &Path, results: &Results) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.json", results.commit));
+    fs::write(&path, serde_json::to_string_pretty(results)? + "\n")?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use advent_of_code::solution;
+
+    use super::*;
+
+    #[test]
+    fn test_measurement() {
+        let mut timings = [5, 1, 4, 2, 3].map(Duration::from_nanos);
+        let measurement = Measurement::new((2015, 1, Phase::Part1), &mut timings);
+        assert_eq!(measurement.min_ns, 1);
+        assert_eq!(measurement.median_ns, 3);
+        assert_eq!(measurement.p95_ns, 5);
+        assert_eq!(measurement.max_ns, 5);
+        assert_eq!(measurement.mean_ns, 3);
+
+        let mut timings = (1..=20).map(Duration::from_nanos).collect::<Vec<_>>();
+        let measurement = Measurement::new((2015, 1, Phase::Part1), &mut timings);
+        assert_eq!(measurement.median_ns, 10);
+        assert_eq!(measurement.p95_ns, 19);
+
+        let entry = solution::find(2015, 1).unwrap();
+        let measurements = measure(entry, "(()))", 1, 3).unwrap();
+        assert_eq!(
+            measurements.iter().map(|m| m.phase).collect::<Vec<_>>(),
+            Phase::ALL
+        );
+        assert!(measurements.iter().all(|m| m.min_ns <= m.max_ns));
+        assert!(measure(entry, "(()", 0, 3).is_err());
+        assert!(measure(entry, "(()x", 0, 3).is_err());
+    }
+
+    #[test]
+    fn test_baseline() {
+        let dir = std::env::temp_dir().join(format!("aoc-bench-{}", std::process::id()));
+        let results = |commit: &str, timestamp, median_ns| Results {
+            commit: commit.to_string(),
+            timestamp,
+            warmup: 1,
+            samples: 10,
+            measurements: vec![Measurement {
+                year: 2015,
+                day: 1,
+                phase: Phase::Part1,
+                min_ns: median_ns,
+                median_ns,
+                p95_ns: median_ns,
+                max_ns: median_ns,
+                mean_ns: median_ns,
+            }],
+        };
+
+        let none = load_baseline(&dir, None, "ccc").unwrap();
+        save(&dir, &results("aaa", 1, 100)).unwrap();
+        save(&dir, &results("bbb", 2, 200)).unwrap();
+        save(&dir, &results("ccc", 3, 300)).unwrap();
+        fs::write(dir.join("broken.json"), "{").unwrap();
+        let latest = load_baseline(&dir, None, "ccc").unwrap();
+        let named = load_baseline(&dir, Some("aaa"), "ccc").unwrap();
+        let missing = load_baseline(&dir, Some("ddd"), "ccc");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(none, None);
+        assert_eq!(latest, Some(results("bbb", 2, 200)));
+        assert_eq!(named, Some(results("aaa", 1, 100)));
+        assert!(missing.is_err());
+
+        let before = &results("aaa", 1, 100).measurements[0];
+        let after = &results("bbb", 2, 115).measurements[0];
+        assert!((change(before, after) - 15.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,386 @@
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::config::Config;
+
+/// Where puzzles are fetched from and answers submitted to.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// How long to wait between two requests to the server, across every `aoc`
+/// process sharing the same cache directory.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = concat!(
+    "aoc-cli/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/martial-plains/advent-of-code)"
+);
+
+/// Options for the subcommands that talk to the server.
+#[derive(Debug, Clone, clap::Args)]
+pub struct ClientArgs {
+    /// The session cookie of your account. Defaults to the contents of the
+    /// session file.
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    pub session: Option<String>,
+
+    /// The file holding the session cookie. Defaults to the config file's
+    /// `session-file`, or `$XDG_CONFIG_HOME/aoc/session`.
+    #[arg(long, env = "AOC_SESSION_FILE")]
+    pub session_file: Option<PathBuf>,
+
+    /// The server to talk to, without a trailing slash.
+    #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    pub base_url: String,
+
+    /// Where downloaded inputs and other state are kept. Defaults to
+    /// `$XDG_CACHE_HOME/aoc`.
+    #[arg(long, env = "AOC_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// A rate-limited client for the Advent of Code server.
+#[derive(Debug)]
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Option<String>,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+/// Why a request to the server failed.
+#[derive(Debug)]
+pub enum ClientError {
+    /// The request needs a session, and none was configured.
+    NoSession,
+    /// The server answered with something other than `200 OK`.
+    Status {
+        status: u16,
+        body: String,
+    },
+    /// The server couldn't be reached.
+    Transport(String),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSession => write!(
+                f,
+                "no session token; pass `--session`, set `AOC_SESSION`, or write it to {} \
+                 or the config file's `session-file`",
+                config_dir().join("session").display()
+            ),
+            Self::Status { status: 400, .. } => {
+                write!(f, "the server rejected the session token (400)")
+            }
+            Self::Status { status: 404, .. } => {
+                write!(f, "the puzzle doesn't exist or hasn't unlocked yet (404)")
+            }
+            Self::Status { status: 429, .. } => {
+                write!(
+                    f,
+                    "the server is rate limiting requests (429); try again later"
+                )
+            }
+            Self::Status { status, body } => {
+                write!(f, "the server answered {status}: {}", body.trim())
+            }
+            Self::Transport(message) => write!(f, "request failed: {message}"),
+            Self::Io { path, source } => write!(f, "failed to access {}: {source}", path.display()),
+        }
+    }
+}
+
+impl ClientArgs {
+    pub fn client(&self, config: &Config) -> Client {
+        let session_file = self
+            .session_file
+            .clone()
+            .or_else(|| config.session_file.clone())
+            .unwrap_or_else(|| config_dir().join("session"));
+        let session = self.session.clone().or_else(|| {
+            fs::read_to_string(session_file)
+                .ok()
+                .map(|session| session.trim().to_string())
+                .filter(|session| !session.is_empty())
+        });
+
+        Client::new(self.base_url.clone(), session, self.cache_dir())
+    }
+
+    pub fn cache_dir(&self) -> PathBuf {
+        self.cache_dir.clone().unwrap_or_else(cache_dir)
+    }
+}
+
+impl Client {
+    pub fn new(base_url: String, session: Option<String>, cache_dir: PathBuf) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            cache_dir,
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    #[cfg(test)]
+    #[must_use]
+    pub const fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Fetches `path` from the server, returning the body.
+    pub fn get(&self, path: &str) -> Result<String, ClientError> {
+        let request = self.request("GET", path)?;
+        Self::read(request.call())
+    }
+
+    /// Posts `form` to `path`, returning the body of the response.
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, ClientError> {
+        let request = self.request("POST", path)?;
+        Self::read(request.send_form(form))
+    }
+
+    fn request(&self, method: &str, path: &str) -> Result<ureq::Request, ClientError> {
+        let session = self.session.as_ref().ok_or(ClientError::NoSession)?;
+        self.throttle()?;
+
+        Ok(self
+            .agent
+            .request(method, &format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={session}")))
+    }
+
+    fn read(response: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, response)) => {
+                return Err(ClientError::Status {
+                    status,
+                    body: response.into_string().unwrap_or_default(),
+                })
+            }
+            Err(error) => return Err(ClientError::Transport(error.to_string())),
+        };
+
+        response
+            .into_string()
+            .map_err(|error| ClientError::Transport(error.to_string()))
+    }
+
+    /// Waits until `min_interval` has passed since the last request made
+    /// with this cache directory, then records this one.
+    fn throttle(&self) -> Result<(), ClientError> {
+        let stamp = self.cache_dir.join("last-request");
+        let io_error = |source| ClientError::Io {
+            path: stamp.clone(),
+            source,
+        };
+
+        // The stamp holds the time of the last request in nanoseconds since
+        // the Unix epoch; file modification times are too coarse for this.
+        let now = || {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+        };
+        let last = fs::read_to_string(&stamp)
+            .ok()
+            .and_then(|last| last.trim().parse().ok())
+            .map(Duration::from_nanos);
+        if let Some(last) = last {
+            let elapsed = now().saturating_sub(last);
+            if let Some(remaining) = self.min_interval.checked_sub(elapsed) {
+                thread::sleep(remaining);
+            }
+        }
+
+        fs::create_dir_all(&self.cache_dir).map_err(io_error)?;
+        fs::write(&stamp, now().as_nanos().to_string()).map_err(io_error)
+    }
+}
+
+/// `$XDG_CONFIG_HOME/aoc`, or `~/.config/aoc`.
+pub fn config_dir() -> PathBuf {
+    xdg_dir("XDG_CONFIG_HOME", ".config").join("aoc")
+}
+
+/// `$XDG_CACHE_HOME/aoc`, or `~/.cache/aoc`.
+pub fn cache_dir() -> PathBuf {
+    xdg_dir("XDG_CACHE_HOME", ".cache").join("aoc")
+}
+
+fn xdg_dir(var: &str, fallback: &str) -> PathBuf {
+    env::var_os(var).filter(|dir| !dir.is_empty()).map_or_else(
+        || {
+            env::var_os("HOME")
+                .map_or_else(PathBuf::new, PathBuf::from)
+                .join(fallback)
+        },
+        PathBuf::from,
+    )
+}
+
+/// A stand-in for the server that answers each connection with the next
+/// canned response and records the requests it received.
+#[cfg(test)]
+pub mod mock {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    #[derive(Debug, Clone, Default)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Server {
+        pub url: String,
+        pub requests: Arc<Mutex<Vec<Request>>>,
+    }
+
+    /// Serves `responses`, as `(status, body)` pairs, one per connection.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> Server {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let received = Arc::clone(&requests);
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+                let request = read_request(&mut reader);
+                received.lock().unwrap().push(request);
+
+                let mut stream = reader.into_inner();
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+
+        Server { url, requests }
+    }
+
+    fn read_request(reader: &mut impl BufRead) -> Request {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let mut parts = line.split_whitespace();
+        let mut request = Request {
+            method: parts.next().unwrap_or_default().to_string(),
+            path: parts.next().unwrap_or_default().to_string(),
+            ..Request::default()
+        };
+
+        loop {
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            let Some((key, value)) = line.trim_end().split_once(": ") else {
+                break;
+            };
+            request.headers.push((key.to_string(), value.to_string()));
+        }
+
+        let length = request
+            .header("Content-Length")
+            .and_then(|length| length.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        request.body = String::from_utf8(body).unwrap();
+        request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn test_get_sends_session() {
+        let server = mock::serve(vec![(200, "hello"), (400, "Please log in")]);
+        let cache = temp_dir("client-get");
+        let client = Client::new(server.url.clone(), Some("abc".to_string()), cache.clone())
+            .with_min_interval(Duration::ZERO);
+
+        assert_eq!(client.get("/2015/day/1/input").unwrap(), "hello");
+        assert!(matches!(
+            client.get("/2015/day/2/input"),
+            Err(ClientError::Status { status: 400, .. })
+        ));
+        fs::remove_dir_all(&cache).unwrap();
+
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2015/day/1/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc"));
+        assert!(requests[0]
+            .header("User-Agent")
+            .is_some_and(|agent| agent.starts_with("aoc-cli/")));
+    }
+
+    #[test]
+    fn test_no_session() {
+        let client = Client::new("http://127.0.0.1:9".to_string(), None, temp_dir("none"));
+        assert!(matches!(client.get("/"), Err(ClientError::NoSession)));
+    }
+
+    #[test]
+    fn test_throttle() {
+        let server = mock::serve(vec![(200, "a"), (200, "b")]);
+        let cache = temp_dir("client-throttle");
+        let client = Client::new(server.url.clone(), Some("abc".to_string()), cache.clone())
+            .with_min_interval(Duration::from_millis(300));
+
+        let start = Instant::now();
+        client.get("/a").unwrap();
+        client.get("/b").unwrap();
+        let elapsed = start.elapsed();
+        fs::remove_dir_all(&cache).unwrap();
+
+        assert!(elapsed >= Duration::from_millis(300), "{elapsed:?}");
+    }
+}
@@ -0,0 +1,300 @@
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{client, report::Format};
+
+/// The name of the config file looked for in the working directory and its
+/// ancestors, and in `$XDG_CONFIG_HOME/aoc`.
+pub const FILE_NAME: &str = "aoc.toml";
+
+/// Defaults shared by everyone working in a checkout, read from `aoc.toml`.
+///
+/// Every setting is overridden by the matching flag or environment variable.
+/// Relative paths are resolved against the directory holding the file.
+///
+/// ```toml
+/// year = 2016
+/// input-dir = "inputs"
+/// answers = "answers.toml"
+/// format = "text"
+/// threads = 4
+/// session-file = "~/.config/aoc/session"
+/// timeout = "10s"
+///
+/// [days.2016.11]
+/// input = "inputs/2016/day_11_small.txt"
+/// skip = true
+/// timeout = "2m"
+/// ```
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// The year `run-all` and `verify` select when `--year` isn't given.
+    pub year: Option<u16>,
+    /// Where inputs are read from when no input is given, before falling back
+    /// to the embedded ones.
+    pub input_dir: Option<PathBuf>,
+    /// The answers file for `verify`.
+    pub answers: Option<PathBuf>,
+    pub format: Option<Format>,
+    /// The size of the thread pool used by `--parallel`.
+    pub threads: Option<usize>,
+    /// The file holding the session token.
+    pub session_file: Option<PathBuf>,
+    /// How long a part of any day may take before `run-all` and `verify` give
+    /// up on it, see [`parse_duration`].
+    #[serde(default, deserialize_with = "duration")]
+    pub timeout: Option<Duration>,
+    #[serde(default)]
+    days: BTreeMap<String, BTreeMap<String, DayConfig>>,
+    /// The file the settings were loaded from, if any.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// Settings for a single day.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DayConfig {
+    /// The input file for the day, instead of the one in `input-dir`.
+    pub input: Option<PathBuf>,
+    /// Leave the day out of `run-all` and `verify`.
+    #[serde(default)]
+    pub skip: bool,
+    /// How long a part of the day may take, instead of the top-level
+    /// `timeout`.
+    #[serde(default, deserialize_with = "duration")]
+    pub timeout: Option<Duration>,
+}
+
+/// Why the config file couldn't be loaded.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io { path: PathBuf, source: io::Error },
+    Parse { path: PathBuf, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "failed to read {}: {source}", path.display()),
+            Self::Parse { path, message } => {
+                write!(f, "failed to parse {}: {message}", path.display())
+            }
+        }
+    }
+}
+
+impl Config {
+    /// Loads `path` if given, or else the first `aoc.toml` found in the
+    /// working directory, its ancestors, or `$XDG_CONFIG_HOME/aoc`. Without
+    /// any, every setting is left unset.
+    pub fn discover(path: Option<&Path>) -> Result<Self, ConfigError> {
+        if let Some(path) = path {
+            return Self::load(path);
+        }
+
+        find(&env::current_dir().unwrap_or_default())
+            .map_or_else(|| Ok(Self::default()), |path| Self::load(&path))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let contents = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        let mut config =
+            Self::parse(&contents, path.parent().unwrap_or(Path::new(""))).map_err(|message| {
+                ConfigError::Parse {
+                    path: path.to_path_buf(),
+                    message,
+                }
+            })?;
+        config.path = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    fn parse(contents: &str, dir: &Path) -> Result<Self, String> {
+        let mut config = toml::from_str::<Self>(contents).map_err(|error| error.to_string())?;
+
+        for (year, days) in &config.days {
+            year.parse::<u16>()
+                .map_err(|_| format!("`{year}` in `days` is not a year"))?;
+            for day in days.keys() {
+                day.parse::<u8>()
+                    .map_err(|_| format!("`{day}` of {year} in `days` is not a day"))?;
+            }
+        }
+
+        let resolve = |path: &mut PathBuf| *path = resolve(dir, path);
+        config.input_dir.as_mut().map(resolve);
+        config.answers.as_mut().map(resolve);
+        config.session_file.as_mut().map(resolve);
+        for day in config.days.values_mut().flat_map(BTreeMap::values_mut) {
+            day.input.as_mut().map(resolve);
+        }
+
+        Ok(config)
+    }
+
+    /// The settings for `day` of `year`, if it has any.
+    pub fn day(&self, year: u16, day: u8) -> Option<&DayConfig> {
+        self.days
+            .iter()
+            .filter(|(key, _)| key.parse() == Ok(year))
+            .flat_map(|(_, days)| days)
+            .find(|(key, _)| key.parse() == Ok(day))
+            .map(|(_, config)| config)
+    }
+
+    /// Whether `day` of `year` is left out of `run-all` and `verify`.
+    pub fn skips(&self, year: u16, day: u8) -> bool {
+        self.day(year, day).is_some_and(|day| day.skip)
+    }
+
+    /// How long a part of `day` of `year` may take, if it has a budget.
+    pub fn timeout(&self, year: u16, day: u8) -> Option<Duration> {
+        self.day(year, day)
+            .and_then(|day| day.timeout)
+            .or(self.timeout)
+    }
+}
+
+/// Parses a duration such as `500ms`, `10s`, `1.5m` or `1h`.
+pub fn parse_duration(duration: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration `{duration}`, expected e.g. `500ms`, `10s` or `2m`");
+
+    let split = duration
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .ok_or_else(invalid)?;
+    let (value, unit) = duration.split_at(split);
+    let value = value.parse::<f64>().map_err(|_| invalid())?;
+    let seconds = match unit {
+        "ms" => value / 1000.0,
+        "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 60.0 * 60.0,
+        _ => return Err(invalid()),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|_| invalid())
+}
+
+/// Deserializes an optional duration written as for [`parse_duration`].
+fn duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    let duration = String::deserialize(deserializer)?;
+    parse_duration(&duration)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// The first `aoc.toml` in `dir`, its ancestors, or `$XDG_CONFIG_HOME/aoc`.
+fn find(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(FILE_NAME))
+        .chain([client::config_dir().join(FILE_NAME)])
+        .find(|path| path.is_file())
+}
+
+/// Expands a leading `~` to the home directory, and makes a relative `path`
+/// relative to `dir`.
+fn resolve(dir: &Path, path: &Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~") {
+        if let Some(home) = env::var_os("HOME") {
+            return PathBuf::from(home).join(rest);
+        }
+    }
+
+    dir.join(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r#"
+                year = 2016
+                input-dir = "inputs"
+                answers = "/shared/answers.toml"
+                format = "ndjson"
+                threads = 4
+                timeout = "10s"
+
+                [days.2016.11]
+                input = "small.txt"
+                skip = true
+                timeout = "2m"
+
+                [days.2015.1]
+                input = "other.txt"
+            "#,
+            Path::new("/repo"),
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2016));
+        assert_eq!(config.input_dir, Some(PathBuf::from("/repo/inputs")));
+        assert_eq!(config.answers, Some(PathBuf::from("/shared/answers.toml")));
+        assert_eq!(config.format, Some(Format::Ndjson));
+        assert_eq!(config.threads, Some(4));
+        assert_eq!(config.session_file, None);
+        assert_eq!(
+            config.day(2016, 11),
+            Some(&DayConfig {
+                input: Some(PathBuf::from("/repo/small.txt")),
+                skip: true,
+                timeout: Some(Duration::from_secs(120)),
+            })
+        );
+        assert_eq!(config.timeout(2016, 11), Some(Duration::from_secs(120)));
+        assert_eq!(config.timeout(2015, 1), Some(Duration::from_secs(10)));
+        assert!(config.skips(2016, 11));
+        assert!(!config.skips(2015, 1));
+        assert_eq!(config.day(2016, 12), None);
+
+        assert!(Config::parse("colour = true", Path::new("")).is_err());
+        assert!(Config::parse("[days.2016.x]\nskip = true", Path::new("")).is_err());
+        assert!(Config::parse("[days.2016.1]\ntimeout = 1", Path::new("")).is_err());
+        assert!(Config::parse("timeout = \"soon\"", Path::new("")).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("1.5m"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("10 s").is_err());
+        assert!(parse_duration("-1s").is_err());
+        assert!(parse_duration("1d").is_err());
+    }
+
+    #[test]
+    fn test_discover() {
+        let root = env::temp_dir().join(format!("aoc-config-{}", std::process::id()));
+        let nested = root.join("crates").join("cli");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join(FILE_NAME), "year = 2015\n").unwrap();
+
+        let found = find(&nested);
+        let config = Config::load(found.as_deref().unwrap());
+        let missing = Config::load(&nested.join(FILE_NAME));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(found, Some(root.join(FILE_NAME)));
+        assert_eq!(config.unwrap().year, Some(2015));
+        assert!(matches!(missing, Err(ConfigError::Io { .. })));
+    }
+}
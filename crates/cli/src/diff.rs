@@ -0,0 +1,375 @@
+use std::{borrow::Cow, path::PathBuf, process::ExitCode};
+
+use advent_of_code::{
+    solution::{self, Entry},
+    Answer, SolveError,
+};
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    input,
+    report::{self, AnswerValue, ErrorRecord, Format},
+};
+
+/// Options for `aoc diff`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The year of the day to compare.
+    pub year: u16,
+
+    /// The day to compare (1-25).
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+
+    /// Only compare these variants, such as `default,md-5`. Defaults to every
+    /// variant of the day.
+    #[arg(long, value_delimiter = ',')]
+    pub variants: Vec<String>,
+
+    /// How many inputs to generate besides the puzzle input, if the day has a
+    /// generator.
+    #[arg(long, default_value_t = 5)]
+    pub samples: u64,
+
+    /// The seed of the first generated input; each next one adds one to it.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+
+    /// The puzzle input: a file, `-` for stdin, or a directory holding
+    /// `<year>/day_<dd>.txt`. Defaults to `AOC_INPUT_DIR`, the config file, or
+    /// the input embedded in the day's module.
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+}
+
+/// One input the variants are compared on.
+#[derive(Debug)]
+pub struct Case<'a> {
+    /// The seed the input was generated from, or `None` for the puzzle input.
+    pub seed: Option<u64>,
+    pub input: Cow<'a, str>,
+}
+
+/// Every variant's result for one part of one input.
+#[derive(Debug)]
+pub struct Comparison {
+    pub seed: Option<u64>,
+    pub part: u8,
+    pub results: Vec<(&'static str, Result<Answer, SolveError>)>,
+}
+
+impl Comparison {
+    /// Whether every variant gave the same answer, or every one of them
+    /// failed with the same kind of error.
+    pub fn agrees(&self) -> bool {
+        self.results
+            .windows(2)
+            .all(|pair| match (&pair[0].1, &pair[1].1) {
+                (Ok(answer), Ok(other)) => answer == other,
+                (Err(error), Err(other)) => error.kind() == other.kind(),
+                _ => false,
+            })
+    }
+}
+
+/// The machine-readable result of comparing one part on one input.
+#[derive(Debug, Serialize)]
+struct DiffRecord<'a> {
+    year: u16,
+    day: u8,
+    part: u8,
+    /// `None` for the puzzle input.
+    seed: Option<u64>,
+    agree: bool,
+    variants: Vec<VariantRecord<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct VariantRecord<'a> {
+    variant: &'static str,
+    answer: Option<AnswerValue<'a>>,
+    error: Option<ErrorRecord>,
+}
+
+pub fn run(args: &Args, format: Format, config: &Config) -> ExitCode {
+    let (entry, variants) = match select(args) {
+        Ok(selected) => selected,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = match input::load(args.year, args.day, args.input.as_deref(), config) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let cases = cases(entry, input, args.seed, args.samples);
+    let comparisons = compare(entry, &variants, &cases);
+
+    match format {
+        Format::Text => print_comparisons(entry, &variants, &cases, &comparisons),
+        Format::Json => report::print_json(&records(entry, &comparisons)),
+        Format::Ndjson => report::print_ndjson(&records(entry, &comparisons)),
+    }
+
+    if comparisons.iter().all(Comparison::agrees) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// The day named by `args`, and the names of the variants to compare, which
+/// need to be at least two.
+fn select(args: &Args) -> Result<(&'static Entry, Vec<&'static str>), String> {
+    let entry = solution::find(args.year, args.day).ok_or_else(|| {
+        SolveError::UnknownDay {
+            year: args.year,
+            day: args.day,
+        }
+        .to_string()
+    })?;
+    if entry.variants.is_empty() {
+        return Err(format!(
+            "day {} of {} has no variants to compare",
+            entry.day, entry.year
+        ));
+    }
+
+    if args.variants.is_empty() {
+        return Ok((entry, entry.variant_names().collect()));
+    }
+    let variants = args
+        .variants
+        .iter()
+        .map(|name| {
+            entry
+                .variant_names()
+                .find(|variant| variant == name)
+                .ok_or_else(|| {
+                    format!(
+                        "day {} of {} has no variant `{name}`, only {}",
+                        entry.day,
+                        entry.year,
+                        entry.variant_names().collect::<Vec<_>>().join(", ")
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if variants.len() < 2 {
+        return Err("at least two variants are needed to compare".to_string());
+    }
+
+    Ok((entry, variants))
+}
+
+/// The puzzle input, followed by `samples` inputs generated from `seed` on,
+/// if the day has a generator.
+pub fn cases<'a>(entry: &Entry, input: Cow<'a, str>, seed: u64, samples: u64) -> Vec<Case<'a>> {
+    let generated = entry.generate.into_iter().flat_map(|generate| {
+        (seed..seed.saturating_add(samples)).map(move |seed| Case {
+            seed: Some(seed),
+            input: Cow::Owned(generate(seed)),
+        })
+    });
+
+    [Case { seed: None, input }]
+        .into_iter()
+        .chain(generated)
+        .collect()
+}
+
+/// Solves both parts of every case with each of `variants`.
+pub fn compare(entry: &Entry, variants: &[&'static str], cases: &[Case]) -> Vec<Comparison> {
+    cases
+        .iter()
+        .flat_map(|case| {
+            let mut runs = variants
+                .iter()
+                .map(|&name| {
+                    let variant = entry.variant(name).expect("variants were selected by name");
+                    (name, variant.run(&case.input, &[1, 2]).parts.into_iter())
+                })
+                .collect::<Vec<_>>();
+
+            [1, 2].map(|part| Comparison {
+                seed: case.seed,
+                part,
+                results: runs
+                    .iter_mut()
+                    .map(|(name, parts)| (*name, parts.next().expect("both parts were run").result))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+fn records<'a>(entry: &Entry, comparisons: &'a [Comparison]) -> Vec<DiffRecord<'a>> {
+    comparisons
+        .iter()
+        .map(|comparison| DiffRecord {
+            year: entry.year,
+            day: entry.day,
+            part: comparison.part,
+            seed: comparison.seed,
+            agree: comparison.agrees(),
+            variants: comparison
+                .results
+                .iter()
+                .map(|(variant, result)| VariantRecord {
+                    variant,
+                    answer: result.as_ref().ok().map(AnswerValue::from),
+                    error: result.as_ref().err().map(ErrorRecord::from),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn print_comparisons(entry: &Entry, variants: &[&str], cases: &[Case], comparisons: &[Comparison]) {
+    println!(
+        "{} day {}: {} on the puzzle input and {} generated inputs",
+        entry.year,
+        entry.day,
+        variants.join(", "),
+        cases.len() - 1,
+    );
+
+    let name_width = variants
+        .iter()
+        .map(|name| name.len() + 1)
+        .max()
+        .unwrap_or(0);
+    for comparison in comparisons {
+        let input = comparison
+            .seed
+            .map_or_else(|| "puzzle input".to_string(), |seed| format!("seed {seed}"));
+        let agreed = comparison.agrees().then(|| &comparison.results[0].1);
+        match agreed {
+            Some(Ok(answer)) if !answer.to_string().contains('\n') => {
+                println!("{input:<14}  part {}  agree  {answer}", comparison.part);
+            }
+            Some(Err(error)) => {
+                println!(
+                    "{input:<14}  part {}  agree  error: {error}",
+                    comparison.part
+                );
+            }
+            Some(Ok(_)) => println!("{input:<14}  part {}  agree", comparison.part),
+            None => {
+                println!("{input:<14}  part {}  DIFFER", comparison.part);
+                for (name, result) in &comparison.results {
+                    let result = match result {
+                        Ok(answer) => answer.to_string().replace('\n', "\n      "),
+                        Err(error) => format!("error: {error}"),
+                    };
+                    println!("    {:<name_width$}  {result}", format!("{name}:"));
+                }
+            }
+        }
+    }
+
+    let differ = comparisons
+        .iter()
+        .filter(|comparison| !comparison.agrees())
+        .count();
+    println!();
+    println!("{differ} of {} comparisons disagree", comparisons.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use advent_of_code::solution::{Observer, Timed, Variant};
+
+    use super::*;
+
+    /// 2015 day 1, except that it gets part 1 wrong on inputs of odd length.
+    fn broken(input: &str, parts: &[u8], observer: &mut dyn Observer) -> Timed {
+        let mut timed = solution::find(2015, 1)
+            .unwrap()
+            .run_observed(input, parts, observer);
+        for part in &mut timed.parts {
+            if part.part == 1 && input.len() % 2 == 1 {
+                part.result = Ok(Answer::Signed(-99));
+            }
+        }
+        timed
+    }
+
+    static VARIANTS: [Variant; 1] = [Variant {
+        name: "broken",
+        phases: broken,
+    }];
+
+    #[test]
+    fn test_agrees() {
+        let agrees = |results| {
+            Comparison {
+                seed: None,
+                part: 1,
+                results,
+            }
+            .agrees()
+        };
+
+        assert!(agrees(vec![
+            ("default", Err(SolveError::no_solution("none"))),
+            ("other", Err(SolveError::no_solution("nothing found"))),
+        ]));
+        assert!(!agrees(vec![
+            ("default", Err(SolveError::no_solution("none"))),
+            (
+                "other",
+                Err(SolveError::Panic("panicked: boom".to_string()))
+            ),
+        ]));
+        assert!(!agrees(vec![
+            ("default", Ok(Answer::Unsigned(1))),
+            ("other", Err(SolveError::no_solution("none"))),
+        ]));
+    }
+
+    #[test]
+    fn test_compare() {
+        let entry = Entry {
+            variants: &VARIANTS,
+            generate: Some(|seed| "(".repeat(usize::try_from(seed).unwrap())),
+            ..*solution::find(2015, 1).unwrap()
+        };
+
+        let cases = cases(&entry, Cow::Borrowed("()"), 3, 2);
+        assert_eq!(
+            cases.iter().map(|case| case.seed).collect::<Vec<_>>(),
+            [None, Some(3), Some(4)]
+        );
+
+        let variants = entry.variant_names().collect::<Vec<_>>();
+        let comparisons = compare(&entry, &variants, &cases);
+        assert_eq!(
+            comparisons
+                .iter()
+                .map(|comparison| (comparison.seed, comparison.part, comparison.agrees()))
+                .collect::<Vec<_>>(),
+            [
+                (None, 1, true),
+                (None, 2, true),
+                (Some(3), 1, false),
+                (Some(3), 2, true),
+                (Some(4), 1, true),
+                (Some(4), 2, true),
+            ]
+        );
+        assert_eq!(
+            comparisons[2].results,
+            [
+                ("default", Ok(Answer::Signed(3))),
+                ("broken", Ok(Answer::Signed(-99)))
+            ]
+        );
+    }
+}
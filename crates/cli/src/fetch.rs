@@ -0,0 +1,173 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use advent_of_code::input::InputDir;
+
+use crate::{
+    client::{Client, ClientArgs, ClientError},
+    config::Config,
+    scaffold,
+};
+
+/// Options for `aoc fetch`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The year of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u16).range(2015..))]
+    pub year: u16,
+
+    /// The day of the puzzle (1-25).
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+
+    /// Also copy the input into the day module's `input.txt`.
+    #[arg(long)]
+    pub write: bool,
+
+    /// Print the input to stdout instead of its path.
+    #[arg(long)]
+    pub print: bool,
+
+    #[command(flatten)]
+    pub client: ClientArgs,
+}
+
+/// Why an input couldn't be fetched.
+#[derive(Debug)]
+pub enum FetchError {
+    Client(ClientError),
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Client(error) => error.fmt(f),
+            Self::Io { path, source } => write!(f, "failed to write {}: {source}", path.display()),
+        }
+    }
+}
+
+impl From<ClientError> for FetchError {
+    fn from(error: ClientError) -> Self {
+        Self::Client(error)
+    }
+}
+
+pub fn run(args: &Args, config: &Config) -> ExitCode {
+    let client = args.client.client(config);
+    let (path, input) = match fetch(&client, args.year, args.day) {
+        Ok(fetched) => fetched,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.write {
+        if let Err(error) = write_to_source(args.year, args.day, &input) {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if args.print {
+        print!("{input}");
+    } else {
+        println!("{}", path.display());
+    }
+    ExitCode::SUCCESS
+}
+
+/// The directory downloaded inputs are cached in, laid out so that it can be
+/// used as `AOC_INPUT_DIR`.
+pub fn inputs(cache_dir: &Path) -> InputDir {
+    InputDir::new(cache_dir.join("inputs"))
+}
+
+/// Returns the cached input for `day` of `year`, downloading it first if it
+/// isn't cached yet. A cached input is never downloaded again.
+pub fn fetch(client: &Client, year: u16, day: u8) -> Result<(PathBuf, String), FetchError> {
+    let path = inputs(client.cache_dir()).path(year, day);
+    if let Ok(input) = fs::read_to_string(&path) {
+        return Ok((path, input));
+    }
+
+    let input = client.get(&format!("/{year}/day/{day}/input"))?;
+
+    // Write to a temporary file first so that an interrupted download never
+    // leaves a truncated input behind in the cache.
+    let io_error = |source| FetchError::Io {
+        path: path.clone(),
+        source,
+    };
+    let partial = path.with_extension("txt.partial");
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(&partial, &input).map_err(io_error)?;
+    fs::rename(&partial, &path).map_err(io_error)?;
+
+    Ok((path, input))
+}
+
+fn write_to_source(year: u16, day: u8, input: &str) -> Result<(), String> {
+    let root = scaffold::find_root().map_err(|error| error.to_string())?;
+    let day_dir = root
+        .join("crates")
+        .join("core")
+        .join("src")
+        .join(format!("year_{year}"))
+        .join(format!("day_{day:02}"));
+    if !day_dir.is_dir() {
+        return Err(format!(
+            "{} doesn't exist; create it with `aoc new {year} {day}`",
+            day_dir.display()
+        ));
+    }
+
+    let path = day_dir.join("input.txt");
+    fs::write(&path, input).map_err(|error| format!("failed to write {}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::client::mock;
+
+    #[test]
+    fn test_fetch_caches() {
+        let server = mock::serve(vec![(200, "1 2 3\n"), (404, "not yet")]);
+        let cache = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let client = Client::new(server.url.clone(), Some("abc".to_string()), cache.clone())
+            .with_min_interval(Duration::ZERO);
+
+        let first = fetch(&client, 2016, 3).unwrap();
+        let second = fetch(&client, 2016, 3).unwrap();
+        let missing = fetch(&client, 2016, 4);
+        let cached = fs::read_to_string(cache.join("inputs/2016/day_03.txt")).unwrap();
+        let partial = cache.join("inputs/2016/day_04.txt.partial").exists();
+        fs::remove_dir_all(&cache).unwrap();
+
+        assert_eq!(first.1, "1 2 3\n");
+        assert_eq!(second, first);
+        assert_eq!(cached, "1 2 3\n");
+        assert!(matches!(
+            missing,
+            Err(FetchError::Client(ClientError::Status { status: 404, .. }))
+        ));
+        assert!(!partial);
+
+        let requests = server.requests.lock().unwrap();
+        let paths = requests
+            .iter()
+            .map(|request| request.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["/2016/day/3/input", "/2016/day/4/input"]);
+    }
+}
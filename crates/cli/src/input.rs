@@ -0,0 +1,180 @@
+use std::{
+    borrow::Cow,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use advent_of_code::{
+    input::{Embedded, FromEnv, InputDir, InputProvider, INPUT_DIR_VAR},
+    InputError,
+};
+
+use crate::config::Config;
+
+/// Loads the input for `day` of `year`.
+///
+/// - No `path` loads it through [`provider`].
+/// - `-` reads standard input until EOF.
+/// - A directory resolves to `<dir>/<year>/day_<dd>.txt`.
+/// - Anything else is read as a file.
+pub fn load(
+    year: u16,
+    day: u8,
+    path: Option<&Path>,
+    config: &Config,
+) -> Result<Cow<'static, str>, InputError> {
+    let Some(path) = path else {
+        return provider(None, config).load(year, day);
+    };
+
+    if path == Path::new("-") {
+        let mut input = String::new();
+        return io::stdin()
+            .read_to_string(&mut input)
+            .map(|_| Cow::Owned(input))
+            .map_err(|source| InputError::Io {
+                path: path.to_path_buf(),
+                source,
+            });
+    }
+
+    if path.is_dir() {
+        return InputDir::new(path).load(year, day);
+    }
+    read_file(path)
+}
+
+fn read_file(path: &Path) -> Result<Cow<'static, str>, InputError> {
+    fs::read_to_string(path)
+        .map(Cow::Owned)
+        .map_err(|source| InputError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Where to load every selected day's input from, in order of precedence:
+///
+/// 1. `dir`, from `--input`.
+/// 2. The directory named by `AOC_INPUT_DIR`.
+/// 3. The day's `input` in the config file.
+/// 4. The config file's `input-dir`.
+/// 5. The input embedded in the day's module.
+pub fn provider<'a>(dir: Option<&Path>, config: &'a Config) -> Box<dyn InputProvider + 'a> {
+    if let Some(dir) = dir {
+        return Box::new(InputDir::new(dir));
+    }
+    if std::env::var_os(INPUT_DIR_VAR).is_some_and(|dir| !dir.is_empty()) {
+        return Box::new(FromEnv);
+    }
+    Box::new(Configured(config))
+}
+
+/// The inputs named by the config file, falling back to the embedded ones.
+#[derive(Debug)]
+struct Configured<'a>(&'a Config);
+
+impl InputProvider for Configured<'_> {
+    fn load(&self, year: u16, day: u8) -> Result<Cow<'static, str>, InputError> {
+        if let Some(path) = self.0.day(year, day).and_then(|day| day.input.as_ref()) {
+            return read_file(path);
+        }
+        match &self.0.input_dir {
+            Some(dir) => InputDir::new(dir).load(year, day),
+            None => Embedded.load(year, day),
+        }
+    }
+
+    fn path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        if let Some(path) = self.0.day(year, day).and_then(|day| day.input.as_ref()) {
+            return Some(path.clone());
+        }
+        self.0
+            .input_dir
+            .as_ref()
+            .map(|dir| InputDir::new(dir).path(year, day))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use advent_of_code::{input, SolveError};
+
+    use super::*;
+
+    fn load(year: u16, day: u8, path: Option<&Path>) -> Result<Cow<'static, str>, InputError> {
+        super::load(year, day, path, &Config::default())
+    }
+
+    #[test]
+    fn test_embedded_input() {
+        // Either both load the same input, or neither has one to load.
+        assert_eq!(load(2015, 1, None).ok(), input::load(2015, 1).ok());
+        assert!(matches!(
+            load(2015, 26, None),
+            Err(InputError::Solve(SolveError::UnknownDay {
+                year: 2015,
+                day: 26
+            }))
+        ));
+        assert!(matches!(
+            load(1999, 1, None),
+            Err(InputError::Solve(SolveError::UnknownYear(1999)))
+        ));
+    }
+
+    #[test]
+    fn test_input_dir() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::create_dir_all(dir.join("2016")).unwrap();
+        fs::write(dir.join("2016").join("day_03.txt"), "1 2 3\n").unwrap();
+
+        let input = load(2016, 3, Some(&dir));
+        let missing = load(2016, 4, Some(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(input.unwrap(), "1 2 3\n");
+        assert!(
+            matches!(missing, Err(InputError::Io { path, .. }) if path.ends_with("2016/day_04.txt"))
+        );
+    }
+
+    #[test]
+    fn test_configured() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-config-{}", std::process::id()));
+        fs::create_dir_all(dir.join("inputs").join("2016")).unwrap();
+        fs::write(dir.join("inputs/2016/day_03.txt"), "1 2 3\n").unwrap();
+        fs::write(dir.join("small.txt"), "4 5 6\n").unwrap();
+        fs::write(
+            dir.join("aoc.toml"),
+            "input-dir = \"inputs\"\n[days.2016.4]\ninput = \"small.txt\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&dir.join("aoc.toml")).unwrap();
+        let from_dir = Configured(&config).load(2016, 3);
+        let overridden = Configured(&config).load(2016, 4);
+        let missing = Configured(&config).load(2016, 5);
+        let embedded = Configured(&Config::default()).load(2015, 1);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.path, Some(dir.join("aoc.toml")));
+        assert_eq!(
+            Configured(&config).path(2016, 3),
+            Some(dir.join("inputs/2016/day_03.txt"))
+        );
+        assert_eq!(
+            Configured(&config).path(2016, 4),
+            Some(dir.join("small.txt"))
+        );
+        assert_eq!(Configured(&Config::default()).path(2015, 1), None);
+
+        assert_eq!(from_dir.unwrap(), "1 2 3\n");
+        assert_eq!(overridden.unwrap(), "4 5 6\n");
+        assert!(
+            matches!(missing, Err(InputError::Io { path, .. }) if path == dir.join("inputs/2016/day_05.txt"))
+        );
+        assert_eq!(embedded.ok(), Embedded.load(2015, 1).ok());
+    }
+}
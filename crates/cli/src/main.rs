@@ -1,13 +1,92 @@
-use std::{fs, path::PathBuf};
+use std::{path::PathBuf, process::ExitCode, slice, time::Duration};
 
-use advent_of_code::solve;
-use clap::Parser;
+use advent_of_code::{
+    solution::{self, Phase},
+    solve,
+};
+use alloc_stats::Recorder;
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
+use config::Config;
+use report::{AllocRecord, Format, Record};
+
+mod alloc_stats;
+mod answers;
+mod bench;
+mod client;
+mod config;
+mod diff;
+mod fetch;
+mod input;
+mod report;
+mod run_all;
+mod sandbox;
+mod scaffold;
+mod submit;
+mod tui;
+mod verify;
+mod watch;
 
 /// Represents the command-line interface (CLI) options for the Advent of Code application.
 ///
+/// Without a subcommand, a single part of a single day is solved, see [`SolveArgs`].
+#[derive(Debug, Parser)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    subcommand_negates_reqs = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    solve: SolveArgs,
+
+    /// How to print results: a human-readable `text`, a `json` array, or `ndjson` records.
+    /// Defaults to the config file's `format`, or `text`.
+    #[arg(long, value_enum, global = true, env = "AOC_FORMAT")]
+    format: Option<Format>,
+
+    /// The config file to use instead of the first `aoc.toml` found in the current directory, its
+    /// ancestors, or `$XDG_CONFIG_HOME/aoc`.
+    #[arg(long, global = true, env = "AOC_CONFIG")]
+    config: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve every registered day against its embedded input, with timings.
+    RunAll(run_all::Args),
+    /// Solve the selected days and check the answers against a file of known answers.
+    Verify(verify::Args),
+    /// Time the selected days over several runs, save the results and compare them to a baseline.
+    Bench(bench::Args),
+    /// Run every variant of a day on its puzzle input and on generated ones, and report where
+    /// their answers disagree.
+    Diff(diff::Args),
+    /// Create a new day's module from a template and register it.
+    New(scaffold::Args),
+    /// Rebuild and re-run a day, and its example tests, whenever its source or input changes.
+    Watch(watch::Args),
+    /// Download a day's puzzle input into the cache, unless it's already there.
+    Fetch(fetch::Args),
+    /// Submit an answer, or the solved one, unless earlier guesses already rule it out.
+    Submit(submit::Args),
+    /// Browse every day with its last answer, status and timing, and run parts from a terminal UI.
+    Tui(tui::Args),
+    /// Solve one part against the input on stdin for `run-all --isolate`, and print the result as
+    /// JSON.
+    #[command(name = "__child", hide = true)]
+    Child(sandbox::ChildArgs),
+}
+
+/// The parameters for solving a single part of a single day.
+///
 /// This struct is used to parse and store the input parameters provided by the user when running the
 /// Advent of Code CLI application. It includes the year, day, and part of the challenge being solved,
-/// as well as the path to the input file that contains the puzzle data.
+/// as well as where to read the puzzle data from.
 ///
 /// # Attributes
 ///
@@ -20,28 +99,124 @@ use clap::Parser;
 /// - `part`: The part of the day's challenge to solve. Typically, there are two parts for each day's
 ///   challenge, with valid values being 1 or 2.
 ///
-/// - `input_file`: The path to the input file containing the puzzle data. This file is expected to be
-///   provided by the user and should match the format required for the specific challenge being solved.
-#[derive(Debug, Parser)]
-#[command(author, version, about, long_about = None)]
-struct Cli {
+/// - `input`: Where to read the puzzle data from. Without it, the input named by `AOC_INPUT_DIR` or
+///   the config file is used, or else the one embedded in the day's module; `-` reads stdin, and a
+///   directory resolves to `<dir>/<year>/day_<dd>.txt`.
+#[derive(Debug, clap::Args)]
+struct SolveArgs {
     /// The year of the Advent of Code challenge (e.g., 2024).
-    pub year: u16,
+    #[arg(required = true)]
+    pub year: Option<u16>,
 
     /// The day of the challenge within the Advent of Code event (1-25).
-    pub day: u8,
+    #[arg(required = true)]
+    pub day: Option<u8>,
 
     /// The part of the day's challenge to solve (1 or 2).
-    pub part: u8,
+    #[arg(required = true)]
+    pub part: Option<u8>,
+
+    /// The puzzle input: a file, `-` for stdin, or a directory holding `<year>/day_<dd>.txt`.
+    /// Defaults to `AOC_INPUT_DIR`, the config file, or the input embedded in the day's module.
+    pub input: Option<PathBuf>,
 
-    /// The path to the input file containing the puzzle data.
-    pub input_file: PathBuf,
+    /// Also report the peak memory, total bytes and number of allocations of parsing and the
+    /// part, and the most memory the process held in RAM, on stderr. Needs `aoc` built with the
+    /// `alloc-stats` feature.
+    #[arg(long)]
+    pub alloc_stats: bool,
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args = Cli::parse();
+    if args.command.is_some() && (args.solve.year.is_some() || args.solve.alloc_stats) {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "a subcommand can't be combined with <YEAR> <DAY> <PART>",
+            )
+            .exit();
+    }
+
+    let config = match Config::discover(args.config.as_deref()) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let format = args.format.or(config.format).unwrap_or_default();
+
+    match args.command {
+        Some(Command::RunAll(run_all)) => run_all::run(&run_all, format, &config),
+        Some(Command::Verify(verify)) => verify::run(&verify, format, &config),
+        Some(Command::Bench(bench)) => bench::run(&bench, format, &config),
+        Some(Command::Diff(diff)) => diff::run(&diff, format, &config),
+        Some(Command::New(new)) => scaffold::run(&new),
+        Some(Command::Watch(watch)) => watch::run(&watch, &config),
+        Some(Command::Fetch(fetch)) => fetch::run(&fetch, &config),
+        Some(Command::Submit(submit)) => submit::run(&submit, &config),
+        Some(Command::Tui(tui)) => tui::run(&tui, &config),
+        Some(Command::Child(child)) => sandbox::child(&child),
+        None => solve_one(args.solve, format, &config),
+    }
+}
+
+fn solve_one(args: SolveArgs, format: Format, config: &Config) -> ExitCode {
+    let (Some(year), Some(day), Some(part)) = (args.year, args.day, args.part) else {
+        unreachable!("clap requires every argument without a subcommand");
+    };
+
+    if args.alloc_stats && !alloc_stats::ENABLED {
+        eprintln!("error: `--alloc-stats` needs aoc built with the `alloc-stats` feature");
+        return ExitCode::FAILURE;
+    }
+
+    let input = match input::load(year, day, args.input.as_deref(), config) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let entry = solution::find(year, day);
+    let mut alloc = args.alloc_stats.then(Recorder::default);
+    let (result, phases) = match entry {
+        Some(entry) => {
+            let mut timed = match &mut alloc {
+                Some(recorder) => entry.run_observed(&input, &[part], recorder),
+                None => entry.run(&input, &[part]),
+            };
+            let part = timed.parts.swap_remove(0);
+            (part.result, (timed.parse, part.elapsed))
+        }
+        None => (
+            solve(year, day, part, &input),
+            (Duration::ZERO, Duration::ZERO),
+        ),
+    };
 
-    let input = fs::read_to_string(args.input_file).unwrap();
+    let title = entry.map(|entry| entry.title);
+    let mut record = Record::new((year, day, part), title, &result, phases);
+    record.alloc = alloc.as_ref().map(|alloc| AllocRecord {
+        parse: alloc.get(Phase::Parse),
+        solve: alloc.get(Phase::Part(part)),
+    });
+    match (format, &result) {
+        (Format::Text, Ok(answer)) => println!("{answer}"),
+        (Format::Text, Err(error)) => eprintln!("error: {error}"),
+        (Format::Json, _) => report::print_json(slice::from_ref(&record)),
+        (Format::Ndjson, _) => report::print_ndjson(slice::from_ref(&record)),
+    }
+    if let (Format::Text, Some(alloc)) = (format, &record.alloc) {
+        eprintln!("parse   {}", alloc_stats::describe(&alloc.parse));
+        eprintln!("part {part}  {}", alloc_stats::describe(&alloc.solve));
+    }
 
-    println!("{}", solve(args.year, args.day, args.part, &input))
+    if result.is_ok() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
 }
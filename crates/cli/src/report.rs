@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use advent_of_code::{Answer, SolveError};
+use serde::{Deserialize, Serialize};
+
+use crate::alloc_stats::AllocStats;
+
+/// How results are written to stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// Human-readable output.
+    #[default]
+    Text,
+    /// A single JSON array of records.
+    Json,
+    /// One JSON record per line.
+    Ndjson,
+}
+
+/// The machine-readable result of running one part of one day.
+///
+/// The field names and their meaning are part of the CLI's interface; add
+/// fields rather than renaming or repurposing existing ones.
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// `None` if the day isn't registered.
+    pub title: Option<&'a str>,
+    pub answer: Option<AnswerValue<'a>>,
+    /// One of `signed`, `unsigned`, `text` or `art`.
+    pub answer_type: Option<&'static str>,
+    /// `parse_ns + solve_ns`.
+    pub duration_ns: u64,
+    /// How long parsing the input took. Both parts of a day share a single
+    /// parse, so their records hold the same time here.
+    pub parse_ns: u64,
+    /// How long solving this part took once the input was parsed.
+    pub solve_ns: u64,
+    pub error: Option<ErrorRecord>,
+    /// Only set with `--alloc-stats`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocRecord>,
+}
+
+/// What parsing the input and solving the part allocated.
+#[derive(Debug, Serialize)]
+pub struct AllocRecord {
+    /// Shared by both parts of a day, like `parse_ns`.
+    pub parse: AllocStats,
+    pub solve: AllocStats,
+}
+
+/// An answer as its natural JSON value: a number or a string.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum AnswerValue<'a> {
+    Signed(i64),
+    Unsigned(u64),
+    Text(&'a str),
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorRecord {
+    /// One of `unknown_year`, `unknown_day`, `unknown_part`, `malformed_input`, `no_solution`,
+    /// `timeout`, `panic`, `signal`, `exit`, `spawn` or `out_of_memory`.
+    pub kind: String,
+    pub message: String,
+    /// Only set for `malformed_input`, 1-based.
+    pub line: Option<usize>,
+    /// Only set for `malformed_input`, 1-based.
+    pub column: Option<usize>,
+}
+
+impl<'a> Record<'a> {
+    pub fn new(
+        (year, day, part): (u16, u8, u8),
+        title: Option<&'a str>,
+        result: &'a Result<Answer, SolveError>,
+        (parse, solve): (Duration, Duration),
+    ) -> Self {
+        let nanos = |elapsed: Duration| u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        let (answer, answer_type, error) = match result {
+            Ok(answer) => (Some(AnswerValue::from(answer)), Some(answer.kind()), None),
+            Err(error) => (None, None, Some(ErrorRecord::from(error))),
+        };
+
+        Self {
+            year,
+            day,
+            part,
+            title,
+            answer,
+            answer_type,
+            duration_ns: nanos(parse + solve),
+            parse_ns: nanos(parse),
+            solve_ns: nanos(solve),
+            error,
+            alloc: None,
+        }
+    }
+}
+
+impl<'a> From<&'a Answer> for AnswerValue<'a> {
+    fn from(answer: &'a Answer) -> Self {
+        match answer {
+            Answer::Signed(value) => Self::Signed(*value),
+            Answer::Unsigned(value) => Self::Unsigned(*value),
+            Answer::Text(value) | Answer::Art(value) => Self::Text(value),
+        }
+    }
+}
+
+impl From<&SolveError> for ErrorRecord {
+    fn from(error: &SolveError) -> Self {
+        let (line, column) = match *error {
+            SolveError::MalformedInput { line, column, .. } => (Some(line), Some(column)),
+            _ => (None, None),
+        };
+
+        Self {
+            kind: error.kind().to_string(),
+            message: error.to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+/// Writes `records` to stdout as a single JSON array.
+pub fn print_json(records: &[impl Serialize]) {
+    println!("{}", serde_json::to_string_pretty(records).unwrap());
+}
+
+/// Writes `records` to stdout, one JSON object per line.
+pub fn print_ndjson(records: &[impl Serialize]) {
+    for record in records {
+        println!("{}", serde_json::to_string(record).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_shape() {
+        let ok = Ok(Answer::Unsigned(42));
+        let record = Record::new(
+            (2015, 1, 2),
+            Some("Not Quite Lisp"),
+            &ok,
+            (Duration::from_micros(1), Duration::from_micros(2)),
+        );
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"year":2015,"day":1,"part":2,"title":"Not Quite Lisp","answer":42,"answer_type":"unsigned","duration_ns":3000,"parse_ns":1000,"solve_ns":2000,"error":null}"#
+        );
+
+        let input = "(()x";
+        let err = Err(SolveError::malformed(
+            input,
+            &input[3..],
+            "expected `(` or `)`",
+        ));
+        let record = Record::new(
+            (2015, 1, 1),
+            Some("Not Quite Lisp"),
+            &err,
+            (Duration::ZERO, Duration::ZERO),
+        );
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"year":2015,"day":1,"part":1,"title":"Not Quite Lisp","answer":null,"answer_type":null,"duration_ns":0,"parse_ns":0,"solve_ns":0,"error":{"kind":"malformed_input","message":"malformed input at 1:4: expected `(` or `)`","line":1,"column":4}}"#
+        );
+    }
+}
@@ -0,0 +1,697 @@
+use std::{
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process::ExitCode,
+    sync::{
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use advent_of_code::{
+    input::InputProvider,
+    solution::{self, Entry, Observer, Phase, Timed, TimedPart},
+    Answer, InputError, SolveError,
+};
+use rayon::prelude::*;
+
+use crate::{
+    alloc_stats::{self, AllocStats, Recorder},
+    config::{self, Config},
+    input,
+    report::{self, AllocRecord, Format, Record},
+    sandbox::{self, Sandbox},
+};
+
+/// Options for `aoc run-all`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Solve the days in parallel on the rayon thread pool.
+    #[arg(long)]
+    pub parallel: bool,
+
+    /// How many threads `--parallel` uses. Defaults to the config file's
+    /// `threads`, or one per CPU.
+    #[arg(long, env = "AOC_THREADS")]
+    pub threads: Option<usize>,
+
+    /// Also report the peak memory, total bytes and number of allocations of
+    /// parsing and each part. Needs `aoc` built with the `alloc-stats`
+    /// feature, and can't be combined with `--parallel`.
+    #[arg(long, conflicts_with = "parallel")]
+    pub alloc_stats: bool,
+
+    /// How long each part may take, parsing included, such as `500ms`, `10s`
+    /// or `2m`. A part that takes longer is reported as timed out, but can't
+    /// be stopped: it keeps a CPU busy until `aoc` exits, which may slow the
+    /// days after it, and what it allocates from then on isn't counted by
+    /// `--alloc-stats`. Use `--isolate` to have it killed instead. Defaults
+    /// to the config file's `timeout` for the day, or its top-level
+    /// `timeout`.
+    #[arg(long, env = "AOC_TIMEOUT", value_parser = config::parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// Run each part in a child process of its own, so that a panic, a crash
+    /// or running out of memory is reported as the part's error instead of
+    /// ending the run. A part over its `--timeout` is then killed.
+    #[arg(long)]
+    pub isolate: bool,
+
+    /// The most memory each child process of `--isolate` may use, such as
+    /// `512MiB` or `2GiB`. Only supported on Unix.
+    #[arg(long, requires = "isolate", value_parser = sandbox::parse_size)]
+    pub memory_limit: Option<u64>,
+}
+
+/// Which days to run, and where to read their inputs from.
+#[derive(Debug, clap::Args)]
+pub struct Selection {
+    /// Only run the days of this year. Defaults to the config file's `year`.
+    #[arg(long, env = "AOC_YEAR")]
+    pub year: Option<u16>,
+
+    /// Only run these days, either a single day or a range such as `1..=10` or `5..`.
+    /// A day the config file skips is only run when it's selected on its own.
+    #[arg(long, value_parser = parse_days)]
+    pub days: Option<RangeInclusive<u8>>,
+
+    /// A directory holding `<year>/day_<dd>.txt` inputs. Defaults to
+    /// `AOC_INPUT_DIR`, the inputs named by the config file, or the inputs
+    /// embedded in the day modules.
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+}
+
+/// The outcome of running one part of a day.
+pub type PartRun = TimedPart;
+
+/// How [`run_day`] runs the parts of a day.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options {
+    /// Record what each phase allocated.
+    pub alloc_stats: bool,
+    /// How long each part may take, see [`run_within`].
+    pub budget: Option<Duration>,
+    /// Run each part in a child process, see [`Sandbox::run`].
+    pub sandbox: Option<Sandbox>,
+}
+
+/// Both parts of a day, run against its input.
+#[derive(Debug)]
+pub struct DayRun {
+    pub entry: &'static Entry,
+    /// How long parsing the input took, once for both parts.
+    pub parse: Duration,
+    pub parts: [PartRun; 2],
+    /// What parsing and each part allocated, with `--alloc-stats`.
+    pub alloc: Option<Recorder>,
+}
+
+impl DayRun {
+    fn elapsed(&self) -> Duration {
+        self.parse + self.parts.iter().map(|part| part.elapsed).sum::<Duration>()
+    }
+}
+
+pub fn run(args: &Args, format: Format, config: &Config) -> ExitCode {
+    let (runs, input_errors) = match run_selected(args, config) {
+        Ok(runs) => runs,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match format {
+        Format::Text => print_table(&runs),
+        Format::Json => report::print_json(&records(&runs)),
+        Format::Ndjson => report::print_ndjson(&records(&runs)),
+    }
+
+    if input_errors == 0
+        && runs
+            .iter()
+            .flat_map(|run| &run.parts)
+            .all(|part| part.result.is_ok())
+    {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// The registered days matching `--year` and `--days`, falling back to the
+/// config file's `year`. Days the config file skips are left out, unless
+/// `--days` names that single day.
+pub fn select(selection: &Selection, config: &Config) -> Result<Vec<&'static Entry>, String> {
+    let year = selection.year.or(config.year);
+    if let Some(year) = year {
+        if solution::solutions_for(year).next().is_none() {
+            return Err(SolveError::UnknownYear(year).to_string());
+        }
+    }
+
+    let single = selection
+        .days
+        .as_ref()
+        .is_some_and(|days| days.start() == days.end());
+    let entries = solution::solutions()
+        .filter(|entry| year.is_none_or(|year| entry.year == year))
+        .filter(|entry| {
+            selection
+                .days
+                .as_ref()
+                .is_none_or(|days| days.contains(&entry.day))
+        })
+        .filter(|entry| single || !config.skips(entry.year, entry.day))
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return Err("no registered day matches the selection".to_string());
+    }
+
+    Ok(entries)
+}
+
+/// Runs every day selected by `args`.
+///
+/// Days whose input can't be loaded are reported on stderr and skipped; how
+/// many there were is returned alongside the runs.
+pub fn run_selected(args: &Args, config: &Config) -> Result<(Vec<DayRun>, usize), String> {
+    if args.alloc_stats && !alloc_stats::ENABLED {
+        return Err("`--alloc-stats` needs aoc built with the `alloc-stats` feature".to_string());
+    }
+    let entries = select(&args.selection, config)?;
+    let inputs = input::provider(args.selection.input.as_deref(), config);
+    let sandbox = args.isolate.then_some(Sandbox {
+        memory_limit: args.memory_limit,
+    });
+    let run_day = |entry: &'static Entry| {
+        let options = Options {
+            alloc_stats: args.alloc_stats,
+            budget: args
+                .timeout
+                .or_else(|| config.timeout(entry.year, entry.day)),
+            sandbox,
+        };
+        run_day(entry, &*inputs, options)
+    };
+
+    let runs = if args.parallel {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(args.threads.or(config.threads).unwrap_or(0))
+            .build()
+            .map_err(|error| format!("failed to start the thread pool: {error}"))?;
+        pool.install(|| entries.into_par_iter().map(run_day).collect::<Vec<_>>())
+    } else {
+        entries.into_iter().map(run_day).collect()
+    };
+
+    let mut input_errors = 0;
+    let runs = runs
+        .into_iter()
+        .filter_map(|run| {
+            run.map_err(|error| {
+                eprintln!("error: {error}");
+                input_errors += 1;
+            })
+            .ok()
+        })
+        .collect();
+
+    Ok((runs, input_errors))
+}
+
+/// Runs both parts of `entry` against its input from `inputs`, as set out by
+/// `options`, see [`run_within`] and [`Sandbox::run`].
+pub fn run_day(
+    entry: &'static Entry,
+    inputs: &dyn InputProvider,
+    options: Options,
+) -> Result<DayRun, InputError> {
+    let input = inputs.load(entry.year, entry.day)?;
+    let mut alloc = options.alloc_stats.then(Recorder::default);
+    let timed = match options.sandbox {
+        Some(sandbox) => sandbox.run(entry, &input, options.budget, alloc.as_mut()),
+        None => run_within(entry, &input, options.budget, alloc.as_mut()),
+    };
+
+    Ok(DayRun {
+        entry,
+        parse: timed.parse,
+        parts: timed.parts.try_into().expect("both parts were run"),
+        alloc,
+    })
+}
+
+/// Runs both parts of `entry` against `input` on a worker thread, which
+/// parses the input once for both. A part that takes longer than `budget`,
+/// parsing included for the first one, is given up on with
+/// [`SolveError::Timeout`], and one that panics fails with
+/// [`SolveError::Panic`].
+///
+/// The parts left after either run on a new worker, which parses the input
+/// again; the parse time and allocations of the first parse are the ones
+/// reported. As threads can't be stopped, a worker that was given up on keeps
+/// running in the background until the process exits, and what it allocates
+/// from then on isn't counted.
+fn run_within(
+    entry: &'static Entry,
+    input: &str,
+    budget: Option<Duration>,
+    mut alloc: Option<&mut Recorder>,
+) -> Timed {
+    let input = Arc::<str>::from(input);
+    let mut parse = None;
+    let mut parts = Vec::new();
+    let mut remaining = vec![1, 2];
+
+    while !remaining.is_empty() {
+        let (sender, receiver) = mpsc::channel();
+        let ticket = alloc_stats::start_worker();
+        let mut relay = Relay {
+            sender,
+            alloc_stats: alloc.is_some(),
+            started: Instant::now(),
+        };
+        let worker_input = Arc::clone(&input);
+        let worker_parts = remaining.clone();
+        thread::Builder::new()
+            .name(format!("{} day {}", entry.year, entry.day))
+            .spawn(move || {
+                alloc_stats::enter_worker(ticket);
+                let run = panic::catch_unwind(AssertUnwindSafe(|| {
+                    entry.run_observed(&worker_input, &worker_parts, &mut relay)
+                }));
+                if let Err(payload) = run {
+                    let _ = relay
+                        .sender
+                        .send(Event::Panicked(sandbox::panic_message(&*payload, None)));
+                }
+            })
+            .expect("failed to spawn a worker thread");
+
+        let mut deadline = budget.map(|budget| Instant::now() + budget);
+        let mut first_part = true;
+        let mut current = None;
+        loop {
+            let event = match deadline {
+                Some(deadline) => {
+                    receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match event {
+                Ok(Event::Started(Phase::Part(part))) => {
+                    current = Some(part);
+                    if !first_part {
+                        deadline = budget.map(|budget| Instant::now() + budget);
+                    }
+                    first_part = false;
+                }
+                Ok(Event::Started(Phase::Parse)) => {}
+                Ok(Event::Ended(phase, elapsed, stats)) => {
+                    if phase == Phase::Parse {
+                        if parse.is_some() {
+                            continue;
+                        }
+                        parse = Some(elapsed);
+                    }
+                    if let (Some(alloc), Some(stats)) = (alloc.as_deref_mut(), stats) {
+                        alloc.phases.push((phase, stats));
+                    }
+                }
+                Ok(Event::Solved(part)) => {
+                    remaining.retain(|&left| left != part.part);
+                    parts.push(part);
+                    current = None;
+                    // Whatever the worker does from here on, such as dropping
+                    // the parsed input, is no part's business.
+                    if remaining.is_empty() {
+                        alloc_stats::abandon_worker();
+                        break;
+                    }
+                }
+                Ok(Event::Panicked(message)) => {
+                    // A panic while parsing fails every part left.
+                    let failed = current.map_or_else(|| remaining.clone(), |part| vec![part]);
+                    give_up(
+                        &mut parts,
+                        &mut remaining,
+                        &failed,
+                        SolveError::Panic(message),
+                    );
+                    break;
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let budget = budget.expect("only a budget times out");
+                    alloc_stats::abandon_worker();
+                    let Some(failed) = current.or_else(|| remaining.first().copied()) else {
+                        break;
+                    };
+                    give_up(
+                        &mut parts,
+                        &mut remaining,
+                        &[failed],
+                        SolveError::Timeout(budget),
+                    );
+                    parts.last_mut().unwrap().elapsed = budget;
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    assert!(remaining.is_empty(), "the worker reports every part");
+                    break;
+                }
+            }
+        }
+    }
+
+    parts.sort_by_key(|part| part.part);
+    Timed {
+        parse: parse.unwrap_or_default(),
+        parts,
+    }
+}
+
+/// Fails each of `failed` with `error`, instead of waiting on the worker.
+fn give_up(parts: &mut Vec<TimedPart>, remaining: &mut Vec<u8>, failed: &[u8], error: SolveError) {
+    for &part in failed {
+        parts.push(TimedPart {
+            part,
+            result: Err(error.clone()),
+            elapsed: Duration::ZERO,
+        });
+    }
+    remaining.retain(|part| !failed.contains(part));
+}
+
+/// What the worker of [`run_within`] tells the thread waiting on it.
+enum Event {
+    Started(Phase),
+    /// How long the phase took, and what it allocated with `--alloc-stats`.
+    Ended(Phase, Duration, Option<AllocStats>),
+    Solved(TimedPart),
+    Panicked(String),
+}
+
+/// Passes the phases of the worker of [`run_within`] on as they happen,
+/// measuring them the way [`Recorder`] does.
+struct Relay {
+    sender: Sender<Event>,
+    alloc_stats: bool,
+    started: Instant,
+}
+
+// Nobody is listening anymore once the worker was given up on.
+impl Observer for Relay {
+    fn start(&mut self, phase: Phase) {
+        let _ = self.sender.send(Event::Started(phase));
+        if self.alloc_stats {
+            alloc_stats::reset();
+        }
+        self.started = Instant::now();
+    }
+
+    fn end(&mut self, phase: Phase) {
+        let elapsed = self.started.elapsed();
+        let stats = self.alloc_stats.then(alloc_stats::snapshot);
+        let _ = self.sender.send(Event::Ended(phase, elapsed, stats));
+    }
+
+    fn solved(&mut self, part: &TimedPart) {
+        let _ = self.sender.send(Event::Solved(part.clone()));
+    }
+}
+
+/// One record per part of every run.
+pub fn records(runs: &[DayRun]) -> Vec<Record<'_>> {
+    runs.iter()
+        .flat_map(|run| {
+            run.parts.iter().map(|part| {
+                let mut record = Record::new(
+                    (run.entry.year, run.entry.day, part.part),
+                    Some(run.entry.title),
+                    &part.result,
+                    (run.parse, part.elapsed),
+                );
+                record.alloc = run.alloc.as_ref().map(|alloc| AllocRecord {
+                    parse: alloc.get(Phase::Parse),
+                    solve: alloc.get(Phase::Part(part.part)),
+                });
+                record
+            })
+        })
+        .collect()
+}
+
+fn print_table(runs: &[DayRun]) {
+    let title_width = column_width(runs.iter().map(|run| run.entry.title.len()), "Title");
+    let answer_width = column_width(
+        runs.iter()
+            .flat_map(|run| &run.parts)
+            .map(|part| answer_cell(part).len()),
+        "Part 1",
+    );
+
+    for runs in runs.chunk_by(|a, b| a.entry.year == b.entry.year) {
+        println!("{}", runs[0].entry.year);
+        println!(
+            "{:>3}  {:<title_width$}  {:>10}  {:<answer_width$}  {:>10}  {:<answer_width$}  {:>10}",
+            "Day", "Title", "Parse", "Part 1", "Time", "Part 2", "Time",
+        );
+
+        for run in runs {
+            print!(
+                "{:>3}  {:<title_width$}  {:>10}",
+                run.entry.day,
+                run.entry.title,
+                format!("{:.2?}", run.parse),
+            );
+            for part in &run.parts {
+                print!(
+                    "  {:<answer_width$}  {:>10}",
+                    answer_cell(part),
+                    format!("{:.2?}", part.elapsed),
+                );
+            }
+            println!();
+
+            if let Some(alloc) = &run.alloc {
+                let phases = [("parse", Phase::Parse)]
+                    .into_iter()
+                    .chain(run.parts.iter().map(|part| match part.part {
+                        1 => ("part 1", Phase::Part(1)),
+                        _ => ("part 2", Phase::Part(2)),
+                    }));
+                for (label, phase) in phases {
+                    let stats = alloc_stats::describe(&alloc.get(phase));
+                    println!("     {label:<6}  {stats}");
+                }
+            }
+
+            for part in &run.parts {
+                match &part.result {
+                    Ok(Answer::Art(art)) => {
+                        for line in art.lines() {
+                            println!("     {line}");
+                        }
+                    }
+                    Err(error) => println!("     part {}: {error}", part.part),
+                    Ok(_) => {}
+                }
+            }
+        }
+
+        let total = runs.iter().map(DayRun::elapsed).sum::<Duration>();
+        println!("Total: {total:.2?}");
+        println!();
+    }
+}
+
+fn column_width(widths: impl Iterator<Item = usize>, header: &str) -> usize {
+    widths.max().unwrap_or_default().max(header.len())
+}
+
+fn answer_cell(part: &PartRun) -> String {
+    match &part.result {
+        Ok(Answer::Art(_)) => "(see below)".to_string(),
+        Ok(answer) => answer.to_string(),
+        Err(SolveError::Timeout(_)) => "timeout".to_string(),
+        Err(SolveError::Panic(_)) => "panic".to_string(),
+        Err(SolveError::OutOfMemory { .. }) => "out of memory".to_string(),
+        Err(SolveError::Signal(_) | SolveError::Exit(_)) => "crashed".to_string(),
+        Err(_) => "error".to_string(),
+    }
+}
+
+/// Parses `N`, `A..=B`, `A..B`, `A..` or `..=B` into an inclusive range of days.
+fn parse_days(days: &str) -> Result<RangeInclusive<u8>, String> {
+    let day = |day: &str, default: u8| {
+        if day.is_empty() {
+            return Ok(default);
+        }
+        day.parse::<u8>()
+            .map_err(|error| format!("invalid day `{day}`: {error}"))
+    };
+
+    if let Some((start, end)) = days.split_once("..=") {
+        Ok(day(start, 1)?..=day(end, 25)?)
+    } else if let Some((start, end)) = days.split_once("..") {
+        let end = day(end, 26)?
+            .checked_sub(1)
+            .ok_or_else(|| format!("`{days}` is an empty range"))?;
+        Ok(day(start, 1)?..=end)
+    } else {
+        let day = day(days, 0)?;
+        Ok(day..=day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use advent_of_code::input::InputDir;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("7"), Ok(7..=7));
+        assert_eq!(parse_days("1..=10"), Ok(1..=10));
+        assert_eq!(parse_days("1..10"), Ok(1..=9));
+        assert_eq!(parse_days("20.."), Ok(20..=25));
+        assert_eq!(parse_days("..=3"), Ok(1..=3));
+        assert!(parse_days("..0").is_err());
+        assert!(parse_days("x").is_err());
+    }
+
+    #[test]
+    fn test_run_day() {
+        let dir = std::env::temp_dir().join(format!("aoc-run-day-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("2015")).unwrap();
+        std::fs::write(dir.join("2015").join("day_01.txt"), "()())").unwrap();
+        let inputs = InputDir::new(&dir);
+
+        let entry = solution::find(2015, 1).unwrap();
+        let run = run_day(entry, &inputs, Options::default()).unwrap();
+        assert_eq!(run.parts[0].result, Ok(Answer::Signed(-1)));
+        assert_eq!(run.parts[1].result, Ok(Answer::Signed(5)));
+        assert!(run.alloc.is_none());
+
+        let alloc_stats = Options {
+            alloc_stats: true,
+            ..Options::default()
+        };
+        let run = run_day(entry, &inputs, alloc_stats).unwrap();
+        let alloc = run.alloc.unwrap();
+        assert_eq!(
+            alloc
+                .phases
+                .iter()
+                .map(|&(phase, _)| phase)
+                .collect::<Vec<_>>(),
+            [Phase::Parse, Phase::Part(1), Phase::Part(2)]
+        );
+
+        let budget = Options {
+            budget: Some(Duration::from_secs(60)),
+            ..alloc_stats
+        };
+        let run = run_day(entry, &inputs, budget);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let run = run.unwrap();
+        assert_eq!(run.parts[0].result, Ok(Answer::Signed(-1)));
+        assert_eq!(run.parts[1].result, Ok(Answer::Signed(5)));
+        assert_eq!(run.alloc.unwrap().phases.len(), 3);
+    }
+
+    #[test]
+    fn test_run_within() {
+        static PARSES: AtomicUsize = AtomicUsize::new(0);
+
+        // Sleeps through part 1 of a `slow` input, and panics on part 2
+        // unless the input is `linger`, which it then sleeps on after both.
+        fn phases(input: &str, parts: &[u8], observer: &mut dyn Observer) -> Timed {
+            observer.start(Phase::Parse);
+            PARSES.fetch_add(1, Ordering::Relaxed);
+            thread::sleep(Duration::from_millis(1));
+            observer.end(Phase::Parse);
+
+            let parts = parts
+                .iter()
+                .map(|&part| {
+                    observer.start(Phase::Part(part));
+                    match (part, input) {
+                        (1, "slow") => thread::sleep(Duration::from_secs(60)),
+                        (2, "linger") => {}
+                        (2, _) => panic!("boom"),
+                        _ => {}
+                    }
+                    observer.end(Phase::Part(part));
+                    let part = TimedPart {
+                        part,
+                        result: Ok(Answer::Unsigned(part.into())),
+                        elapsed: Duration::ZERO,
+                    };
+                    observer.solved(&part);
+                    part
+                })
+                .collect();
+            if input == "linger" {
+                thread::sleep(Duration::from_secs(60));
+            }
+            Timed {
+                parse: Duration::from_millis(1),
+                parts,
+            }
+        }
+
+        let entry = Entry {
+            phases,
+            ..*solution::find(2015, 1).unwrap()
+        };
+        let entry: &'static Entry = Box::leak(Box::new(entry));
+
+        // Long enough to report the panic, backtrace included.
+        let budget = Duration::from_secs(1);
+        let mut recorder = Recorder::default();
+        let timed = run_within(entry, "slow", Some(budget), Some(&mut recorder));
+        assert!(timed.parse >= Duration::from_millis(1));
+        assert_eq!(timed.parts[0].result, Err(SolveError::Timeout(budget)));
+        assert_eq!(timed.parts[0].elapsed, budget);
+        assert_eq!(
+            timed.parts[1].result,
+            Err(SolveError::Panic("panicked: boom".to_string()))
+        );
+        assert_eq!(
+            recorder
+                .phases
+                .iter()
+                .map(|&(phase, _)| phase)
+                .collect::<Vec<_>>(),
+            [Phase::Parse]
+        );
+
+        let parses = PARSES.load(Ordering::Relaxed);
+        let timed = run_within(entry, "", None, None);
+        assert_eq!(PARSES.load(Ordering::Relaxed), parses + 1);
+        assert_eq!(timed.parts[0].result, Ok(Answer::Unsigned(1)));
+        assert_eq!(
+            timed.parts[1].result,
+            Err(SolveError::Panic("panicked: boom".to_string()))
+        );
+
+        // Done with once both parts are, however long the worker lingers.
+        let timed = run_within(entry, "linger", Some(budget), None);
+        assert_eq!(timed.parts[0].result, Ok(Answer::Unsigned(1)));
+        assert_eq!(timed.parts[1].result, Ok(Answer::Unsigned(2)));
+    }
+}
@@ -0,0 +1,548 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    env,
+    io::{self, Read, Write},
+    panic::{self, AssertUnwindSafe},
+    process::{Command, ExitCode, ExitStatus, Stdio},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use advent_of_code::{
+    solution::{self, Entry, Phase, Timed, TimedPart},
+    Answer, SolveError,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::alloc_stats::{AllocStats, Recorder};
+
+/// How often a child is checked on while it runs under a budget.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// What Rust's default allocation error handler prints before aborting.
+const ALLOC_FAILED: &str = "memory allocation of ";
+
+thread_local! {
+    /// Where the last panic on this thread happened, set by the child's panic
+    /// hook.
+    static PANIC_LOCATION: Cell<Option<String>> = const { Cell::new(None) };
+}
+
+/// Options for the hidden `aoc __child`, which `run-all --isolate` runs once
+/// per part with the input on stdin, and which prints a [`Report`] on stdout.
+#[derive(Debug, clap::Args)]
+pub struct ChildArgs {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+
+    /// The most address space the process may use, in bytes.
+    #[arg(long)]
+    pub memory_limit: Option<u64>,
+
+    /// Record what parsing and the part allocated.
+    #[arg(long)]
+    pub alloc_stats: bool,
+}
+
+/// Runs each part of a day in a child process of its own, so that a panic,
+/// a crash or running out of memory only takes that part down.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sandbox {
+    /// The most address space each child may use, in bytes.
+    pub memory_limit: Option<u64>,
+}
+
+/// What a child prints on stdout once its part is solved.
+#[derive(Debug, Serialize, Deserialize)]
+struct Report {
+    parse_ns: u64,
+    solve_ns: u64,
+    result: Result<Answer, Failure>,
+    /// What parsing and the part allocated, with `--alloc-stats`.
+    alloc: Option<[AllocStats; 2]>,
+}
+
+/// A [`SolveError`] on its way from a child.
+#[derive(Debug, Serialize, Deserialize)]
+struct Failure {
+    kind: String,
+    message: String,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl Sandbox {
+    /// Runs each part of `entry` against `input` in a child process of its
+    /// own, one after the other, and kills a child that takes longer than
+    /// `budget`, process startup included.
+    ///
+    /// A panic, a signal, an exit without a report or running out of memory
+    /// is reported as the part's error. Each part parses the input itself,
+    /// and the parse time of the first one to report is used.
+    pub fn run(
+        self,
+        entry: &Entry,
+        input: &str,
+        budget: Option<Duration>,
+        mut alloc: Option<&mut Recorder>,
+    ) -> Timed {
+        let mut parse = None;
+
+        let parts = [1, 2]
+            .into_iter()
+            .map(|part| {
+                let command = self.command(entry, part, alloc.is_some());
+                match self.run_command(command, input, budget) {
+                    Ok(report) => {
+                        parse.get_or_insert(Duration::from_nanos(report.parse_ns));
+                        if let (Some(alloc), Some([parse, solve])) =
+                            (alloc.as_deref_mut(), report.alloc)
+                        {
+                            alloc.phases.push((Phase::Parse, parse));
+                            alloc.phases.push((Phase::Part(part), solve));
+                        }
+
+                        TimedPart {
+                            part,
+                            result: report
+                                .result
+                                .map_err(|failure| failure.into_error(entry, part)),
+                            elapsed: Duration::from_nanos(report.solve_ns),
+                        }
+                    }
+                    Err(error) => TimedPart {
+                        part,
+                        elapsed: match error {
+                            SolveError::Timeout(budget) => budget,
+                            _ => Duration::ZERO,
+                        },
+                        result: Err(error),
+                    },
+                }
+            })
+            .collect();
+
+        Timed {
+            parse: parse.unwrap_or_default(),
+            parts,
+        }
+    }
+
+    /// The command running `part` of `entry` in a copy of this executable.
+    fn command(self, entry: &Entry, part: u8, alloc_stats: bool) -> Command {
+        let mut command = Command::new(env::current_exe().unwrap_or_else(|_| "aoc".into()));
+        command.args([
+            "__child".to_string(),
+            entry.year.to_string(),
+            entry.day.to_string(),
+            part.to_string(),
+        ]);
+        if let Some(limit) = self.memory_limit {
+            command.arg(format!("--memory-limit={limit}"));
+        }
+        if alloc_stats {
+            command.arg("--alloc-stats");
+        }
+        command
+    }
+
+    /// Runs `command` as the child solving a part, and returns the report it
+    /// printed, or why there isn't one.
+    fn run_command(
+        self,
+        command: Command,
+        input: &str,
+        budget: Option<Duration>,
+    ) -> Result<Report, SolveError> {
+        let output = run_child(command, input, budget)
+            .map_err(|error| SolveError::Spawn(error.to_string()))?;
+        self.report(output, budget)
+    }
+
+    /// The report a child printed, or why it didn't print one.
+    fn report(self, output: Output, budget: Option<Duration>) -> Result<Report, SolveError> {
+        // Whatever else the part printed is passed on.
+        io::stderr().write_all(&output.stderr).ok();
+
+        let Some(status) = output.status else {
+            return Err(SolveError::Timeout(budget.unwrap_or_default()));
+        };
+        if !status.success() && String::from_utf8_lossy(&output.stderr).contains(ALLOC_FAILED) {
+            return Err(SolveError::OutOfMemory {
+                limit: self.memory_limit,
+            });
+        }
+        if let Some(signal) = signal(status) {
+            return Err(SolveError::Signal(signal));
+        }
+
+        match serde_json::from_slice(&output.stdout) {
+            Ok(report) if status.success() => Ok(report),
+            _ => Err(SolveError::Exit(status.code().unwrap_or(-1))),
+        }
+    }
+}
+
+/// What a child left behind.
+#[derive(Debug)]
+struct Output {
+    /// `None` if the child was killed for going over its budget.
+    status: Option<ExitStatus>,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+/// Runs `command` with `input` on its stdin, killing it once it has run for
+/// longer than `budget`.
+fn run_child(mut command: Command, input: &str, budget: Option<Duration>) -> io::Result<Output> {
+    let start = Instant::now();
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    // A child that died early stops reading; its status tells why.
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes()).ok();
+    }
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if budget.is_some_and(|budget| start.elapsed() >= budget) {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    // Whatever the child started may still hold the pipes open after it was
+    // killed, and nothing it printed matters then.
+    let output = |pipe: JoinHandle<Vec<u8>>| match status {
+        Some(_) => pipe.join().unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    Ok(Output {
+        status,
+        stdout: output(stdout),
+        stderr: output(stderr),
+    })
+}
+
+/// Reads everything from `pipe` on a thread of its own, so that a child
+/// never blocks on a full pipe.
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buffer).ok();
+        }
+        buffer
+    })
+}
+
+#[cfg(unix)]
+fn signal(status: ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn signal(_: ExitStatus) -> Option<i32> {
+    None
+}
+
+impl Failure {
+    /// Rebuilds the error as the child had it, as far as it can be.
+    fn into_error(self, entry: &Entry, part: u8) -> SolveError {
+        match (self.kind.as_str(), self.line, self.column) {
+            ("malformed_input", Some(line), Some(column)) => SolveError::MalformedInput {
+                line,
+                column,
+                message: self.message,
+            },
+            ("unknown_part", ..) => SolveError::UnknownPart {
+                year: entry.year,
+                day: entry.day,
+                part,
+            },
+            ("panic", ..) => SolveError::Panic(self.message),
+            ("no_solution", ..) => SolveError::NoSolution(self.message.into()),
+            _ => SolveError::Relayed {
+                kind: self.kind.into(),
+                message: self.message,
+            },
+        }
+    }
+}
+
+impl From<&SolveError> for Failure {
+    fn from(error: &SolveError) -> Self {
+        match error {
+            SolveError::MalformedInput {
+                line,
+                column,
+                message,
+            } => Self {
+                kind: error.kind().to_string(),
+                message: message.clone(),
+                line: Some(*line),
+                column: Some(*column),
+            },
+            SolveError::NoSolution(reason) => Self {
+                kind: error.kind().to_string(),
+                message: reason.to_string(),
+                line: None,
+                column: None,
+            },
+            _ => Self {
+                kind: error.kind().to_string(),
+                message: error.to_string(),
+                line: None,
+                column: None,
+            },
+        }
+    }
+}
+
+/// Runs `aoc __child`: solves one part against the input on stdin and
+/// prints a [`Report`] on stdout.
+pub fn child(args: &ChildArgs) -> ExitCode {
+    if let Some(limit) = args.memory_limit {
+        if let Err(error) = limit_memory(limit) {
+            eprintln!("error: failed to limit memory: {error}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let Some(entry) = solution::find(args.year, args.day) else {
+        eprintln!(
+            "error: day {} of {} is not implemented",
+            args.day, args.year
+        );
+        return ExitCode::FAILURE;
+    };
+    let mut input = String::new();
+    if let Err(error) = io::stdin().read_to_string(&mut input) {
+        eprintln!("error: failed to read the input: {error}");
+        return ExitCode::FAILURE;
+    }
+
+    // The panic is reported on stdout instead.
+    panic::set_hook(Box::new(|info| {
+        PANIC_LOCATION.set(info.location().map(ToString::to_string));
+    }));
+
+    let report = solve(entry, args.part, &input, args.alloc_stats);
+    println!("{}", serde_json::to_string(&report).unwrap());
+    ExitCode::SUCCESS
+}
+
+/// Solves `part` of `entry` against `input`, catching a panic.
+fn solve(entry: &Entry, part: u8, input: &str, alloc_stats: bool) -> Report {
+    let mut recorder = alloc_stats.then(Recorder::default);
+    let timed = panic::catch_unwind(AssertUnwindSafe(|| match &mut recorder {
+        Some(recorder) => entry.run_observed(input, &[part], recorder),
+        None => entry.run(input, &[part]),
+    }));
+
+    let nanos = |elapsed: Duration| u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+    match timed {
+        Ok(mut timed) => {
+            let solved = timed.parts.swap_remove(0);
+            Report {
+                parse_ns: nanos(timed.parse),
+                solve_ns: nanos(solved.elapsed),
+                result: solved.result.map_err(|error| Failure::from(&error)),
+                alloc: recorder
+                    .map(|recorder| [recorder.get(Phase::Parse), recorder.get(Phase::Part(part))]),
+            }
+        }
+        Err(payload) => Report {
+            parse_ns: 0,
+            solve_ns: 0,
+            result: Err(Failure::from(&SolveError::Panic(panic_message(
+                payload.as_ref(),
+                PANIC_LOCATION.take(),
+            )))),
+            alloc: None,
+        },
+    }
+}
+
+/// Describes a caught panic by its payload and, if known, where it happened.
+pub fn panic_message(payload: &(dyn Any + Send), location: Option<String>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string());
+
+    match location {
+        Some(location) => format!("panicked at {location}: {message}"),
+        None => format!("panicked: {message}"),
+    }
+}
+
+/// Limits the address space of this process to `bytes`, so that allocating
+/// beyond it fails.
+#[cfg(unix)]
+fn limit_memory(bytes: u64) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+    // SAFETY: `limit` is a valid `rlimit` for the duration of the call.
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_: u64) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "memory limits need a Unix system",
+    ))
+}
+
+/// Parses a size in bytes such as `4096`, `512MiB` or `2GiB`.
+pub fn parse_size(size: &str) -> Result<u64, String> {
+    let invalid = || format!("invalid size `{size}`, expected e.g. `512MiB` or `2GiB`");
+
+    let split = size
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (value, unit) = size.split_at(split);
+    let value = value.parse::<u64>().map_err(|_| invalid())?;
+    let shift = match unit {
+        "" | "B" => 0,
+        "KiB" => 10,
+        "MiB" => 20,
+        "GiB" => 30,
+        "TiB" => 40,
+        _ => return Err(invalid()),
+    };
+
+    value
+        .checked_mul(1 << shift)
+        .filter(|&bytes| bytes > 0)
+        .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("4096"), Ok(4096));
+        assert_eq!(parse_size("512MiB"), Ok(512 << 20));
+        assert_eq!(parse_size("2GiB"), Ok(2 << 30));
+        assert!(parse_size("0").is_err());
+        assert!(parse_size("2GB").is_err());
+        assert!(parse_size("MiB").is_err());
+        assert!(parse_size("1.5GiB").is_err());
+        assert!(parse_size("99999999TiB").is_err());
+    }
+
+    #[test]
+    fn test_solve() {
+        let entry = solution::find(2015, 1).unwrap();
+        let report = solve(entry, 2, "(()))", false);
+        assert_eq!(report.result.unwrap(), Answer::Signed(5));
+        assert!(report.alloc.is_none());
+
+        let input = "(()x";
+        let report = solve(entry, 1, input, false);
+        let error = report.result.unwrap_err().into_error(entry, 1);
+        assert_eq!(
+            error,
+            SolveError::malformed(input, &input[3..], "expected `(` or `)`")
+        );
+
+        let panics = Entry {
+            phases: |_, _, _| panic!("boom"),
+            ..*entry
+        };
+        let report = solve(&panics, 1, "", false);
+        let error = report.result.unwrap_err().into_error(&panics, 1);
+        assert!(matches!(&error, SolveError::Panic(message) if message.ends_with(": boom")));
+
+        let round_trip = |error: &SolveError| {
+            let json = serde_json::to_string(&Failure::from(error)).unwrap();
+            serde_json::from_str::<Failure>(&json)
+                .unwrap()
+                .into_error(entry, 1)
+        };
+        let error = SolveError::no_solution("nothing fits");
+        assert_eq!(round_trip(&error), error);
+        let error = SolveError::UnknownDay {
+            year: 2015,
+            day: 26,
+        };
+        assert_eq!(round_trip(&error).kind(), "unknown_day");
+        assert_eq!(round_trip(&error).to_string(), error.to_string());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_report() {
+        let sandbox = Sandbox {
+            memory_limit: Some(1 << 20),
+        };
+        let report = |script: &str, budget| {
+            let mut command = Command::new("sh");
+            command.args(["-c", script]);
+            sandbox.run_command(command, "", budget)
+        };
+
+        let ok = r#"echo '{"parse_ns":1,"solve_ns":2,"result":{"Ok":{"type":"unsigned","value":7}},"alloc":null}'"#;
+        let ok = report(ok, None).unwrap();
+        assert_eq!((ok.parse_ns, ok.solve_ns), (1, 2));
+        assert_eq!(ok.result.unwrap(), Answer::Unsigned(7));
+
+        assert_eq!(
+            report("kill -ABRT $$", None).unwrap_err(),
+            SolveError::Signal(6)
+        );
+        assert_eq!(report("exit 3", None).unwrap_err(), SolveError::Exit(3));
+        assert_eq!(report("echo '{}'", None).unwrap_err(), SolveError::Exit(0));
+        assert_eq!(
+            report(
+                "echo 'memory allocation of 64 bytes failed' >&2; kill -ABRT $$",
+                None
+            )
+            .unwrap_err(),
+            SolveError::OutOfMemory {
+                limit: Some(1 << 20)
+            }
+        );
+
+        let budget = Duration::from_millis(50);
+        let start = Instant::now();
+        assert_eq!(
+            report("sleep 60", Some(budget)).unwrap_err(),
+            SolveError::Timeout(budget)
+        );
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        let missing = Command::new("/nonexistent/aoc");
+        assert!(matches!(
+            sandbox.run_command(missing, "", None),
+            Err(SolveError::Spawn(_))
+        ));
+    }
+}
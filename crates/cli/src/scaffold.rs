@@ -0,0 +1,537 @@
+use std::{
+    env,
+    ffi::OsString,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// Options for `aoc new`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The year of the new day.
+    #[arg(value_parser = clap::value_parser!(u16).range(2015..))]
+    pub year: u16,
+
+    /// The day to create (1-25).
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+
+    /// The puzzle's title. Defaults to `Day <day>`.
+    #[arg(long)]
+    pub title: Option<String>,
+
+    /// The root of the workspace. Defaults to the closest directory above the
+    /// current one that holds `crates/core`.
+    #[arg(long)]
+    pub root: Option<PathBuf>,
+}
+
+/// Why the day couldn't be scaffolded.
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// No directory above the current one holds `crates/core`.
+    NoWorkspace,
+    /// The day's module already exists.
+    Exists(PathBuf),
+    /// A file that needs to be edited doesn't look the way it's expected to.
+    Layout {
+        path: PathBuf,
+        message: &'static str,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoWorkspace => write!(f, "no directory above this one holds `crates/core`"),
+            Self::Exists(path) => write!(f, "{} already exists", path.display()),
+            Self::Layout { path, message } => write!(f, "{}: {message}", path.display()),
+            Self::Io { path, source } => write!(f, "failed to access {}: {source}", path.display()),
+        }
+    }
+}
+
+pub fn run(args: &Args) -> ExitCode {
+    let root = match args.root.clone().map_or_else(find_root, Ok) {
+        Ok(root) => root,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let title = args
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", args.day));
+    match scaffold(
+        &root.join("crates").join("core"),
+        args.year,
+        args.day,
+        &title,
+    ) {
+        Ok(written) => {
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The closest directory above the current one that holds `crates/core`.
+pub fn find_root() -> Result<PathBuf, ScaffoldError> {
+    let cwd = env::current_dir().map_err(|source| ScaffoldError::Io {
+        path: PathBuf::from("."),
+        source,
+    })?;
+
+    cwd.ancestors()
+        .find(|dir| dir.join("crates").join("core").join("src").is_dir())
+        .map(Path::to_path_buf)
+        .ok_or(ScaffoldError::NoWorkspace)
+}
+
+/// Creates `day` of `year` in the core crate at `core`, registering it with
+/// its year, and its year with the library if the year is new.
+///
+/// Every file is prepared and written to a temporary file beside it before
+/// any is moved into place, so nothing is touched if one of them can't be
+/// edited or written. Returns the paths that were written.
+pub fn scaffold(
+    core: &Path,
+    year: u16,
+    day: u8,
+    title: &str,
+) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let year_mod = format!("year_{year}");
+    let day_mod = format!("day_{day:02}");
+    let src = core.join("src");
+    let year_dir = src.join(&year_mod);
+    let day_dir = year_dir.join(&day_mod);
+    if day_dir.exists() {
+        return Err(ScaffoldError::Exists(day_dir));
+    }
+
+    let mut files = vec![
+        (day_dir.join("mod.rs"), day_template(year, day, title)),
+        (day_dir.join("input.txt"), String::new()),
+    ];
+
+    let year_file = year_dir.join("mod.rs");
+    if year_file.exists() {
+        let contents = read(&year_file)?;
+        let contents = insert_sorted(&contents, "pub mod day_", &format!("pub mod {day_mod};"))
+            .and_then(|contents| {
+                add_to_list(
+                    &contents,
+                    "pub const SOLUTIONS: &[Entry] = &[",
+                    "];",
+                    &format!("Entry::new::<{day_mod}::Puzzle>()"),
+                )
+            })
+            .ok_or_else(|| {
+                layout(
+                    &year_file,
+                    "expected `pub mod day_..` lines and `SOLUTIONS`",
+                )
+            })?;
+        files.push((year_file, contents));
+    } else {
+        files.push((year_file, year_template(year, &day_mod)));
+
+        let lib = src.join("lib.rs");
+        let contents = insert_sorted(
+            &read(&lib)?,
+            "pub mod year_",
+            &format!("pub mod {year_mod};"),
+        )
+        .ok_or_else(|| layout(&lib, "expected `pub mod year_..` lines"))?;
+        files.push((lib, contents));
+
+        let solution = src.join("solution.rs");
+        let contents = register_year(&read(&solution)?, &year_mod)
+            .ok_or_else(|| layout(&solution, "expected `use crate::{..}` and `REGISTRY`"))?;
+        files.push((solution, contents));
+    }
+
+    let new_dirs = [&year_dir, &day_dir]
+        .into_iter()
+        .filter(|dir| !dir.exists())
+        .cloned()
+        .collect::<Vec<_>>();
+    fs::create_dir_all(&day_dir).map_err(|source| ScaffoldError::Io {
+        path: day_dir.clone(),
+        source,
+    })?;
+
+    let mut staged = Vec::with_capacity(files.len());
+    for (path, contents) in files {
+        match stage(&path, &contents) {
+            Ok(temp) => staged.push((temp, path)),
+            Err(error) => {
+                for (temp, _) in staged {
+                    let _ = fs::remove_file(temp);
+                }
+                for dir in new_dirs.iter().rev() {
+                    let _ = fs::remove_dir(dir);
+                }
+                return Err(error);
+            }
+        }
+    }
+
+    staged
+        .into_iter()
+        .map(|(temp, path)| {
+            fs::rename(&temp, &path)
+                .map(|()| path.clone())
+                .map_err(|source| ScaffoldError::Io { path, source })
+        })
+        .collect()
+}
+
+/// Writes `contents` to a temporary file beside `path`, to be renamed over
+/// it once every file has been written.
+fn stage(path: &Path, contents: &str) -> Result<PathBuf, ScaffoldError> {
+    let mut name = OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(".new");
+    let temp = path.with_file_name(name);
+    fs::write(&temp, contents)
+        .map(|()| temp.clone())
+        .map_err(|source| ScaffoldError::Io { path: temp, source })
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn layout(path: &Path, message: &'static str) -> ScaffoldError {
+    ScaffoldError::Layout {
+        path: path.to_path_buf(),
+        message,
+    }
+}
+
+/// Inserts `line` into the run of lines starting with `prefix`, keeping the
+/// run sorted. Returns `None` if there's no such run.
+fn insert_sorted(contents: &str, prefix: &str, line: &str) -> Option<String> {
+    let mut lines = contents.lines().collect::<Vec<_>>();
+    let first = lines.iter().position(|l| l.starts_with(prefix))?;
+    let at = lines[first..]
+        .iter()
+        .position(|l| !l.starts_with(prefix) || *l > line)
+        .map_or(lines.len(), |i| first + i);
+    lines.insert(at, line);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Adds `year_mod` to the `use crate::{..}` import and to the `REGISTRY`
+/// in `solution.rs`.
+fn register_year(contents: &str, year_mod: &str) -> Option<String> {
+    let contents = add_to_list(contents, "use crate::{", "};", year_mod)?;
+    add_to_list(
+        &contents,
+        "pub static REGISTRY: &[&[Entry]] = &[",
+        "];",
+        &format!("{year_mod}::SOLUTIONS"),
+    )
+}
+
+/// Adds `item` to the sorted, comma-separated list between `open` and
+/// `close`, and lays the list out the way rustfmt would.
+///
+/// Only the commas outside of any brackets separate items, so that an item
+/// may hold a list of its own.
+fn add_to_list(contents: &str, open: &str, close: &str, item: &str) -> Option<String> {
+    let start = contents.find(open)?;
+    let first = start + open.len();
+
+    let mut items = Vec::new();
+    let mut depth = 0_usize;
+    let mut item_start = first;
+    let mut end = None;
+    for (at, c) in contents[first..].char_indices() {
+        let at = first + at;
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth == 0 => {
+                end = Some(at);
+                break;
+            }
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(&contents[item_start..at]);
+                item_start = at + 1;
+            }
+            _ => {}
+        }
+    }
+    let end = end.filter(|&end| contents[end..].starts_with(close))?;
+    items.push(&contents[item_start..end]);
+
+    let mut items = items
+        .into_iter()
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect::<Vec<_>>();
+    let at = items
+        .iter()
+        .position(|existing| *existing > item)
+        .unwrap_or(items.len());
+    items.insert(at, item);
+
+    // rustfmt keeps arrays on one line only while they're at most 60
+    // characters wide, and any other list while the line fits in 100.
+    let joined = items.join(", ");
+    let single = format!("{open}{joined}{close}");
+    let fits = single.len() <= 100 && (!open.ends_with('[') || joined.len() + 2 <= 60);
+    let list = if fits {
+        single
+    } else {
+        let items = items
+            .iter()
+            .map(|item| format!("    {item},\n"))
+            .collect::<String>();
+        format!("{open}\n{items}{close}")
+    };
+
+    Some(format!(
+        "{}{list}{}",
+        &contents[..start],
+        &contents[end + close.len()..]
+    ))
+}
+
+fn year_template(year: u16, day_mod: &str) -> String {
+    format!(
+        "use crate::solution::Entry;
+
+pub mod {day_mod};
+
+/// Every day of {year}, in order.
+pub const SOLUTIONS: &[Entry] = &[Entry::new::<{day_mod}::Puzzle>()];
+"
+    )
+}
+
+fn day_template(year: u16, day: u8, title: &str) -> String {
+    format!(
+        r#"use crate::{{answer::Answer, error::SolveError, input::embedded, solution::Solution}};
+
+pub const TITLE: &str = {title:?};
+
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {{
+    const YEAR: u16 = {year};
+    const DAY: u8 = {day};
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {{
+        parse(input)
+    }}
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {{
+        part1(parsed).map(Answer::from)
+    }}
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {{
+        part2(parsed).map(Answer::from)
+    }}
+}}
+
+/// # Errors
+/// Returns an error if the input is malformed
+pub fn parse(input: &str) -> Result<Vec<&str>, SolveError> {{
+    Ok(input.lines().collect())
+}}
+
+/// # Errors
+/// Returns an error if the puzzle has no solution for the input
+pub fn part1(_lines: &[&str]) -> Result<u64, SolveError> {{
+    Err(SolveError::no_solution("part 1 is not solved yet"))
+}}
+
+/// # Errors
+/// Returns an error if the puzzle has no solution for the input
+pub fn part2(_lines: &[&str]) -> Result<u64, SolveError> {{
+    Err(SolveError::no_solution("part 2 is not solved yet"))
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_part1_example() {{
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()).unwrap(), 0);
+    }}
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_part1_puzzle() {{
+        let input = crate::input::for_test({year}, {day});
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 0);
+    }}
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_part2_example() {{
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()).unwrap(), 0);
+    }}
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_part2_puzzle() {{
+        let input = crate::input::for_test({year}, {day});
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 0);
+    }}
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_sorted() {
+        let contents = "use a;\n\npub mod day_01;\npub mod day_03;\n\nfn main() {}\n";
+        assert_eq!(
+            insert_sorted(contents, "pub mod day_", "pub mod day_02;").unwrap(),
+            "use a;\n\npub mod day_01;\npub mod day_02;\npub mod day_03;\n\nfn main() {}\n"
+        );
+        assert_eq!(
+            insert_sorted(contents, "pub mod day_", "pub mod day_04;").unwrap(),
+            "use a;\n\npub mod day_01;\npub mod day_03;\npub mod day_04;\n\nfn main() {}\n"
+        );
+        assert!(insert_sorted(contents, "pub mod year_", "pub mod year_2015;").is_none());
+    }
+
+    #[test]
+    fn test_add_to_list() {
+        let open = "pub const SOLUTIONS: &[Entry] = &[";
+        let contents = "pub const SOLUTIONS: &[Entry] = &[\n    Entry::new::<day_01::Puzzle>(),\n    Entry::new::<day_04::Puzzle>().with_variants(&[Variant::new::<A>(\"a\"), Variant::new::<B>(\"b\")]),\n];\n";
+        assert_eq!(
+            add_to_list(contents, open, "];", "Entry::new::<day_02::Puzzle>()").unwrap(),
+            "pub const SOLUTIONS: &[Entry] = &[\n    Entry::new::<day_01::Puzzle>(),\n    Entry::new::<day_02::Puzzle>(),\n    Entry::new::<day_04::Puzzle>().with_variants(&[Variant::new::<A>(\"a\"), Variant::new::<B>(\"b\")]),\n];\n"
+        );
+        assert_eq!(
+            add_to_list("use crate::{a::{b, c}, d};\n", "use crate::{", "};", "c").unwrap(),
+            "use crate::{a::{b, c}, c, d};\n"
+        );
+        assert!(add_to_list("const A: [u8; 2] = [1, 2];\n", open, "];", "3").is_none());
+    }
+
+    #[test]
+    fn test_register_year() {
+        let contents = "use crate::{answer::Answer, year_2015, year_2016};\n\npub static REGISTRY: &[&[Entry]] = &[year_2015::SOLUTIONS, year_2016::SOLUTIONS];\n";
+        assert_eq!(
+            register_year(contents, "year_2017").unwrap(),
+            "use crate::{answer::Answer, year_2015, year_2016, year_2017};\n\npub static REGISTRY: &[&[Entry]] = &[\n    year_2015::SOLUTIONS,\n    year_2016::SOLUTIONS,\n    year_2017::SOLUTIONS,\n];\n"
+        );
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let core = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let src = core.join("src");
+        fs::create_dir_all(src.join("year_2015")).unwrap();
+        fs::write(src.join("lib.rs"), "pub mod error;\npub mod year_2015;\n").unwrap();
+        fs::write(
+            src.join("solution.rs"),
+            "use crate::{answer::Answer, year_2015};\n\npub static REGISTRY: &[&[Entry]] = &[year_2015::SOLUTIONS];\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("year_2015").join("mod.rs"),
+            year_template(2015, "day_01"),
+        )
+        .unwrap();
+
+        let written = scaffold(&core, 2015, 2, "Two").unwrap();
+        let day_dir = src.join("year_2015").join("day_02");
+        assert_eq!(
+            written,
+            [
+                day_dir.join("mod.rs"),
+                day_dir.join("input.txt"),
+                src.join("year_2015").join("mod.rs"),
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(day_dir.join("mod.rs")).unwrap(),
+            day_template(2015, 2, "Two")
+        );
+        assert_eq!(fs::read_to_string(day_dir.join("input.txt")).unwrap(), "");
+        let year = fs::read_to_string(src.join("year_2015").join("mod.rs")).unwrap();
+        assert!(year.contains("pub mod day_01;\npub mod day_02;\n"));
+        assert!(year.contains("Entry::new::<day_02::Puzzle>()"));
+        assert!(matches!(
+            scaffold(&core, 2015, 2, "Two"),
+            Err(ScaffoldError::Exists(_))
+        ));
+
+        let written = scaffold(&core, 2016, 1, "One").unwrap();
+        assert_eq!(written.len(), 5);
+        assert_eq!(
+            fs::read_to_string(src.join("year_2016").join("mod.rs")).unwrap(),
+            year_template(2016, "day_01")
+        );
+        assert_eq!(
+            fs::read_to_string(src.join("lib.rs")).unwrap(),
+            "pub mod error;\npub mod year_2015;\npub mod year_2016;\n"
+        );
+        assert!(fs::read_to_string(src.join("solution.rs"))
+            .unwrap()
+            .contains("year_2015, year_2016};"));
+
+        // A file that can't be edited leaves the tree as it was.
+        fs::write(src.join("lib.rs"), "").unwrap();
+        assert!(matches!(
+            scaffold(&core, 2017, 1, "One"),
+            Err(ScaffoldError::Layout { .. })
+        ));
+        assert!(!src.join("year_2017").exists());
+
+        let leftovers = [src.clone(), src.join("year_2015"), day_dir]
+            .iter()
+            .flat_map(|dir| fs::read_dir(dir).unwrap())
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with('.')
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+        fs::remove_dir_all(&core).unwrap();
+    }
+}
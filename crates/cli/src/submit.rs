@@ -0,0 +1,462 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use advent_of_code::{solve, Answer};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Client, ClientArgs, ClientError},
+    config::Config,
+    input,
+};
+
+/// Options for `aoc submit`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The year of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u16).range(2015..))]
+    pub year: u16,
+
+    /// The day of the puzzle (1-25).
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+
+    /// The part of the puzzle (1 or 2).
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: u8,
+
+    /// The answer to submit. Defaults to solving the part.
+    pub answer: Option<String>,
+
+    /// The puzzle input to solve when no answer is given: a file, `-` for
+    /// stdin, or a directory holding `<year>/day_<dd>.txt`.
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub client: ClientArgs,
+}
+
+/// How the server judged a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// The answer wasn't judged because the previous one was too recent.
+    RateLimited,
+    /// The part is already solved, or part 1 isn't yet.
+    WrongLevel,
+    /// The response couldn't be understood.
+    Unknown,
+}
+
+impl Outcome {
+    const fn is_wrong(self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+}
+
+/// One answer sent to the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch.
+    pub at: u64,
+}
+
+/// Why a guess won't be sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved with this answer.
+    AlreadyCorrect,
+    /// The part was already solved with another answer.
+    Solved(String),
+    /// The same answer was already judged wrong.
+    KnownWrong(Outcome),
+    /// The answer is at least an answer already judged too high.
+    AtLeast(i128),
+    /// The answer is at most an answer already judged too low.
+    AtMost(i128),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadyCorrect => write!(f, "this answer was already accepted"),
+            Self::Solved(answer) => write!(f, "this part was already solved with {answer}"),
+            Self::KnownWrong(outcome) => {
+                write!(
+                    f,
+                    "this answer was already rejected ({})",
+                    outcome_text(*outcome)
+                )
+            }
+            Self::AtLeast(high) => write!(f, "{high} was already too high"),
+            Self::AtMost(low) => write!(f, "{low} was already too low"),
+        }
+    }
+}
+
+pub fn run(args: &Args, config: &Config) -> ExitCode {
+    let answer = match args
+        .answer
+        .clone()
+        .map_or_else(|| solve_part(args, config), Ok)
+    {
+        Ok(answer) => answer,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let client = args.client.client(config);
+    match submit(&client, args.year, args.day, args.part, &answer) {
+        Ok(Submitted::Refused(Refusal::AlreadyCorrect)) => {
+            println!("{answer}: {}", Refusal::AlreadyCorrect);
+            ExitCode::SUCCESS
+        }
+        Ok(Submitted::Refused(refusal)) => {
+            eprintln!("error: not submitting {answer}: {refusal}");
+            ExitCode::FAILURE
+        }
+        Ok(Submitted::Judged(outcome, message)) => {
+            println!("{answer}: {}", outcome_text(outcome));
+            if !message.is_empty() {
+                println!("{message}");
+            }
+            if outcome == Outcome::Correct {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn solve_part(args: &Args, config: &Config) -> Result<String, String> {
+    let input = input::load(args.year, args.day, args.input.as_deref(), config)
+        .map_err(|error| error.to_string())?;
+    match solve(args.year, args.day, args.part, &input).map_err(|error| error.to_string())? {
+        Answer::Art(_) => {
+            Err("the answer is drawn as art; read it and pass the letters".to_string())
+        }
+        answer => Ok(answer.to_string()),
+    }
+}
+
+/// What happened to an answer passed to [`submit`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submitted {
+    /// The history rules the answer out, so it wasn't sent.
+    Refused(Refusal),
+    /// The server judged the answer, explaining why in the message.
+    Judged(Outcome, String),
+}
+
+/// Why an answer couldn't be submitted.
+#[derive(Debug)]
+pub enum SubmitError {
+    Client(ClientError),
+    History { path: PathBuf, message: String },
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Client(error) => error.fmt(f),
+            Self::History { path, message } => write!(f, "{}: {message}", path.display()),
+        }
+    }
+}
+
+impl From<ClientError> for SubmitError {
+    fn from(error: ClientError) -> Self {
+        Self::Client(error)
+    }
+}
+
+/// Submits `answer` to `part` of `day` of `year`, unless the guess history
+/// already rules it out, and records the server's verdict in the history.
+pub fn submit(
+    client: &Client,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Submitted, SubmitError> {
+    let path = history_path(client.cache_dir(), year, day);
+    let mut history = load_history(&path)?;
+    if let Some(refusal) = check(&history, part, answer) {
+        return Ok(Submitted::Refused(refusal));
+    }
+
+    let body = client.post_form(
+        &format!("/{year}/day/{day}/answer"),
+        &[("level", &part.to_string()), ("answer", answer)],
+    )?;
+    let (outcome, message) = parse_response(&body);
+
+    history.push(Guess {
+        part,
+        answer: answer.to_string(),
+        outcome,
+        at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()),
+    });
+    save_history(&path, &history)?;
+
+    Ok(Submitted::Judged(outcome, message))
+}
+
+/// Where the guesses for `day` of `year` are recorded.
+pub fn history_path(cache_dir: &Path, year: u16, day: u8) -> PathBuf {
+    cache_dir
+        .join("guesses")
+        .join(year.to_string())
+        .join(format!("day_{day:02}.json"))
+}
+
+fn load_history(path: &Path) -> Result<Vec<Guess>, SubmitError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents).map_err(|error| SubmitError::History {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        }),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(SubmitError::History {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        }),
+    }
+}
+
+fn save_history(path: &Path, history: &[Guess]) -> Result<(), SubmitError> {
+    let error = |message: String| SubmitError::History {
+        path: path.to_path_buf(),
+        message,
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|source| error(source.to_string()))?;
+    }
+    let contents =
+        serde_json::to_string_pretty(history).map_err(|source| error(source.to_string()))?;
+    fs::write(path, contents + "\n").map_err(|source| error(source.to_string()))
+}
+
+/// Why `answer` shouldn't be sent for `part`, given the earlier guesses.
+pub fn check(history: &[Guess], part: u8, answer: &str) -> Option<Refusal> {
+    let guesses = history.iter().filter(|guess| guess.part == part);
+
+    if let Some(correct) = guesses
+        .clone()
+        .find(|guess| guess.outcome == Outcome::Correct)
+    {
+        return Some(if correct.answer == answer {
+            Refusal::AlreadyCorrect
+        } else {
+            Refusal::Solved(correct.answer.clone())
+        });
+    }
+
+    if let Some(wrong) = guesses
+        .clone()
+        .find(|guess| guess.outcome.is_wrong() && guess.answer == answer)
+    {
+        return Some(Refusal::KnownWrong(wrong.outcome));
+    }
+
+    let value = answer.trim().parse::<i128>().ok()?;
+    let bound = |outcome| {
+        guesses
+            .clone()
+            .filter(move |guess| guess.outcome == outcome)
+            .filter_map(|guess| guess.answer.trim().parse::<i128>().ok())
+    };
+    if let Some(high) = bound(Outcome::TooHigh).filter(|&high| value >= high).min() {
+        return Some(Refusal::AtLeast(high));
+    }
+    if let Some(low) = bound(Outcome::TooLow).filter(|&low| value <= low).max() {
+        return Some(Refusal::AtMost(low));
+    }
+
+    None
+}
+
+/// Reads the verdict out of the server's response page, along with the
+/// page's message as plain text.
+pub fn parse_response(body: &str) -> (Outcome, String) {
+    let article = body
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(body, |(article, _)| article);
+    let message = strip_tags(article);
+
+    let outcome = if message.contains("That's the right answer") {
+        Outcome::Correct
+    } else if message.contains("That's not the right answer") {
+        if message.contains("too high") {
+            Outcome::TooHigh
+        } else if message.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if message.contains("You gave an answer too recently") {
+        Outcome::RateLimited
+    } else if message.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown
+    };
+
+    (outcome, message)
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+const fn outcome_text(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Correct => "correct",
+        Outcome::TooHigh => "too high",
+        Outcome::TooLow => "too low",
+        Outcome::Wrong => "wrong",
+        Outcome::RateLimited => "rate limited, not judged",
+        Outcome::WrongLevel => "wrong level, not judged",
+        Outcome::Unknown => "unknown response",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::client::mock;
+
+    const TOO_HIGH: &str = "<main>\n<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2015/about\">about page</a>. Please wait one minute before trying again. <a href=\"/2015/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+    const CORRECT: &str = "<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to powering the weather machine.</p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 42s left to wait. <a href=\"/2015/day/1\">[Return to Day 1]</a></p></article>";
+
+    fn guess(part: u8, answer: &str, outcome: Outcome) -> Guess {
+        Guess {
+            part,
+            answer: answer.to_string(),
+            outcome,
+            at: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response(TOO_HIGH).0, Outcome::TooHigh);
+        assert_eq!(parse_response(CORRECT).0, Outcome::Correct);
+        assert_eq!(
+            parse_response(&TOO_HIGH.replace("too high", "too low")).0,
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse_response("<article><p>That's not the right answer.</p></article>").0,
+            Outcome::Wrong
+        );
+        assert_eq!(
+            parse_response("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>").0,
+            Outcome::WrongLevel
+        );
+        assert_eq!(parse_response("<html></html>").0, Outcome::Unknown);
+
+        let (outcome, message) = parse_response(TOO_RECENT);
+        assert_eq!(outcome, Outcome::RateLimited);
+        assert!(message.ends_with("You have 42s left to wait. [Return to Day 1]"));
+    }
+
+    #[test]
+    fn test_check() {
+        let history = [
+            guess(1, "100", Outcome::TooHigh),
+            guess(1, "10", Outcome::TooLow),
+            guess(1, "50", Outcome::Wrong),
+            guess(1, "60", Outcome::RateLimited),
+            guess(2, "abc", Outcome::Correct),
+        ];
+
+        assert_eq!(
+            check(&history, 1, "50"),
+            Some(Refusal::KnownWrong(Outcome::Wrong))
+        );
+        assert_eq!(check(&history, 1, "150"), Some(Refusal::AtLeast(100)));
+        assert_eq!(
+            check(&history, 1, "100"),
+            Some(Refusal::KnownWrong(Outcome::TooHigh))
+        );
+        assert_eq!(check(&history, 1, "-3"), Some(Refusal::AtMost(10)));
+        assert_eq!(check(&history, 1, "60"), None);
+        assert_eq!(check(&history, 1, "42"), None);
+        assert_eq!(check(&history, 1, "xyz"), None);
+        assert_eq!(check(&history, 2, "abc"), Some(Refusal::AlreadyCorrect));
+        assert_eq!(
+            check(&history, 2, "abd"),
+            Some(Refusal::Solved("abc".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = mock::serve(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let cache = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let client = Client::new(server.url.clone(), Some("abc".to_string()), cache.clone())
+            .with_min_interval(Duration::ZERO);
+
+        let first = submit(&client, 2015, 1, 1, "200").unwrap();
+        let refused = submit(&client, 2015, 1, 1, "300").unwrap();
+        let second = submit(&client, 2015, 1, 1, "138").unwrap();
+        let history = load_history(&history_path(&cache, 2015, 1)).unwrap();
+        fs::remove_dir_all(&cache).unwrap();
+
+        assert!(matches!(first, Submitted::Judged(Outcome::TooHigh, _)));
+        assert_eq!(refused, Submitted::Refused(Refusal::AtLeast(200)));
+        assert!(matches!(second, Submitted::Judged(Outcome::Correct, _)));
+        assert_eq!(
+            history
+                .iter()
+                .map(|guess| (guess.answer.as_str(), guess.outcome))
+                .collect::<Vec<_>>(),
+            [("200", Outcome::TooHigh), ("138", Outcome::Correct)]
+        );
+
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2015/day/1/answer");
+        assert_eq!(requests[0].body, "level=1&answer=200");
+    }
+}
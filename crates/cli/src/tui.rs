@@ -0,0 +1,766 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    cmp::Reverse,
+    collections::BTreeMap,
+    env, fs,
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, Stdio},
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use advent_of_code::{
+    input::InputProvider,
+    solution::{self, Entry},
+    Answer,
+};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    crossterm::{
+        event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+        execute,
+        terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+    },
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Cell as TableCell, Paragraph, Row as TableRow, Table, TableState, Wrap,
+    },
+    Frame, Terminal,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answers::{self, Answers},
+    client,
+    config::Config,
+    input,
+    run_all::PartRun,
+    sandbox,
+    verify::{self, Status},
+};
+
+/// Options for `aoc tui`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Only list the days of this year. Defaults to the config file's `year`.
+    #[arg(long, env = "AOC_YEAR")]
+    pub year: Option<u16>,
+
+    /// A directory holding `<year>/day_<dd>.txt` inputs. Defaults to
+    /// `AOC_INPUT_DIR`, the inputs named by the config file, or the inputs
+    /// embedded in the day modules.
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+
+    /// The file holding the known answers, TOML or `.json`. Defaults to the
+    /// config file's `answers`, or `answers.toml`.
+    #[arg(long, env = "AOC_ANSWERS")]
+    pub answers: Option<PathBuf>,
+
+    /// Where the last results are remembered between sessions. Defaults to
+    /// `$XDG_CACHE_HOME/aoc`.
+    #[arg(long, env = "AOC_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// The last result of running one part, remembered between sessions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastRun {
+    pub outcome: Outcome,
+    pub status: Status,
+    pub duration_ns: u64,
+}
+
+/// What running a part produced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "text")]
+pub enum Outcome {
+    Answer(String),
+    /// An answer drawn as ASCII art.
+    Art(String),
+    /// The day returned an error, or its input couldn't be loaded.
+    Error(String),
+    /// The day panicked, with the panic's message and location.
+    Panic(String),
+}
+
+/// Every remembered [`LastRun`], keyed by `<year>.<day>.<part>`.
+type History = BTreeMap<String, LastRun>;
+
+/// What a key press asks the event loop to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Action {
+    None,
+    Quit,
+    /// Run these parts, given as indices into the rows and part numbers.
+    Run(Vec<(usize, u8)>),
+    /// Open the input of this row in the pager.
+    Page(usize),
+}
+
+/// A day, with the last result of each part.
+#[derive(Debug)]
+struct Row {
+    entry: &'static Entry,
+    parts: [Option<LastRun>; 2],
+    queued: [bool; 2],
+}
+
+#[derive(Debug)]
+struct App {
+    rows: Vec<Row>,
+    /// Indices into `rows`, in the order they are listed.
+    order: Vec<usize>,
+    state: TableState,
+    slowest_first: bool,
+    answers: Option<Arc<Answers>>,
+    history: History,
+    /// Shown in place of the key help until the next key press.
+    message: String,
+}
+
+/// The name of the thread that runs the parts, so that the panic hook can tell
+/// its panics apart from ones in the UI.
+const WORKER: &str = "aoc-tui-worker";
+
+/// A part taking at least this long is highlighted as slow.
+const SLOW: Duration = Duration::from_secs(1);
+
+const HELP: &str =
+    "↑↓ select  1/2 run part  enter run day  a run all  p page input  s sort by time  q quit";
+
+thread_local! {
+    /// Where the last panic on this thread happened, set by the panic hook.
+    static PANIC_LOCATION: Cell<Option<String>> = const { Cell::new(None) };
+}
+
+pub fn run(args: &Args, config: &Config) -> ExitCode {
+    let year = args.year.or(config.year);
+    let entries = solution::solutions()
+        .filter(|entry| year.is_none_or(|year| entry.year == year))
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        eprintln!("error: no registered day matches the selection");
+        return ExitCode::FAILURE;
+    }
+
+    let (answers, message) = match Answers::load(&answers::path(args.answers.as_deref(), config)) {
+        Ok(answers) => (Some(Arc::new(answers)), String::new()),
+        Err(error) => (None, format!("{error}; answers aren't checked")),
+    };
+    let history_path = args
+        .cache_dir
+        .clone()
+        .unwrap_or_else(client::cache_dir)
+        .join("last-runs.json");
+    let mut app = App::new(entries, load_history(&history_path), answers, message);
+
+    let (jobs, queue) = mpsc::channel::<(usize, &'static Entry, u8)>();
+    let (done, results) = mpsc::channel();
+    let worker = {
+        let dir = args.input.clone();
+        let config = config.clone();
+        let answers = app.answers.clone();
+        thread::Builder::new()
+            .name(WORKER.to_string())
+            .spawn(move || {
+                let inputs = input::provider(dir.as_deref(), &config);
+                for (row, entry, part) in queue {
+                    let run = run_part(entry, part, &*inputs, answers.as_deref());
+                    if done.send((row, part, run)).is_err() {
+                        break;
+                    }
+                }
+            })
+    };
+    if let Err(error) = worker {
+        eprintln!("error: failed to start the worker thread: {error}");
+        return ExitCode::FAILURE;
+    }
+
+    install_panic_hook();
+    let result = enter().and_then(|()| {
+        let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+        let inputs = input::provider(args.input.as_deref(), config);
+        event_loop(
+            &mut terminal,
+            &mut app,
+            &*inputs,
+            &jobs,
+            &results,
+            &history_path,
+        )
+    });
+    let _ = leave();
+
+    // A part that is still running is abandoned along with the worker thread
+    // when the process exits.
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn event_loop<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    inputs: &dyn InputProvider,
+    jobs: &mpsc::Sender<(usize, &'static Entry, u8)>,
+    results: &mpsc::Receiver<(usize, u8, LastRun)>,
+    history_path: &Path,
+) -> io::Result<()> {
+    loop {
+        let mut finished = false;
+        while let Ok((row, part, run)) = results.try_recv() {
+            app.finish(row, part, run);
+            finished = true;
+        }
+        if finished {
+            if let Err(error) = save_history(history_path, &app.history) {
+                app.message = format!("failed to save {}: {error}", history_path.display());
+            }
+        }
+
+        terminal.draw(|frame| app.render(frame))?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match app.handle(key) {
+            Action::None => {}
+            Action::Quit => return Ok(()),
+            Action::Run(parts) => {
+                for (row, part) in parts {
+                    app.rows[row].queued[usize::from(part - 1)] = true;
+                    let _ = jobs.send((row, app.rows[row].entry, part));
+                }
+            }
+            Action::Page(row) => {
+                let entry = app.rows[row].entry;
+                let paged = inputs
+                    .load(entry.year, entry.day)
+                    .map_err(|error| error.to_string())
+                    .and_then(|input| {
+                        leave().map_err(|error| error.to_string())?;
+                        let paged = page(&input);
+                        enter().map_err(|error| error.to_string())?;
+                        paged
+                    });
+                terminal.clear()?;
+                if let Err(error) = paged {
+                    app.message = error;
+                }
+            }
+        }
+    }
+}
+
+impl App {
+    fn new(
+        entries: Vec<&'static Entry>,
+        history: History,
+        answers: Option<Arc<Answers>>,
+        message: String,
+    ) -> Self {
+        let rows = entries
+            .into_iter()
+            .map(|entry| Row {
+                entry,
+                parts: [1, 2].map(|part| history.get(&key(entry, part)).cloned()),
+                queued: [false; 2],
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            order: (0..rows.len()).collect(),
+            rows,
+            state: TableState::default().with_selected(Some(0)),
+            slowest_first: false,
+            answers,
+            history,
+            message,
+        }
+    }
+
+    /// The index into `rows` of the selected row.
+    fn selected(&self) -> usize {
+        self.order[self.state.selected().unwrap_or(0)]
+    }
+
+    fn handle(&mut self, key: KeyEvent) -> Action {
+        self.message.clear();
+        let last = self.order.len() - 1;
+        let selected = self.state.selected().unwrap_or(0);
+        let select = |index: usize| Some(index.min(last));
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.select(select(selected.saturating_sub(1)));
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.state.select(select(selected + 1)),
+            KeyCode::PageUp => self.state.select(select(selected.saturating_sub(10))),
+            KeyCode::PageDown => self.state.select(select(selected + 10)),
+            KeyCode::Home | KeyCode::Char('g') => self.state.select(Some(0)),
+            KeyCode::End | KeyCode::Char('G') => self.state.select(Some(last)),
+            KeyCode::Char(part @ ('1' | '2')) => {
+                let part = if part == '1' { 1 } else { 2 };
+                return Action::Run(vec![(self.selected(), part)]);
+            }
+            KeyCode::Enter | KeyCode::Char('r') => {
+                return Action::Run(vec![(self.selected(), 1), (self.selected(), 2)]);
+            }
+            KeyCode::Char('a') => {
+                return Action::Run(
+                    self.order
+                        .iter()
+                        .flat_map(|&row| [(row, 1), (row, 2)])
+                        .collect(),
+                );
+            }
+            KeyCode::Char('p') => return Action::Page(self.selected()),
+            KeyCode::Char('s') => {
+                self.slowest_first = !self.slowest_first;
+                self.sort();
+            }
+            _ => {}
+        }
+
+        Action::None
+    }
+
+    /// Records the result of running `part` of `row`.
+    fn finish(&mut self, row: usize, part: u8, run: LastRun) {
+        let row = &mut self.rows[row];
+        self.history.insert(key(row.entry, part), run.clone());
+        row.parts[usize::from(part - 1)] = Some(run);
+        row.queued[usize::from(part - 1)] = false;
+        if self.slowest_first {
+            self.sort();
+        }
+    }
+
+    /// Orders the rows by day, or by the time both parts took, keeping the
+    /// same row selected.
+    fn sort(&mut self) {
+        let selected = self.selected();
+        self.order.sort_by_key(|&row| row);
+        if self.slowest_first {
+            self.order.sort_by_key(|&row| {
+                Reverse(
+                    self.rows[row]
+                        .parts
+                        .iter()
+                        .flatten()
+                        .map(|part| part.duration_ns)
+                        .sum::<u64>(),
+                )
+            });
+        }
+        self.state
+            .select(self.order.iter().position(|&row| row == selected));
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        let [table_area, details_area, footer_area] = Layout::vertical([
+            Constraint::Min(5),
+            Constraint::Length(10),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let header = TableRow::new(
+            [
+                "Year", "Day", "Title", "Part 1", "", "Time", "Part 2", "", "Time",
+            ]
+            .map(|title| TableCell::from(title).style(Style::new().add_modifier(Modifier::BOLD))),
+        );
+        let rows = self.order.iter().map(|&row| {
+            let row = &self.rows[row];
+            let mut cells = vec![
+                TableCell::from(row.entry.year.to_string()),
+                TableCell::from(format!("{:>3}", row.entry.day)),
+                TableCell::from(row.entry.title),
+            ];
+            for (part, queued) in row.parts.iter().zip(row.queued) {
+                cells.extend(part_cells(part.as_ref(), queued));
+            }
+            TableRow::new(cells)
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(16),
+            Constraint::Length(1),
+            Constraint::Length(10),
+            Constraint::Length(16),
+            Constraint::Length(1),
+            Constraint::Length(10),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::new().borders(Borders::BOTTOM))
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, table_area, &mut self.state);
+
+        frame.render_widget(
+            Paragraph::new(self.details()).wrap(Wrap { trim: false }),
+            details_area,
+        );
+
+        let footer = if self.message.is_empty() {
+            Line::from(HELP).style(Style::new().fg(Color::DarkGray))
+        } else {
+            Line::from(self.message.as_str()).style(Style::new().fg(Color::Yellow))
+        };
+        frame.render_widget(footer, footer_area);
+    }
+
+    /// The full results of the selected day, including art, error messages and
+    /// the expected answer of a failing part.
+    fn details(&self) -> Vec<Line<'_>> {
+        let row = &self.rows[self.selected()];
+        let entry = row.entry;
+        let mut lines =
+            vec![
+                Line::from(format!("{} day {}: {}", entry.year, entry.day, entry.title))
+                    .style(Style::new().add_modifier(Modifier::BOLD)),
+            ];
+
+        for (part, run) in (1..=2).zip(&row.parts) {
+            let Some(run) = run else {
+                lines.push(Line::from(format!("part {part}: not run yet")));
+                continue;
+            };
+
+            let elapsed = Duration::from_nanos(run.duration_ns);
+            let (text, style) = match &run.outcome {
+                Outcome::Answer(answer) | Outcome::Art(answer) => (answer.as_str(), Style::new()),
+                Outcome::Error(error) | Outcome::Panic(error) => {
+                    (error.as_str(), Style::new().fg(Color::Red))
+                }
+            };
+            let mut first = format!("part {part}: ");
+            if !matches!(run.outcome, Outcome::Art(_)) {
+                first += text;
+            }
+            first += &format!("  ({elapsed:.2?})");
+            if run.status == Status::Fail {
+                if let Some(expected) = self
+                    .answers
+                    .as_ref()
+                    .and_then(|answers| answers.get(entry.year, entry.day, part))
+                {
+                    first += &format!("  expected {expected}");
+                }
+            }
+            lines.push(Line::from(first).style(style));
+
+            if let Outcome::Art(art) = &run.outcome {
+                lines.extend(art.lines().map(|line| Line::from(format!("  {line}"))));
+            }
+        }
+
+        lines
+    }
+}
+
+/// The answer, status and time cells of one part.
+fn part_cells(run: Option<&LastRun>, queued: bool) -> [TableCell<'static>; 3] {
+    let Some(run) = run else {
+        let answer = if queued { "queued" } else { "" };
+        return [answer.into(), "".into(), "".into()];
+    };
+
+    let answer = match &run.outcome {
+        Outcome::Answer(answer) => answer.clone(),
+        Outcome::Art(_) => "(art)".to_string(),
+        Outcome::Error(_) => "error".to_string(),
+        Outcome::Panic(_) => "panic".to_string(),
+    };
+    let (mark, color) = match run.status {
+        Status::Pass => ("✓", Color::Green),
+        Status::Fail => ("✗", Color::Red),
+        Status::Missing => ("?", Color::DarkGray),
+        Status::Error => ("!", Color::Red),
+        Status::Timeout => ("⧗", Color::Yellow),
+    };
+    let elapsed = Duration::from_nanos(run.duration_ns);
+    let time = Span::from(format!("{elapsed:>10.2?}"));
+    let time = if queued {
+        time.style(Style::new().fg(Color::DarkGray))
+    } else if elapsed >= SLOW {
+        time.style(Style::new().fg(Color::Yellow))
+    } else {
+        time
+    };
+
+    [
+        TableCell::from(answer),
+        TableCell::from(if queued { "…" } else { mark }).style(Style::new().fg(color)),
+        TableCell::from(time),
+    ]
+}
+
+/// Runs `part` of `entry` against its input from `inputs`, catching a panic
+/// as [`Outcome::Panic`].
+fn run_part(
+    entry: &Entry,
+    part: u8,
+    inputs: &dyn InputProvider,
+    answers: Option<&Answers>,
+) -> LastRun {
+    let input = match inputs.load(entry.year, entry.day) {
+        Ok(input) => input,
+        Err(error) => {
+            return LastRun {
+                outcome: Outcome::Error(error.to_string()),
+                status: Status::Error,
+                duration_ns: 0,
+            }
+        }
+    };
+
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| entry.solve(part, &input)));
+    let elapsed = start.elapsed();
+    let duration_ns = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+
+    let result = match result {
+        Ok(result) => result,
+        Err(payload) => {
+            return LastRun {
+                outcome: Outcome::Panic(panic_message(payload.as_ref())),
+                status: Status::Error,
+                duration_ns,
+            }
+        }
+    };
+    let outcome = match &result {
+        Ok(Answer::Art(art)) => Outcome::Art(art.clone()),
+        Ok(answer) => Outcome::Answer(answer.to_string()),
+        Err(error) => Outcome::Error(error.to_string()),
+    };
+    let expected = answers.and_then(|answers| answers.get(entry.year, entry.day, part));
+    let status = verify::status(
+        &PartRun {
+            part,
+            result,
+            elapsed,
+        },
+        expected,
+    );
+
+    LastRun {
+        outcome,
+        status,
+        duration_ns,
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    sandbox::panic_message(payload, PANIC_LOCATION.take())
+}
+
+/// Keeps panics in the worker off the screen, since they are shown in the
+/// table instead, and restores the terminal before reporting any other panic.
+fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() == Some(WORKER) {
+            PANIC_LOCATION.set(info.location().map(ToString::to_string));
+        } else {
+            let _ = leave();
+            previous(info);
+        }
+    }));
+}
+
+fn enter() -> io::Result<()> {
+    terminal::enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)
+}
+
+fn leave() -> io::Result<()> {
+    terminal::disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)
+}
+
+/// Shows `input` in `$PAGER`, or `less`.
+fn page(input: &str) -> Result<(), String> {
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less".to_string());
+    let mut words = pager.split_whitespace();
+    let mut child = Command::new(words.next().unwrap_or_default())
+        .args(words)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("failed to run `{pager}`: {error}"))?;
+
+    // The pager closes its end when it quits before reading everything.
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    child
+        .wait()
+        .map(drop)
+        .map_err(|error| format!("failed to run `{pager}`: {error}"))
+}
+
+fn key(entry: &Entry, part: u8) -> String {
+    format!("{}.{}.{part}", entry.year, entry.day)
+}
+
+/// Reads the remembered results, starting afresh if there are none or they
+/// can't be read.
+fn load_history(path: &Path) -> History {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|history| serde_json::from_str(&history).ok())
+        .unwrap_or_default()
+}
+
+fn save_history(path: &Path, history: &History) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(history)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use advent_of_code::{
+        solution::{Timed, TimedPart},
+        InputError, SolveError,
+    };
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+
+    struct Fixed(&'static str);
+
+    impl InputProvider for Fixed {
+        fn load(&self, _: u16, _: u8) -> Result<Cow<'static, str>, InputError> {
+            Ok(Cow::Borrowed(self.0))
+        }
+    }
+
+    #[test]
+    fn test_run_part() {
+        let entry = solution::find(2015, 1).unwrap();
+        let run = run_part(entry, 1, &Fixed("((("), None);
+        assert_eq!(run.outcome, Outcome::Answer("3".to_string()));
+        assert_eq!(run.status, Status::Missing);
+
+        let answers = Answers::load(Path::new("../../answers.toml")).unwrap();
+        let run = run_part(entry, 1, &Fixed("((("), Some(&answers));
+        assert_eq!(run.status, Status::Fail);
+
+        let panics = Entry {
+            phases: |_, parts, _| match parts {
+                [1] => panic!("boom"),
+                _ => Timed {
+                    parse: Duration::ZERO,
+                    parts: vec![TimedPart {
+                        part: 2,
+                        result: Err(SolveError::no_solution("nothing")),
+                        elapsed: Duration::ZERO,
+                    }],
+                },
+            },
+            ..*entry
+        };
+        let run = run_part(&panics, 1, &Fixed(""), None);
+        assert!(matches!(&run.outcome, Outcome::Panic(message) if message.ends_with("boom")));
+        assert_eq!(run.status, Status::Error);
+        let run = run_part(&panics, 2, &Fixed(""), None);
+        assert_eq!(
+            run.outcome,
+            Outcome::Error("no solution found: nothing".to_string())
+        );
+    }
+
+    #[test]
+    fn test_app() {
+        let entries = solution::solutions_for(2015).take(3).collect();
+        let mut app = App::new(entries, History::new(), None, String::new());
+        let press = |code| KeyEvent::from(code);
+
+        assert_eq!(
+            app.handle(press(KeyCode::Char('1'))),
+            Action::Run(vec![(0, 1)])
+        );
+        app.finish(
+            0,
+            1,
+            LastRun {
+                outcome: Outcome::Answer("138".to_string()),
+                status: Status::Pass,
+                duration_ns: 1_000,
+            },
+        );
+        app.handle(press(KeyCode::Down));
+        app.handle(press(KeyCode::Down));
+        app.handle(press(KeyCode::Down));
+        assert_eq!(
+            app.handle(press(KeyCode::Enter)),
+            Action::Run(vec![(2, 1), (2, 2)])
+        );
+        app.finish(
+            2,
+            2,
+            LastRun {
+                outcome: Outcome::Panic("panicked: boom".to_string()),
+                status: Status::Error,
+                duration_ns: 2_000_000_000,
+            },
+        );
+
+        // Sorting by time puts the slow day first and keeps it selected.
+        app.handle(press(KeyCode::Char('s')));
+        assert_eq!(app.order, [2, 0, 1]);
+        assert_eq!(app.selected(), 2);
+        assert_eq!(app.handle(press(KeyCode::Char('p'))), Action::Page(2));
+        assert_eq!(app.handle(press(KeyCode::Char('q'))), Action::Quit);
+        assert!(app.history.contains_key("2015.1.1"));
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let screen = terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(100)
+            .map(|line| line.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(screen.contains("Not Quite Lisp"));
+        assert!(screen.contains("138"));
+        assert!(screen.contains("panic"));
+        assert!(screen.contains("part 2: panicked: boom"));
+    }
+}
@@ -0,0 +1,219 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use advent_of_code::SolveError;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answers::{self, Answers, Expected},
+    config::Config,
+    report::{self, Format, Record},
+    run_all::{self, DayRun, PartRun},
+};
+
+/// Options for `aoc verify`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    pub run: run_all::Args,
+
+    /// The file holding the known answers, TOML or `.json`. Defaults to the
+    /// config file's `answers`, or `answers.toml`.
+    #[arg(long, env = "AOC_ANSWERS")]
+    pub answers: Option<PathBuf>,
+
+    /// Also fail if a part has no known answer.
+    #[arg(long)]
+    pub strict: bool,
+}
+
+/// How a part's answer compares to the known one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Pass,
+    Fail,
+    /// The answers file has nothing for this part.
+    Missing,
+    /// The part didn't produce an answer at all.
+    Error,
+    /// The part was given up on after its `--timeout`.
+    Timeout,
+}
+
+/// The machine-readable result of verifying one part: a [`Record`] plus the
+/// known answer and how the two compare.
+#[derive(Debug, Serialize)]
+struct VerifyRecord<'a> {
+    #[serde(flatten)]
+    record: Record<'a>,
+    expected: Option<&'a Expected>,
+    status: Status,
+}
+
+pub fn run(args: &Args, format: Format, config: &Config) -> ExitCode {
+    let answers = match Answers::load(&answers::path(args.answers.as_deref(), config)) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let (runs, input_errors) = match run_all::run_selected(&args.run, config) {
+        Ok(runs) => runs,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let checks = runs
+        .iter()
+        .flat_map(|run| run.parts.iter().map(move |part| (run, part)))
+        .map(|(run, part)| {
+            let expected = answers.get(run.entry.year, run.entry.day, part.part);
+            (run, part, expected, status(part, expected))
+        })
+        .collect::<Vec<_>>();
+
+    match format {
+        Format::Text => print_checks(&checks),
+        Format::Json | Format::Ndjson => {
+            let records = checks
+                .iter()
+                .map(|&(run, part, expected, status)| VerifyRecord {
+                    record: Record::new(
+                        (run.entry.year, run.entry.day, part.part),
+                        Some(run.entry.title),
+                        &part.result,
+                        (run.parse, part.elapsed),
+                    ),
+                    expected,
+                    status,
+                })
+                .collect::<Vec<_>>();
+            if format == Format::Json {
+                report::print_json(&records);
+            } else {
+                report::print_ndjson(&records);
+            }
+        }
+    }
+
+    let count = |wanted| checks.iter().filter(|check| check.3 == wanted).count();
+    if input_errors > 0
+        || count(Status::Fail) > 0
+        || count(Status::Error) > 0
+        || count(Status::Timeout) > 0
+        || (args.strict && count(Status::Missing) > 0)
+    {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Compares the answer `part` produced with the `expected` one.
+pub fn status(part: &PartRun, expected: Option<&Expected>) -> Status {
+    match (&part.result, expected) {
+        (Err(SolveError::Timeout(_)), _) => Status::Timeout,
+        (Err(_), _) => Status::Error,
+        (Ok(_), None) => Status::Missing,
+        (Ok(answer), Some(expected)) if expected.matches(answer) => Status::Pass,
+        (Ok(_), Some(_)) => Status::Fail,
+    }
+}
+
+fn print_checks(checks: &[(&DayRun, &PartRun, Option<&Expected>, Status)]) {
+    for &(run, part, expected, status) in checks {
+        let label = match status {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+            Status::Error => "ERROR",
+            Status::Timeout => "TIMEOUT",
+        };
+        println!(
+            "{} day {:>2} part {}  {label}",
+            run.entry.year, run.entry.day, part.part
+        );
+
+        match (&part.result, expected) {
+            (Err(error), _) => println!("    {error}"),
+            (Ok(answer), Some(expected)) if status == Status::Fail => {
+                print_diff(&expected.to_string(), &answer.to_string());
+            }
+            (Ok(answer), None) => {
+                for line in answer.to_string().lines() {
+                    println!("    got: {line}");
+                }
+            }
+            (Ok(_), _) => {}
+        }
+    }
+
+    let count = |wanted| checks.iter().filter(|check| check.3 == wanted).count();
+    println!();
+    println!(
+        "{} passed, {} failed, {} missing, {} errors, {} timed out",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing),
+        count(Status::Error),
+        count(Status::Timeout),
+    );
+}
+
+/// Prints `expected` and `got` one after the other, or line by line with
+/// `-`/`+` markers on the lines that differ if either spans several lines.
+fn print_diff(expected: &str, got: &str) {
+    if !expected.contains('\n') && !got.contains('\n') {
+        println!("    expected: {expected}");
+        println!("    got:      {got}");
+        return;
+    }
+
+    let expected = expected.lines().collect::<Vec<_>>();
+    let got = got.lines().collect::<Vec<_>>();
+    for i in 0..expected.len().max(got.len()) {
+        match (expected.get(i), got.get(i)) {
+            (Some(expected), Some(got)) if expected == got => println!("      {expected}"),
+            (expected, got) => {
+                if let Some(expected) = expected {
+                    println!("    - {expected}");
+                }
+                if let Some(got) = got {
+                    println!("    + {got}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use advent_of_code::Answer;
+
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let part = |result| PartRun {
+            part: 1,
+            result,
+            elapsed: Duration::ZERO,
+        };
+
+        let ok = part(Ok(Answer::Signed(138)));
+        assert_eq!(status(&ok, Some(&Expected::Signed(138))), Status::Pass);
+        assert_eq!(status(&ok, Some(&Expected::Signed(139))), Status::Fail);
+        assert_eq!(status(&ok, None), Status::Missing);
+
+        let err = part(Err(SolveError::no_solution("none")));
+        assert_eq!(status(&err, Some(&Expected::Signed(138))), Status::Error);
+
+        let timeout = part(Err(SolveError::Timeout(Duration::from_secs(1))));
+        assert_eq!(status(&timeout, None), Status::Timeout);
+    }
+}
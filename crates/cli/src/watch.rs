@@ -0,0 +1,291 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use serde::Deserialize;
+
+use crate::{config::Config, input, scaffold};
+
+/// Options for `aoc watch`.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The year of the day to watch.
+    pub year: u16,
+
+    /// The day to watch (1-25).
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+
+    /// How often to check for changes, in milliseconds.
+    #[arg(long, default_value_t = 500)]
+    pub interval: u64,
+
+    /// The root of the workspace. Defaults to the closest directory above the
+    /// current one that holds `crates/core`.
+    #[arg(long)]
+    pub root: Option<PathBuf>,
+}
+
+/// The parts of a run-all record that are compared between runs.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<serde_json::Value>,
+    pub duration_ns: u64,
+    pub error: Option<PartError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PartError {
+    pub message: String,
+}
+
+/// The modification time of every watched file.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+pub fn run(args: &Args, config: &Config) -> ExitCode {
+    let root = match args.root.clone().map_or_else(scaffold::find_root, Ok) {
+        Ok(root) => root,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let day_dir = root
+        .join("crates")
+        .join("core")
+        .join("src")
+        .join(format!("year_{}", args.year))
+        .join(format!("day_{:02}", args.day));
+    if !day_dir.is_dir() {
+        eprintln!("error: {} doesn't exist", day_dir.display());
+        return ExitCode::FAILURE;
+    }
+
+    let mut config = config.clone();
+    let mut watched = watched_paths(&day_dir, args.year, args.day, &config);
+    print_watched(&watched);
+
+    let mut previous = Vec::new();
+    let mut last = None;
+    loop {
+        let mut current = snapshot(&watched);
+        if last.as_ref() != Some(&current) {
+            // The config file may have moved the input somewhere else.
+            if let (Some(_), Some(path)) = (&last, config.path.clone()) {
+                match Config::load(&path) {
+                    Ok(reloaded) => config = reloaded,
+                    Err(error) => eprintln!("error: {error}"),
+                }
+                let now_watched = watched_paths(&day_dir, args.year, args.day, &config);
+                if now_watched != watched {
+                    watched = now_watched;
+                    print_watched(&watched);
+                    current = snapshot(&watched);
+                }
+            }
+            last = Some(current);
+            println!();
+            if let Some(results) = rebuild_and_run(&root, &config, args.year, args.day) {
+                print_results(&results, &previous);
+                previous = results;
+                run_examples(&root, args.year, args.day);
+            }
+        }
+        thread::sleep(Duration::from_millis(args.interval));
+    }
+}
+
+/// The day's module, its input wherever `run-all` would load it from, and the
+/// config file that may say where that is.
+fn watched_paths(day_dir: &Path, year: u16, day: u8, config: &Config) -> Vec<PathBuf> {
+    let mut watched = vec![day_dir.to_path_buf()];
+    watched.extend(input::provider(None, config).path(year, day));
+    watched.extend(config.path.clone());
+    watched
+}
+
+fn print_watched(watched: &[PathBuf]) {
+    for path in watched {
+        println!("watching {}", path.display());
+    }
+}
+
+/// Collects the modification times of `paths`, descending into directories.
+/// Files that are missing are left out, so creating one counts as a change.
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    fn visit(path: &Path, snapshot: &mut Snapshot) -> io::Result<()> {
+        let metadata = fs::metadata(path)?;
+        if metadata.is_dir() {
+            for entry in fs::read_dir(path)? {
+                visit(&entry?.path(), snapshot)?;
+            }
+        } else {
+            snapshot.insert(path.to_path_buf(), metadata.modified()?);
+        }
+        Ok(())
+    }
+
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        // A file may disappear while it's being saved; the next poll sees it.
+        let _ = visit(path, &mut snapshot);
+    }
+    snapshot
+}
+
+fn cargo(root: &Path) -> Command {
+    let mut command = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.current_dir(root);
+    command
+}
+
+/// Rebuilds the CLI and runs both parts of the day. Returns `None` if the
+/// build fails or the day's results can't be read.
+fn rebuild_and_run(root: &Path, config: &Config, year: u16, day: u8) -> Option<Vec<PartResult>> {
+    println!("building...");
+    let built = cargo(root)
+        .args(["build", "--release", "--quiet", "--package", "aoc-cli"])
+        .status()
+        .is_ok_and(|status| status.success());
+    if !built {
+        println!("build failed, waiting for changes");
+        return None;
+    }
+
+    let mut run = cargo(root);
+    run.args(["run", "--release", "--quiet", "--package", "aoc-cli", "--"]);
+    if let Some(path) = &config.path {
+        run.arg("--config").arg(path);
+    }
+    let output = run
+        .args(["--format", "ndjson", "run-all"])
+        .args(["--year", &year.to_string(), "--days", &day.to_string()])
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<PartResult>, _>>()
+        .ok()
+}
+
+/// Runs the day's tests, except for the ones against the puzzle input.
+fn run_examples(root: &Path, year: u16, day: u8) {
+    println!("running example tests...");
+    let _ = cargo(root)
+        .args([
+            "test",
+            "--release",
+            "--quiet",
+            "--package",
+            "advent-of-code",
+            "--lib",
+            "--",
+        ])
+        .args([&format!("year_{year}::day_{day:02}::"), "--skip", "puzzle"])
+        .status();
+}
+
+fn print_results(results: &[PartResult], previous: &[PartResult]) {
+    for result in results {
+        let before = previous.iter().find(|before| before.part == result.part);
+        println!("{}", describe(result, before));
+    }
+}
+
+/// One line summarizing `result`, noting how it changed since `before`.
+fn describe(result: &PartResult, before: Option<&PartResult>) -> String {
+    let answer = answer_text(result);
+    let elapsed = Duration::from_nanos(result.duration_ns);
+    let mut line = format!("part {}: {answer}  ({elapsed:.2?}", result.part);
+
+    if let Some(before) = before {
+        let was = answer_text(before);
+        if before.duration_ns > 0 {
+            #[allow(clippy::cast_precision_loss)]
+            let change = (result.duration_ns as f64 / before.duration_ns as f64 - 1.0) * 100.0;
+            line += &format!(", {change:+.1}%");
+        }
+        line += ")";
+        if was != answer {
+            line += &format!("  changed, was {was}");
+        }
+    } else {
+        line += ")";
+    }
+
+    line
+}
+
+fn answer_text(result: &PartResult) -> String {
+    match (&result.answer, &result.error) {
+        (_, Some(error)) => format!("error: {}", error.message),
+        (Some(serde_json::Value::String(answer)), None) => answer.clone(),
+        (Some(answer), None) => answer.to_string(),
+        (None, None) => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        let result = |answer: u64, duration_ns| PartResult {
+            part: 1,
+            answer: Some(answer.into()),
+            duration_ns,
+            error: None,
+        };
+
+        assert_eq!(
+            describe(&result(138, 1_500_000), None),
+            "part 1: 138  (1.50ms)"
+        );
+        assert_eq!(
+            describe(&result(138, 1_500_000), Some(&result(138, 2_000_000))),
+            "part 1: 138  (1.50ms, -25.0%)"
+        );
+        assert_eq!(
+            describe(&result(139, 3_000_000), Some(&result(138, 2_000_000))),
+            "part 1: 139  (3.00ms, +50.0%)  changed, was 138"
+        );
+
+        let failed = PartResult {
+            answer: None,
+            error: Some(PartError {
+                message: "no solution found".to_string(),
+            }),
+            ..result(0, 1000)
+        };
+        assert_eq!(
+            describe(&failed, Some(&result(138, 1000))),
+            "part 1: error: no solution found  (1.00µs, +0.0%)  changed, was 138"
+        );
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("day_01")).unwrap();
+        fs::write(dir.join("day_01").join("mod.rs"), "").unwrap();
+
+        let before = snapshot(&[dir.join("day_01"), dir.join("missing.txt")]);
+        fs::write(dir.join("missing.txt"), "").unwrap();
+        let after = snapshot(&[dir.join("day_01"), dir.join("missing.txt")]);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(before.len(), 1);
+        assert_eq!(after.len(), 2);
+        assert_ne!(before, after);
+    }
+}
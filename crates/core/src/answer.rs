@@ -0,0 +1,124 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The answer to one part of a puzzle, keeping track of what kind of value
+/// the day produced.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+    /// Letters drawn on a screen, one row per line.
+    Art(String),
+}
+
+impl Answer {
+    /// A stable, lowercase name for the variant, matching its serde tag.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Signed(_) => "signed",
+            Self::Unsigned(_) => "unsigned",
+            Self::Text(_) => "text",
+            Self::Art(_) => "art",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signed(value) => value.fmt(f),
+            Self::Unsigned(value) => value.fmt(f),
+            Self::Text(value) | Self::Art(value) => value.fmt(f),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident($inner:ty): $($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                #[allow(clippy::cast_lossless)]
+                fn from(value: $int) -> Self {
+                    Self::$variant(value as $inner)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Signed(i64): i8, i16, i32, i64, isize);
+impl_from!(Unsigned(u64): u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+/// Numbers compare by value, so `"42"` and `"+42"` both equal `Unsigned(42)`.
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            Self::Signed(value) => other.parse().ok() == Some(*value),
+            Self::Unsigned(value) => other.parse().ok() == Some(*value),
+            Self::Text(value) | Self::Art(value) => value == other,
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        match *self {
+            Self::Signed(value) => value == *other,
+            Self::Unsigned(value) => i64::try_from(value).is_ok_and(|value| value == *other),
+            Self::Text(_) | Self::Art(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comparisons() {
+        assert_eq!(Answer::from(42usize), 42);
+        assert_eq!(Answer::from(-7i32), -7);
+        assert_eq!(Answer::from(42u64), "42");
+        assert_eq!(Answer::from("abc"), "abc");
+        assert_ne!(Answer::from("42"), 42);
+        assert_ne!(Answer::Unsigned(u64::MAX), -1);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let answers = [
+            Answer::Signed(-3),
+            Answer::Unsigned(3),
+            Answer::Text("abc".to_string()),
+            Answer::Art("#.\n.#".to_string()),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(
+            json,
+            r##"[{"type":"signed","value":-3},{"type":"unsigned","value":3},{"type":"text","value":"abc"},{"type":"art","value":"#.\n.#"}]"##
+        );
+        assert_eq!(serde_json::from_str::<Vec<Answer>>(&json).unwrap(), answers);
+    }
+}
@@ -0,0 +1,222 @@
+use std::{borrow::Cow, error::Error, fmt, io, path::PathBuf, time::Duration};
+
+/// Everything that can go wrong while solving a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// No day of the year is registered.
+    UnknownYear(u16),
+    /// The year is known, but the day is not registered.
+    UnknownDay { year: u16, day: u8 },
+    /// Every day only has parts 1 and 2.
+    UnknownPart { year: u16, day: u8, part: u8 },
+    /// The puzzle input could not be parsed. `line` and `column` are 1-based.
+    MalformedInput {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input parsed, but the puzzle has no answer for it.
+    NoSolution(Cow<'static, str>),
+    /// Whoever ran the puzzle gave up on it after the given budget.
+    Timeout(Duration),
+    /// Solving the puzzle panicked, with the panic's message and location.
+    Panic(String),
+    /// The process solving the puzzle was killed by the given signal.
+    Signal(i32),
+    /// The process solving the puzzle exited with the given code without
+    /// reporting a result.
+    Exit(i32),
+    /// The process to solve the puzzle in couldn't be run, for the given
+    /// reason.
+    Spawn(String),
+    /// The process solving the puzzle couldn't allocate any more memory,
+    /// under the given limit in bytes if it had one.
+    OutOfMemory { limit: Option<u64> },
+    /// Another process solved the puzzle and failed with an error that is
+    /// only known here by its kind and message.
+    Relayed {
+        kind: Cow<'static, str>,
+        message: String,
+    },
+}
+
+impl SolveError {
+    /// Builds a [`SolveError::MalformedInput`] pointing at `at`.
+    ///
+    /// `at` is expected to be a subslice of `input`; the line and column are
+    /// derived from its position. If it is not, the error points at the start
+    /// of `input`.
+    #[must_use]
+    pub fn malformed(input: &str, at: &str, message: impl Into<String>) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len() && input.is_char_boundary(offset))
+            .unwrap_or(0);
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rfind('\n')
+            .map_or(before, |newline| &before[newline + 1..])
+            .chars()
+            .count()
+            + 1;
+
+        Self::MalformedInput {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Builds a [`SolveError::NoSolution`] giving `reason`.
+    #[must_use]
+    pub const fn no_solution(reason: &'static str) -> Self {
+        Self::NoSolution(Cow::Borrowed(reason))
+    }
+
+    /// A stable, lowercase name for the variant, for machine-readable output.
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::UnknownYear(_) => "unknown_year",
+            Self::UnknownDay { .. } => "unknown_day",
+            Self::UnknownPart { .. } => "unknown_part",
+            Self::MalformedInput { .. } => "malformed_input",
+            Self::NoSolution(_) => "no_solution",
+            Self::Timeout(_) => "timeout",
+            Self::Panic(_) => "panic",
+            Self::Signal(_) => "signal",
+            Self::Exit(_) => "exit",
+            Self::Spawn(_) => "spawn",
+            Self::OutOfMemory { .. } => "out_of_memory",
+            Self::Relayed { kind, .. } => kind,
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownYear(year) => write!(f, "year {year} is not implemented"),
+            Self::UnknownDay { year, day } => write!(f, "day {day} of {year} is not implemented"),
+            Self::UnknownPart { year, day, part } => {
+                write!(f, "day {day} of {year} has no part {part}")
+            }
+            Self::MalformedInput {
+                line,
+                column,
+                message,
+            } => write!(f, "malformed input at {line}:{column}: {message}"),
+            Self::NoSolution(reason) => write!(f, "no solution found: {reason}"),
+            Self::Timeout(budget) => write!(f, "timed out after {budget:.2?}"),
+            Self::Panic(message) | Self::Relayed { message, .. } => f.write_str(message),
+            Self::Signal(signal) => match signal_name(*signal) {
+                Some(name) => write!(f, "killed by signal {signal} ({name})"),
+                None => write!(f, "killed by signal {signal}"),
+            },
+            Self::Exit(code) => write!(f, "exited with code {code}"),
+            Self::Spawn(reason) => write!(f, "failed to run a child process: {reason}"),
+            Self::OutOfMemory { limit: None } => f.write_str("ran out of memory"),
+            Self::OutOfMemory { limit: Some(limit) } => {
+                write!(f, "ran out of memory under a limit of {limit} bytes")
+            }
+        }
+    }
+}
+
+/// The name of the signals whose numbers POSIX fixes.
+const fn signal_name(signal: i32) -> Option<&'static str> {
+    Some(match signal {
+        1 => "SIGHUP",
+        2 => "SIGINT",
+        3 => "SIGQUIT",
+        4 => "SIGILL",
+        6 => "SIGABRT",
+        8 => "SIGFPE",
+        9 => "SIGKILL",
+        11 => "SIGSEGV",
+        13 => "SIGPIPE",
+        14 => "SIGALRM",
+        15 => "SIGTERM",
+        _ => return None,
+    })
+}
+
+impl Error for SolveError {}
+
+/// Why the input for a day couldn't be loaded.
+#[derive(Debug)]
+pub enum InputError {
+    /// The year or day isn't registered.
+    Solve(SolveError),
+    /// The day is registered, but its input wasn't embedded into the library.
+    NotEmbedded {
+        year: u16,
+        day: u8,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Solve(error) => error.fmt(f),
+            Self::NotEmbedded { year, day } => write!(
+                f,
+                "no input is embedded for day {day} of {year}; set `{}` or enable the `embed-inputs` feature",
+                crate::input::INPUT_DIR_VAR
+            ),
+            Self::Io { path, source } => write!(f, "failed to read {}: {source}", path.display()),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Solve(error) => Some(error),
+            Self::NotEmbedded { .. } => None,
+            Self::Io { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_location() {
+        let input = "1x2x3\n4xfivex6\n";
+        let at = &input[8..12];
+        assert_eq!(
+            SolveError::malformed(input, at, "expected a number"),
+            SolveError::MalformedInput {
+                line: 2,
+                column: 3,
+                message: "expected a number".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_malformed_foreign_slice() {
+        let other = String::from("def");
+        let error = SolveError::malformed("abc", &other, "nope");
+        assert_eq!(error.to_string(), "malformed input at 1:1: nope");
+    }
+
+    #[test]
+    fn test_crash_display() {
+        assert_eq!(
+            SolveError::Signal(6).to_string(),
+            "killed by signal 6 (SIGABRT)"
+        );
+        assert_eq!(SolveError::Signal(64).to_string(), "killed by signal 64");
+        assert_eq!(SolveError::Exit(3).to_string(), "exited with code 3");
+        assert_eq!(SolveError::Signal(9).kind(), "signal");
+    }
+}
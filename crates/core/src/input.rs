@@ -0,0 +1,193 @@
+use std::{
+    borrow::Cow,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{error::InputError, solution, SolveError};
+
+/// The environment variable naming an [`InputDir`] to read inputs from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Wraps a day's input so that it is only compiled in with the
+/// `embed-inputs` feature, and is `None` otherwise.
+#[cfg(feature = "embed-inputs")]
+macro_rules! embedded {
+    ($input:expr) => {
+        Some($input)
+    };
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+macro_rules! embedded {
+    ($input:expr) => {
+        None
+    };
+}
+
+pub(crate) use embedded;
+
+/// Somewhere puzzle inputs can be loaded from.
+pub trait InputProvider: Sync {
+    /// Loads the input for `day` of `year`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider has no input for the day.
+    fn load(&self, year: u16, day: u8) -> Result<Cow<'static, str>, InputError>;
+
+    /// The file [`InputProvider::load`] reads the input for `day` of `year`
+    /// from, or `None` if it doesn't come from a file of its own.
+    fn path(&self, _year: u16, _day: u8) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// The inputs compiled into the library with the `embed-inputs` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Embedded;
+
+/// A directory holding one `<year>/day_<dd>.txt` file per day.
+#[derive(Debug, Clone)]
+pub struct InputDir {
+    dir: PathBuf,
+}
+
+/// The [`InputDir`] named by [`INPUT_DIR_VAR`] if it is set, and the
+/// [`Embedded`] inputs otherwise.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FromEnv;
+
+impl InputProvider for Embedded {
+    fn load(&self, year: u16, day: u8) -> Result<Cow<'static, str>, InputError> {
+        let entry = solution::find(year, day).ok_or_else(|| {
+            InputError::Solve(if solution::solutions_for(year).next().is_none() {
+                SolveError::UnknownYear(year)
+            } else {
+                SolveError::UnknownDay { year, day }
+            })
+        })?;
+
+        entry
+            .input
+            .map(Cow::Borrowed)
+            .ok_or(InputError::NotEmbedded { year, day })
+    }
+}
+
+impl InputDir {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The file holding the input for `day` of `year`.
+    #[must_use]
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day_{day:02}.txt"))
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl InputProvider for InputDir {
+    fn load(&self, year: u16, day: u8) -> Result<Cow<'static, str>, InputError> {
+        let path = self.path(year, day);
+        fs::read_to_string(&path)
+            .map(Cow::Owned)
+            .map_err(|source| InputError::Io { path, source })
+    }
+
+    fn path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        Some(self.path(year, day))
+    }
+}
+
+impl InputProvider for FromEnv {
+    fn load(&self, year: u16, day: u8) -> Result<Cow<'static, str>, InputError> {
+        match env::var_os(INPUT_DIR_VAR) {
+            Some(dir) if !dir.is_empty() => InputDir::new(dir).load(year, day),
+            _ => Embedded.load(year, day),
+        }
+    }
+
+    fn path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        env::var_os(INPUT_DIR_VAR)
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| InputDir::new(dir).path(year, day))
+    }
+}
+
+/// Loads the input for `day` of `year` through [`FromEnv`].
+///
+/// # Errors
+///
+/// Returns an error if the day isn't registered, or its input can't be found.
+pub fn load(year: u16, day: u8) -> Result<Cow<'static, str>, InputError> {
+    FromEnv.load(year, day)
+}
+
+/// The puzzle input for a test of `day` of `year`, loaded like [`load`].
+///
+/// Without the `embed-inputs` feature the tests using it are ignored, and
+/// are run with `--include-ignored` once [`INPUT_DIR_VAR`] is set.
+///
+/// # Panics
+///
+/// Panics if the input can't be loaded, such as it missing from
+/// [`INPUT_DIR_VAR`].
+#[cfg(test)]
+pub(crate) fn for_test(year: u16, day: u8) -> Cow<'static, str> {
+    load(year, day).unwrap_or_else(|error| panic!("{error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_dir() {
+        let dir = env::temp_dir().join(format!("aoc-core-input-{}", std::process::id()));
+        fs::create_dir_all(dir.join("2016")).unwrap();
+        fs::write(dir.join("2016").join("day_03.txt"), "1 2 3\n").unwrap();
+
+        let inputs = InputDir::new(&dir);
+        let input = inputs.load(2016, 3);
+        let missing = inputs.load(2016, 4);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(input.unwrap(), "1 2 3\n");
+        assert!(
+            matches!(missing, Err(InputError::Io { path, .. }) if path.ends_with("2016/day_04.txt"))
+        );
+    }
+
+    #[test]
+    fn test_embedded() {
+        assert!(matches!(
+            Embedded.load(2015, 26),
+            Err(InputError::Solve(SolveError::UnknownDay {
+                year: 2015,
+                day: 26
+            }))
+        ));
+        assert!(matches!(
+            Embedded.load(1999, 1),
+            Err(InputError::Solve(SolveError::UnknownYear(1999)))
+        ));
+
+        let input = Embedded.load(2015, 1);
+        if cfg!(feature = "embed-inputs") {
+            assert!(input.unwrap().starts_with('('));
+        } else {
+            assert!(matches!(
+                input,
+                Err(InputError::NotEmbedded { year: 2015, day: 1 })
+            ));
+        }
+    }
+}
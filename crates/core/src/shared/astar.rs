@@ -1,8 +1,10 @@
 use std::{
     cmp::Ordering,
     collections::{BinaryHeap, HashMap},
+    error::Error,
+    fmt,
     hash::Hash,
-    ops::Add,
+    ops::{Add, ControlFlow},
 };
 
 use num::Zero;
@@ -13,8 +15,72 @@ pub trait Cost = Clone + Ord + Add + Zero;
 #[derive(Debug, Clone)]
 pub struct AStar<N: Node, C: Cost> {
     meta: HashMap<N, Meta<N, C>>,
-    open: BinaryHeap<Open<N, C>>,
+    open: OpenSet<N, C>,
     path: Vec<(N, C)>,
+    stats: Stats,
+    /// The goals reached by the cheapest paths of [`AStar::solve_all`].
+    goals: Vec<N>,
+}
+
+/// Counts of what the last [`AStar::solve`] did, to tell why a search is
+/// slow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Nodes taken off the open set and closed, the goal included.
+    pub expansions: usize,
+    /// Cheaper routes found to nodes that were already closed, which the
+    /// search ignores. Any at all mean the heuristic isn't consistent: it
+    /// dropped by more than the cost of an edge somewhere, and the path found
+    /// may not be the cheapest.
+    pub reopenings: usize,
+    /// Nodes pushed onto the open set again because a cheaper route to them
+    /// was found while they were still on it.
+    pub duplicate_pushes: usize,
+    /// The most entries the open set held at once, duplicates included.
+    pub peak_open: usize,
+    /// The most nodes seen at once, open or closed.
+    pub peak_meta: usize,
+}
+
+/// Why [`AStar::solve_all`] can't list the cheapest paths: some of them go
+/// round a cycle of edges that cost nothing, so there's no end to them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroCostCycle;
+
+impl fmt::Display for ZeroCostCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the cheapest paths go round a cycle of edges that cost nothing"
+        )
+    }
+}
+
+impl Error for ZeroCostCycle {}
+
+/// Watches [`AStar::solve_observed`] expand nodes, to show the search as it
+/// goes or to stop it early.
+pub trait Observer<N, C> {
+    /// Called as `node`, reached at a cost of `cost`, is taken off the open
+    /// set, before it's checked for being the goal. Returning
+    /// [`ControlFlow::Break`] stops the search, which then finds no path.
+    fn expand(&mut self, node: &N, cost: &C, stats: &Stats) -> ControlFlow<()>;
+}
+
+/// Observes nothing.
+impl<N, C> Observer<N, C> for () {
+    fn expand(&mut self, _: &N, _: &C, _: &Stats) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+impl<N, C, F> Observer<N, C> for F
+where
+    F: FnMut(&N, &C, &Stats) -> ControlFlow<()>,
+{
+    fn expand(&mut self, node: &N, cost: &C, stats: &Stats) -> ControlFlow<()> {
+        self(node, cost, stats)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -22,31 +88,98 @@ struct Meta<N: Node, C: Cost> {
     is_closed: bool,
     path: C,
     parent: Option<N>,
+    /// Other parents reaching the node just as cheaply, only kept by
+    /// [`AStar::solve_all`].
+    more_parents: Vec<N>,
+}
+
+impl<N: Node, C: Cost> Meta<N, C> {
+    fn parents(&self) -> impl Iterator<Item = &N> {
+        self.parent.iter().chain(&self.more_parents)
+    }
+
+    fn add_parent(&mut self, parent: &N) {
+        if !self.parents().any(|known| known == parent) {
+            self.more_parents.push(parent.clone());
+        }
+    }
 }
 
-#[derive(Debug, Clone, Eq)]
-struct Open<N: Node, C: Cost> {
-    cost: C,
-    node: N,
+/// The nodes a best-first search has yet to expand, the lowest cost so far
+/// plus heuristic first, and the latest queued of those tied.
+///
+/// Both [`AStar`] and the searches of [`super::search`] queue nodes through
+/// it, so that they all order them the same way.
+#[derive(Debug, Clone)]
+pub(crate) struct OpenSet<N, C> {
+    heap: BinaryHeap<Open<N, C>>,
+    counter: usize,
+}
+
+/// A node taken off an [`OpenSet`].
+#[derive(Debug, Clone)]
+pub(crate) struct Open<N, C> {
+    /// The cost it was reached at plus the heuristic.
+    pub(crate) priority: C,
+    /// The cost it was reached at.
+    pub(crate) cost: C,
+    pub(crate) node: N,
     counter: usize,
 }
 
-impl<N: Node, C: Cost> PartialEq for Open<N, C> {
+impl<N, C: Cost> OpenSet<N, C> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            counter: 0,
+        }
+    }
+
+    /// Queues `node`, reached at a cost of `cost`, which the heuristic
+    /// estimates to cost `heuristic` more to get from to a goal.
+    pub(crate) fn push(&mut self, node: N, cost: C, heuristic: C) {
+        self.counter += 1;
+        self.heap.push(Open {
+            priority: cost.clone() + heuristic,
+            cost,
+            node,
+            counter: self.counter,
+        });
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<Open<N, C>> {
+        self.heap.pop()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.heap.clear();
+        self.counter = 0;
+    }
+}
+
+impl<N, C: Ord> PartialEq for Open<N, C> {
     fn eq(&self, other: &Self) -> bool {
-        self.cost == other.cost
+        self.cmp(other) == Ordering::Equal
     }
 }
 
-impl<N: Node, C: Cost> PartialOrd for Open<N, C> {
+impl<N, C: Ord> Eq for Open<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Open<N, C> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
-impl<N: Node, C: Cost> Ord for Open<N, C> {
+
+impl<N, C: Ord> Ord for Open<N, C> {
     fn cmp(&self, other: &Self) -> Ordering {
         other
-            .cost
-            .cmp(&self.cost)
+            .priority
+            .cmp(&self.priority)
             .then(self.counter.cmp(&other.counter))
     }
 }
@@ -56,47 +189,222 @@ impl<N: Node, C: Cost> AStar<N, C> {
     pub fn new() -> Self {
         Self {
             meta: HashMap::new(),
-            open: BinaryHeap::new(),
+            open: OpenSet::new(),
             path: Vec::new(),
+            stats: Stats::default(),
+            goals: Vec::new(),
         }
     }
 
+    /// What the last search did, or is doing when asked by an [`Observer`].
+    #[must_use]
+    pub const fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
     #[must_use]
     pub fn into_last_path(self) -> Vec<(N, C)> {
         self.path
     }
 
     pub fn solve<FN, FH, FD, NI>(
+        &mut self,
+        init: N,
+        next: FN,
+        heuristic: FH,
+        is_done: FD,
+    ) -> Option<&Vec<(N, C)>>
+    where
+        FN: FnMut(&N) -> NI,
+        FH: FnMut(&N) -> C,
+        FD: FnMut(&N) -> bool,
+        NI: IntoIterator<Item = (N, C)>,
+    {
+        self.solve_observed(init, next, heuristic, is_done, &mut ())
+    }
+
+    /// Like [`AStar::solve`], but tells `observer` about every node as it's
+    /// expanded.
+    pub fn solve_observed<FN, FH, FD, NI>(
+        &mut self,
+        init: N,
+        next: FN,
+        heuristic: FH,
+        is_done: FD,
+        observer: &mut impl Observer<N, C>,
+    ) -> Option<&Vec<(N, C)>>
+    where
+        FN: FnMut(&N) -> NI,
+        FH: FnMut(&N) -> C,
+        FD: FnMut(&N) -> bool,
+        NI: IntoIterator<Item = (N, C)>,
+    {
+        self.path.clear();
+        if let Some(goal) = self.search(init, next, heuristic, is_done, observer, false) {
+            // Reconstruct the path
+            let mut current_node = Some(&goal);
+            while let Some(n) = current_node {
+                let meta = &self.meta[n];
+                self.path.push((n.clone(), meta.path.clone()));
+                current_node = meta.parent.as_ref();
+            }
+
+            self.path.reverse();
+        }
+
+        self.open.clear();
+        self.meta.clear();
+        (!self.path.is_empty()).then_some(&self.path)
+    }
+
+    /// Like [`AStar::solve`], but finds every cheapest path instead of just
+    /// one, and returns their cost. Count them with
+    /// [`AStar::count_optimal_paths`] and go through them with
+    /// [`AStar::optimal_paths`].
+    ///
+    /// The paths are only all found if `heuristic` is consistent: it never
+    /// overestimates, and never drops by more than the cost of the edge
+    /// between two nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ZeroCostCycle`] if the cheapest paths could go round a cycle
+    /// of free edges any number of times, and then finds none.
+    pub fn solve_all<FN, FH, FD, NI>(
+        &mut self,
+        init: N,
+        next: FN,
+        heuristic: FH,
+        is_done: FD,
+    ) -> Result<Option<C>, ZeroCostCycle>
+    where
+        FN: FnMut(&N) -> NI,
+        FH: FnMut(&N) -> C,
+        FD: FnMut(&N) -> bool,
+        NI: IntoIterator<Item = (N, C)>,
+    {
+        self.path.clear();
+        let goal = self.search(init, next, heuristic, is_done, &mut (), true);
+        self.open.clear();
+        if self.parents_first().is_none() {
+            self.goals.clear();
+            return Err(ZeroCostCycle);
+        }
+        Ok(goal.map(|goal| self.meta[&goal].path.clone()))
+    }
+
+    /// How many different cheapest paths the last [`AStar::solve_all`] found.
+    #[must_use]
+    pub fn count_optimal_paths(&self) -> usize {
+        // There's no cycle left, as `solve_all` forgets the goals if it
+        // finds one.
+        let order = self.parents_first().unwrap_or_default();
+        let mut counts = HashMap::<&N, usize>::with_capacity(order.len());
+        for node in order {
+            let meta = &self.meta[node];
+            let count = if meta.parent.is_none() {
+                1
+            } else {
+                meta.parents().map(|parent| counts[parent]).sum()
+            };
+            counts.insert(node, count);
+        }
+        self.goals.iter().map(|goal| counts[goal]).sum()
+    }
+
+    /// Every node on the cheapest paths of the last [`AStar::solve_all`],
+    /// each after all of its parents, or `None` if they go round in a cycle.
+    fn parents_first(&self) -> Option<Vec<&N>> {
+        /// Whether a node's parents are still being gone through, or were.
+        #[derive(PartialEq, Eq)]
+        enum Mark {
+            Open,
+            Done,
+        }
+
+        let mut marks = HashMap::new();
+        let mut order = Vec::new();
+        for goal in &self.goals {
+            if marks.contains_key(goal) {
+                continue;
+            }
+            marks.insert(goal, Mark::Open);
+            let mut stack = vec![(goal, self.meta[goal].parents())];
+            while let Some((node, parents)) = stack.last_mut() {
+                let node = *node;
+                let Some(parent) = parents.next() else {
+                    marks.insert(node, Mark::Done);
+                    order.push(node);
+                    stack.pop();
+                    continue;
+                };
+                match marks.get(parent) {
+                    // Back to a node whose parents led here.
+                    Some(Mark::Open) => return None,
+                    Some(Mark::Done) => {}
+                    None => {
+                        marks.insert(parent, Mark::Open);
+                        stack.push((parent, self.meta[parent].parents()));
+                    }
+                }
+            }
+        }
+        Some(order)
+    }
+
+    /// Every cheapest path the last [`AStar::solve_all`] found, with the cost
+    /// of reaching each node on it like [`AStar::solve`].
+    #[must_use]
+    pub fn optimal_paths(&self) -> OptimalPaths<'_, N, C> {
+        OptimalPaths {
+            meta: &self.meta,
+            goals: self.goals.iter(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Searches from `init` until reaching a goal, or until every cheapest
+    /// path to one was found if `all_paths`, and returns the first goal.
+    fn search<FN, FH, FD, NI>(
         &mut self,
         init: N,
         mut next: FN,
         mut heuristic: FH,
         mut is_done: FD,
-    ) -> Option<&Vec<(N, C)>>
+        observer: &mut impl Observer<N, C>,
+        all_paths: bool,
+    ) -> Option<N>
     where
         FN: FnMut(&N) -> NI,
         FH: FnMut(&N) -> C,
         FD: FnMut(&N) -> bool,
         NI: IntoIterator<Item = (N, C)>,
     {
-        // Used to get FIFO behaviour from the open set
-        let mut counter = 0;
-        self.path.clear();
+        let mut best: Option<C> = None;
+        self.meta.clear();
+        self.goals.clear();
+        self.stats = Stats {
+            peak_open: 1,
+            peak_meta: 1,
+            ..Stats::default()
+        };
         let init_heuristic = heuristic(&init);
         let init_meta = Meta {
             is_closed: false,
             path: C::zero(),
             parent: None,
+            more_parents: Vec::new(),
         };
         self.meta.insert(init.clone(), init_meta);
-        let init_open = Open {
-            node: init,
-            cost: init_heuristic,
-            counter,
-        };
-        self.open.push(init_open);
+        self.open.clear();
+        self.open.push(init, C::zero(), init_heuristic);
 
         while let Some(open) = self.open.pop() {
+            // Every cheapest path was found once all that's left is estimated
+            // to cost more.
+            if best.as_ref().is_some_and(|best| open.priority > *best) {
+                break;
+            }
             let meta = self.meta.get_mut(&open.node)?;
             // This can happen if the same node was inserted multiple times into the
             // open set, because a later found route to the same node actually had a
@@ -105,68 +413,116 @@ impl<N: Node, C: Cost> AStar<N, C> {
                 continue;
             }
             meta.is_closed = true;
+            self.stats.expansions += 1;
 
+            if observer
+                .expand(&open.node, &meta.path, &self.stats)
+                .is_break()
+            {
+                self.goals.clear();
+                return None;
+            }
             if is_done(&open.node) {
-                // Reconstruct the path
-                let mut current_node = Some(&open.node);
-                while let Some(n) = current_node {
-                    let meta = &self.meta[n];
-                    self.path.push((n.clone(), meta.path.clone()));
-                    current_node = meta.parent.as_ref();
+                if !all_paths {
+                    return Some(open.node);
                 }
-
-                self.path.reverse();
-
-                self.open.clear();
-                self.meta.clear();
-                return Some(&self.path);
+                if *best.get_or_insert_with(|| meta.path.clone()) == meta.path {
+                    self.goals.push(open.node);
+                }
+                continue;
             }
             let path_cost = meta.path.clone();
             for (node, edge_cost) in next(&open.node) {
-                let cost = if let Some(meta) = self.meta.get_mut(&node) {
-                    // If the node was already seen, and is in closed,
-                    // the shortest route is already established, and
-                    // there is no need to revisit the node.
-                    if meta.is_closed {
-                        continue;
-                    }
-                    // If the other node is already in the open set
-                    // but the cost through this parent node is cheaper
-                    // it has to be updated.
-                    let path_cost = edge_cost + path_cost.clone();
-                    if meta.path <= path_cost {
-                        continue;
+                let path_cost = edge_cost + path_cost.clone();
+                if let Some(meta) = self.meta.get_mut(&node) {
+                    match path_cost.cmp(&meta.path) {
+                        // Another way that's just as cheap, which only
+                        // matters when looking for all of them.
+                        Ordering::Equal if all_paths => {
+                            meta.add_parent(&open.node);
+                            continue;
+                        }
+                        // If the node was already seen, and is in closed,
+                        // the shortest route is already established, and
+                        // there is no need to revisit the node.
+                        Ordering::Less if meta.is_closed => {
+                            self.stats.reopenings += 1;
+                            continue;
+                        }
+                        // If the other node is already in the open set
+                        // but the cost through this parent node is cheaper
+                        // it has to be updated.
+                        Ordering::Less => {}
+                        Ordering::Equal | Ordering::Greater => continue,
                     }
                     // Update price
                     meta.path = path_cost.clone();
                     meta.parent = Some(open.node.clone());
-                    path_cost
+                    meta.more_parents.clear();
+                    self.stats.duplicate_pushes += 1;
                 } else {
-                    let path_cost = edge_cost + path_cost.clone();
-                    let heuristic_cost = heuristic(&node);
                     self.meta.insert(
                         node.clone(),
                         Meta {
                             is_closed: false,
                             path: path_cost.clone(),
                             parent: Some(open.node.clone()),
+                            more_parents: Vec::new(),
                         },
                     );
-                    path_cost + heuristic_cost
-                };
+                    self.stats.peak_meta = self.stats.peak_meta.max(self.meta.len());
+                }
 
-                counter += 1;
-                self.open.push(Open {
-                    cost,
-                    node,
-                    counter,
-                });
+                let heuristic = heuristic(&node);
+                self.open.push(node, path_cost, heuristic);
+                self.stats.peak_open = self.stats.peak_open.max(self.open.len());
             }
         }
 
-        self.open.clear();
-        self.meta.clear();
-        None
+        self.goals.first().cloned()
+    }
+}
+
+/// The cheapest paths found by [`AStar::solve_all`], see
+/// [`AStar::optimal_paths`].
+#[derive(Debug)]
+pub struct OptimalPaths<'a, N: Node, C: Cost> {
+    meta: &'a HashMap<N, Meta<N, C>>,
+    goals: std::slice::Iter<'a, N>,
+    /// The path being followed back from a goal, with how many parents of
+    /// each node on it were followed so far.
+    stack: Vec<(&'a N, usize)>,
+}
+
+impl<N: Node, C: Cost> Iterator for OptimalPaths<'_, N, C> {
+    type Item = Vec<(N, C)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.stack.is_empty() {
+                self.stack.push((self.goals.next()?, 0));
+            }
+
+            let (node, followed) = self.stack.last_mut().unwrap();
+            let meta = &self.meta[*node];
+            if meta.parent.is_none() {
+                let path = self
+                    .stack
+                    .iter()
+                    .rev()
+                    .map(|(node, _)| ((*node).clone(), self.meta[*node].path.clone()))
+                    .collect();
+                self.stack.pop();
+                return Some(path);
+            }
+
+            if let Some(parent) = meta.parents().nth(*followed) {
+                *followed += 1;
+                self.stack.push((parent, 0));
+            } else {
+                self.stack.pop();
+            }
+        }
     }
 }
 
@@ -175,3 +531,177 @@ impl<N: Node, C: Cost> Default for AStar<N, C> {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// From 0 to 3, where going through 2 is cheaper than straight to 1.
+    fn graph() -> impl FnMut(&u8) -> Vec<(u8, u32)> {
+        |node| match node {
+            0 => vec![(1, 5), (2, 1)],
+            1 => vec![(3, 1)],
+            2 => vec![(1, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut astar = AStar::new();
+        let path = astar.solve(0, graph(), |_| 0, |&node| node == 3).cloned();
+        assert_eq!(path, Some(vec![(0, 0), (2, 1), (1, 2), (3, 3)]));
+        assert_eq!(
+            *astar.stats(),
+            Stats {
+                expansions: 4,
+                reopenings: 0,
+                duplicate_pushes: 1,
+                peak_open: 2,
+                peak_meta: 4,
+            }
+        );
+
+        // A consistent heuristic never closes a node too early, even when
+        // a cheaper route to a node that's still open is found.
+        let next = |&node: &u8| match node {
+            0 => vec![(1, 1), (2, 5), (3, 1)],
+            1 => vec![(2, 1)],
+            3 => vec![(2, 0)],
+            2 => vec![(4, 10)],
+            _ => vec![],
+        };
+        let heuristic = |&node: &u8| u32::from(node == 2 || node == 3);
+        let path = astar.solve(0, next, heuristic, |&node| node == 4).cloned();
+        assert_eq!(path.unwrap().last(), Some(&(4, 11)));
+        assert_eq!(astar.stats().reopenings, 0);
+        assert_eq!(astar.stats().duplicate_pushes, 2);
+
+        // Overestimating the way on from 1 closes 3 before the cheaper
+        // route to it through 1 is found.
+        let heuristic = |&node: &u8| if node == 1 { 10 } else { 0 };
+        let next = |&node: &u8| match node {
+            0 => vec![(1, 1), (2, 4)],
+            1 | 2 => vec![(3, 1)],
+            3 => vec![(4, 100)],
+            _ => vec![],
+        };
+        let path = astar.solve(0, next, heuristic, |&node| node == 4).cloned();
+        assert_eq!(path.unwrap().last(), Some(&(4, 105)));
+        assert_eq!(astar.stats().reopenings, 1);
+    }
+
+    #[test]
+    fn test_cheaper_route_to_open_node() {
+        // S, A, B, C, G as 0 to 4: B is first queued at 5, then at 2 through
+        // A, and at 1 through C, with a consistent heuristic.
+        let next = |&node: &u8| match node {
+            0 => vec![(1, 1), (2, 5), (3, 1)],
+            1 => vec![(2, 1)],
+            3 => vec![(2, 0)],
+            2 => vec![(4, 10)],
+            _ => vec![],
+        };
+        let heuristic = |&node: &u8| u32::from(node == 2 || node == 3);
+
+        let mut astar = AStar::new();
+        let path = astar.solve(0, next, heuristic, |&node| node == 4).cloned();
+        assert_eq!(path.unwrap().last(), Some(&(4, 11)));
+        assert_eq!(
+            astar.solve_all(0, next, heuristic, |&node| node == 4),
+            Ok(Some(11))
+        );
+        assert_eq!(
+            astar.optimal_paths().collect::<Vec<_>>(),
+            [vec![(0, 0), (3, 1), (2, 1), (4, 11)]]
+        );
+    }
+
+    #[test]
+    fn test_observer() {
+        let mut expanded = Vec::new();
+        let mut observer = |&node: &u8, &cost: &u32, _: &Stats| {
+            expanded.push((node, cost));
+            if node == 1 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+
+        let mut astar = AStar::new();
+        let path = astar.solve_observed(0, graph(), |_| 0, |&node| node == 3, &mut observer);
+        assert_eq!(path, None);
+        assert_eq!(expanded, [(0, 0), (2, 1), (1, 2)]);
+        assert_eq!(astar.stats().expansions, 3);
+    }
+
+    #[test]
+    fn test_solve_all() {
+        // Right or down through a 3x3 grid, or straight to the corner for more.
+        let next = |&(x, y): &(u8, u8)| {
+            let mut next = vec![((x + 1, y), 1), ((x, y + 1), 1)];
+            if (x, y) == (0, 0) {
+                next.push(((2, 2), 5));
+            }
+            next.into_iter()
+                .filter(|&((x, y), _)| x <= 2 && y <= 2)
+                .collect::<Vec<_>>()
+        };
+        let manhattan = |&(x, y): &(u8, u8)| u32::from(4 - x - y);
+
+        let mut astar = AStar::new();
+        let cost = astar.solve_all((0, 0), next, manhattan, |&node| node == (2, 2));
+        assert_eq!(cost, Ok(Some(4)));
+        assert_eq!(astar.count_optimal_paths(), 6);
+
+        let paths = astar.optimal_paths().collect::<Vec<_>>();
+        assert_eq!(paths.len(), 6);
+        assert!(paths
+            .iter()
+            .all(|path| path.len() == 5 && path[0] == ((0, 0), 0) && path[4] == ((2, 2), 4)));
+        assert_eq!(
+            paths
+                .iter()
+                .map(|path| path.iter().map(|&(node, _)| node).collect::<Vec<_>>())
+                .collect::<std::collections::HashSet<_>>()
+                .len(),
+            6
+        );
+
+        // Several goals reached just as cheaply each count their own paths.
+        let cost = astar.solve_all((0, 0), next, |_| 0, |&(x, y)| x + y == 2);
+        assert_eq!(cost, Ok(Some(2)));
+        assert_eq!(astar.count_optimal_paths(), 4);
+        assert_eq!(astar.optimal_paths().count(), 4);
+
+        assert_eq!(astar.solve_all((0, 0), next, |_| 0, |_| false), Ok(None));
+        assert_eq!(astar.count_optimal_paths(), 0);
+        assert_eq!(astar.optimal_paths().next(), None);
+
+        // 1 and 2 lead to each other for free.
+        let mut astar = AStar::new();
+        let next = |&node: &u8| match node {
+            0 => vec![(1, 1)],
+            1 => vec![(2, 0)],
+            2 => vec![(1, 0), (3, 1)],
+            _ => vec![],
+        };
+        assert_eq!(
+            astar.solve_all(0, next, |_| 0, |&node| node == 3),
+            Err(ZeroCostCycle)
+        );
+        assert_eq!(astar.count_optimal_paths(), 0);
+        assert_eq!(astar.optimal_paths().next(), None);
+
+        // Too long a path to follow back one call at a time.
+        let mut astar = AStar::new();
+        let end = 100_000;
+        let next = |&node: &u32| [(node + 1, 1)];
+        assert_eq!(
+            astar.solve_all(0, next, |&node| end - node, |&node| node == end),
+            Ok(Some(end))
+        );
+        assert_eq!(astar.count_optimal_paths(), 1);
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::{HashMap, HashSet};
+
+use super::astar::{Cost, Node};
+
+/// Iterative-deepening A*, which finds cheapest paths like
+/// [`super::astar::AStar`] in a fraction of the memory.
+///
+/// Like A*, it only finds a cheapest path if the heuristic never
+/// overestimates, and may find a different one of several tied for the
+/// cheapest. The transposition table doesn't change that, as it never evicts
+/// a node: it stops taking new ones once full, and a node missing from it is
+/// searched again instead of skipped.
+///
+/// It's made of depth-first searches that each go as far as their bound on
+/// the cost plus the heuristic allows, with the next one raising the bound to
+/// the lowest estimate the last one turned back at.
+///
+/// Only the path being searched needs keeping, and a transposition table of
+/// the cheapest cost each node was reached at in the current search, which
+/// saves searching it again from further away. The table holds at most
+/// [`IdaStar::with_table_limit`] nodes, so memory stays bounded however big
+/// the state space is, at the cost of searching nodes again once it's full.
+#[derive(Debug, Clone)]
+pub struct IdaStar<N: Node, C: Cost> {
+    table: HashMap<N, C>,
+    table_limit: usize,
+    path: Vec<(N, C)>,
+    /// The nodes on `path`, to tell when it goes round in circles.
+    on_path: HashSet<N>,
+}
+
+/// How many nodes the transposition table of [`IdaStar::new`] holds.
+pub const DEFAULT_TABLE_LIMIT: usize = 1 << 20;
+
+/// How a depth-first search of [`IdaStar::solve`] ended.
+enum Bounded<C> {
+    Found,
+    /// Turned back at nodes estimated to cost at least this much.
+    Exceeded(C),
+    /// Found no node past the bound to go on to.
+    Exhausted,
+}
+
+impl<N: Node, C: Cost> IdaStar<N, C> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_table_limit(DEFAULT_TABLE_LIMIT)
+    }
+
+    /// A search whose transposition table holds at most `limit` nodes, or
+    /// none at all for 0.
+    #[must_use]
+    pub fn with_table_limit(limit: usize) -> Self {
+        Self {
+            table: HashMap::new(),
+            table_limit: limit,
+            path: Vec::new(),
+            on_path: HashSet::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn into_last_path(self) -> Vec<(N, C)> {
+        self.path
+    }
+
+    /// The cheapest path from `init` to a node that `is_done`, with the cost
+    /// of reaching each node on it, if `heuristic` never overestimates.
+    pub fn solve<FN, FH, FD, NI>(
+        &mut self,
+        init: N,
+        mut next: FN,
+        mut heuristic: FH,
+        mut is_done: FD,
+    ) -> Option<&Vec<(N, C)>>
+    where
+        FN: FnMut(&N) -> NI,
+        FH: FnMut(&N) -> C,
+        FD: FnMut(&N) -> bool,
+        NI: IntoIterator<Item = (N, C)>,
+    {
+        let mut bound = heuristic(&init);
+        self.path.clear();
+        self.on_path.clear();
+        self.on_path.insert(init.clone());
+        self.path.push((init, C::zero()));
+
+        loop {
+            self.table.clear();
+            match self.search(&bound, &mut next, &mut heuristic, &mut is_done) {
+                Bounded::Found => {
+                    self.table.clear();
+                    self.on_path.clear();
+                    return Some(&self.path);
+                }
+                Bounded::Exceeded(next_bound) => bound = next_bound,
+                Bounded::Exhausted => {
+                    self.table.clear();
+                    self.on_path.clear();
+                    self.path.clear();
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Searches on from the only node of `self.path` while the estimate
+    /// stays within `bound`, leaving the path to a goal in `self.path` if it
+    /// finds one.
+    ///
+    /// The search goes depth first, with the children of every node on the
+    /// path still to be gone through kept on a stack of its own, so that
+    /// however long the path gets it never runs out of call stack.
+    fn search<FN, FH, FD, NI>(
+        &mut self,
+        bound: &C,
+        next: &mut FN,
+        heuristic: &mut FH,
+        is_done: &mut FD,
+    ) -> Bounded<C>
+    where
+        FN: FnMut(&N) -> NI,
+        FH: FnMut(&N) -> C,
+        FD: FnMut(&N) -> bool,
+        NI: IntoIterator<Item = (N, C)>,
+    {
+        // For every node on the path whose children are being gone through,
+        // those still to go, and the lowest estimate turned back at so far
+        // beyond the ones gone through.
+        let mut stack: Vec<(NI::IntoIter, Option<C>)> = Vec::new();
+        let mut reached = true;
+
+        loop {
+            let outcome = if reached {
+                reached = false;
+                let (node, path_cost) = self.path.last().unwrap();
+                let estimate = path_cost.clone() + heuristic(node);
+                if estimate > *bound {
+                    Bounded::Exceeded(estimate)
+                } else if is_done(node) {
+                    return Bounded::Found;
+                } else {
+                    stack.push((next(node).into_iter(), None));
+                    continue;
+                }
+            } else {
+                let (children, exceeded) = stack.last_mut().unwrap();
+                let path_cost = self.path.last().unwrap().1.clone();
+                for (child, edge_cost) in children.by_ref() {
+                    let child_cost = path_cost.clone() + edge_cost;
+                    // Going round in circles never makes the path cheaper.
+                    if self.on_path.contains(&child) {
+                        continue;
+                    }
+                    // Neither does going on from where an earlier search of
+                    // this bound already went on from at no greater cost.
+                    if let Some(known) = self.table.get_mut(&child) {
+                        if *known <= child_cost {
+                            continue;
+                        }
+                        *known = child_cost.clone();
+                    } else if self.table.len() < self.table_limit {
+                        self.table.insert(child.clone(), child_cost.clone());
+                    }
+
+                    self.on_path.insert(child.clone());
+                    self.path.push((child, child_cost));
+                    reached = true;
+                    break;
+                }
+                if reached {
+                    continue;
+                }
+
+                let exceeded = exceeded.take();
+                stack.pop();
+                exceeded.map_or(Bounded::Exhausted, Bounded::Exceeded)
+            };
+
+            // Hand how the search from the last node went back to its parent.
+            let Some((_, exceeded)) = stack.last_mut() else {
+                return outcome;
+            };
+            let (node, _) = self.path.pop().unwrap();
+            self.on_path.remove(&node);
+            if let Bounded::Exceeded(estimate) = outcome {
+                if exceeded.as_ref().is_none_or(|lowest| estimate < *lowest) {
+                    *exceeded = Some(estimate);
+                }
+            }
+        }
+    }
+}
+
+impl<N: Node, C: Cost> Default for IdaStar<N, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::astar::AStar;
+
+    #[test]
+    fn test_solve() {
+        // A 6x6 grid with a wall down the middle, apart from the bottom row.
+        let neighbours = |&(x, y): &(i32, i32)| {
+            [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..6).contains(&x) && (0..6).contains(&y) && (x != 3 || y == 5))
+                .map(|cell| (cell, 1))
+                .collect::<Vec<_>>()
+        };
+        let manhattan = |&(x, y): &(i32, i32)| (5 - x).unsigned_abs() + y.unsigned_abs();
+        let goal = |&cell: &(i32, i32)| cell == (5, 0);
+        let expected = AStar::new()
+            .solve((0, 0), neighbours, manhattan, goal)
+            .unwrap()
+            .last()
+            .copied();
+        assert_eq!(expected, Some(((5, 0), 15)));
+
+        for limit in [0, 4, DEFAULT_TABLE_LIMIT] {
+            let mut ida_star = IdaStar::with_table_limit(limit);
+            let path = ida_star.solve((0, 0), neighbours, manhattan, goal).unwrap();
+            assert_eq!(path.last().copied(), expected);
+            assert_eq!(path.len(), 16);
+        }
+
+        let mut ida_star = IdaStar::new();
+        assert_eq!(
+            ida_star.solve((0, 0), neighbours, manhattan, |_| false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_long_path() {
+        // Too long a path to search one call per node.
+        let end = 100_000;
+        let next = |&node: &u32| [(node + 1, 1), (node.saturating_sub(1), 1)];
+        let mut ida_star = IdaStar::new();
+        let path = ida_star
+            .solve(0, next, |&node| end - node, |&node| node == end)
+            .unwrap();
+        assert_eq!(path.len(), 100_001);
+        assert_eq!(path.last(), Some(&(end, end)));
+    }
+}
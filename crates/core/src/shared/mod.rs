@@ -1,3 +1,7 @@
 pub mod astar;
 pub mod grid;
+pub mod ida_star;
+pub mod parse;
 pub mod point;
+pub mod rng;
+pub mod search;
@@ -0,0 +1,25 @@
+use std::fmt::Display;
+
+use crate::error::SolveError;
+
+/// Converts a missing or failed parse step into a [`SolveError::MalformedInput`]
+/// pointing at the offending part of the input.
+pub trait OrMalformed<T> {
+    /// # Errors
+    ///
+    /// Returns [`SolveError::MalformedInput`] located at `at`, which should be a
+    /// subslice of `input`, if `self` is `None` or `Err`.
+    fn or_malformed(self, input: &str, at: &str, message: &str) -> Result<T, SolveError>;
+}
+
+impl<T> OrMalformed<T> for Option<T> {
+    fn or_malformed(self, input: &str, at: &str, message: &str) -> Result<T, SolveError> {
+        self.ok_or_else(|| SolveError::malformed(input, at, message))
+    }
+}
+
+impl<T, E: Display> OrMalformed<T> for Result<T, E> {
+    fn or_malformed(self, input: &str, at: &str, message: &str) -> Result<T, SolveError> {
+        self.map_err(|error| SolveError::malformed(input, at, format!("{message}: {error}")))
+    }
+}
@@ -0,0 +1,59 @@
+/// A small, seedable pseudo-random number generator (`SplitMix64`), for
+/// generating puzzle inputs that can be reproduced from their seed.
+///
+/// It is neither cryptographically secure nor meant for anything but tests.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`, or 0 if `bound` is 0.
+    pub fn below(&mut self, bound: usize) -> usize {
+        if bound == 0 {
+            return 0;
+        }
+        // The bias is at most `bound / 2^64`, which is fine for test inputs.
+        let scaled = (u128::from(self.next_u64()) * bound as u128) >> 64;
+        #[allow(clippy::cast_possible_truncation)] // `scaled` is below `bound`
+        let scaled = scaled as usize;
+        scaled
+    }
+
+    /// A random element of `items`, or `None` if it's empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        items.get(self.below(items.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reproducible() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert!((0..100).all(|_| a.next_u64() == b.next_u64()));
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_below() {
+        let mut rng = Rng::new(0);
+        assert!((0..1000).all(|_| rng.below(6) < 6));
+        assert_eq!(rng.below(0), 0);
+        assert_eq!(rng.choose::<u8>(&[]), None);
+    }
+}
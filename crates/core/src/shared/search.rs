@@ -0,0 +1,592 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, VecDeque},
+};
+
+use super::astar::{Cost, Node, Open, OpenSet};
+
+/// A path found by one of the searches, from the start to a goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    /// Every node on the path, the start and the goal included.
+    pub nodes: Vec<N>,
+    /// The sum of the costs of the path's edges, or how many there are for
+    /// the breadth-first searches.
+    pub cost: C,
+}
+
+/// The path with the fewest edges from `start` to the first node that
+/// `is_goal`, where `successors` gives the nodes one edge away from a node.
+pub fn bfs<N, FN, I>(
+    start: N,
+    mut successors: FN,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Node,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(Path {
+                nodes: walk_back(node, |node| parents[node].as_ref()),
+                cost: steps,
+            });
+        }
+
+        for next in successors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// How many edges away from `start` every node reachable from it is,
+/// `start` included.
+pub fn distances<N, FN, I>(start: N, successors: FN) -> HashMap<N, usize>
+where
+    N: Node,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    reachable_within(start, usize::MAX, successors)
+}
+
+/// How many edges away from `start` every node at most `max_steps` edges
+/// away from it is, `start` included.
+pub fn reachable_within<N, FN, I>(
+    start: N,
+    max_steps: usize,
+    mut successors: FN,
+) -> HashMap<N, usize>
+where
+    N: Node,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if steps == max_steps {
+            continue;
+        }
+
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// The path with the fewest edges from `start` to `goal`, searched from both
+/// ends at once.
+///
+/// `predecessors` gives the nodes one edge before a node, which is the same
+/// as `successors` for an undirected graph.
+pub fn bidirectional_bfs<N, FS, FP, IS, IP>(
+    start: N,
+    goal: N,
+    mut successors: FS,
+    mut predecessors: FP,
+) -> Option<Path<N, usize>>
+where
+    N: Node,
+    FS: FnMut(&N) -> IS,
+    FP: FnMut(&N) -> IP,
+    IS: IntoIterator<Item = N>,
+    IP: IntoIterator<Item = N>,
+{
+    if start == goal {
+        return Some(Path {
+            nodes: vec![start],
+            cost: 0,
+        });
+    }
+
+    let mut forward = Side::new(start);
+    let mut backward = Side::new(goal);
+
+    while !forward.frontier.is_empty() && !backward.frontier.is_empty() {
+        // Growing the smaller side keeps both about as small as they can be.
+        let meeting = if forward.frontier.len() <= backward.frontier.len() {
+            forward.expand(&backward, &mut successors)
+        } else {
+            backward.expand(&forward, &mut predecessors)
+        };
+
+        if let Some(meeting) = meeting {
+            let mut nodes = walk_back(meeting.clone(), |node| forward.seen[node].1.as_ref());
+            let mut rest = walk_back(meeting, |node| backward.seen[node].1.as_ref());
+            rest.reverse();
+            nodes.extend(rest.into_iter().skip(1));
+            return Some(Path {
+                cost: nodes.len() - 1,
+                nodes,
+            });
+        }
+    }
+
+    None
+}
+
+/// One end of [`bidirectional_bfs`].
+struct Side<N> {
+    /// How far every node seen from this end is from it, and the node it was
+    /// reached from.
+    seen: HashMap<N, (usize, Option<N>)>,
+    /// The nodes seen last, which are all equally far away.
+    frontier: Vec<N>,
+}
+
+impl<N: Node> Side<N> {
+    fn new(end: N) -> Self {
+        Self {
+            seen: HashMap::from([(end.clone(), (0, None))]),
+            frontier: vec![end],
+        }
+    }
+
+    /// Moves the frontier one edge further, and returns the node on the
+    /// shortest path through both sides if they met.
+    fn expand<FN, I>(&mut self, other: &Self, neighbours: &mut FN) -> Option<N>
+    where
+        FN: FnMut(&N) -> I,
+        I: IntoIterator<Item = N>,
+    {
+        let mut frontier = Vec::new();
+        let mut meeting: Option<(usize, N)> = None;
+
+        for node in self.frontier.drain(..) {
+            let steps = self.seen[&node].0 + 1;
+            for next in neighbours(&node) {
+                if self.seen.contains_key(&next) {
+                    continue;
+                }
+                self.seen.insert(next.clone(), (steps, Some(node.clone())));
+
+                // Every meeting on this level is as far from this end, but not
+                // necessarily from the other one.
+                if let Some(&(other_steps, _)) = other.seen.get(&next) {
+                    if meeting.as_ref().is_none_or(|(best, _)| other_steps < *best) {
+                        meeting = Some((other_steps, next.clone()));
+                    }
+                }
+                frontier.push(next);
+            }
+        }
+
+        self.frontier = frontier;
+        meeting.map(|(_, node)| node)
+    }
+}
+
+/// The cheapest path from `start` to the first node that `is_goal`, where
+/// `successors` gives the nodes one edge away from a node along with the
+/// cost of that edge.
+pub fn dijkstra<N, C, FN, I>(
+    start: N,
+    successors: FN,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Node,
+    C: Cost,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::zero(), is_goal)
+}
+
+/// The cheapest path from `start` to every node reachable from it, `start`
+/// included, as in [`dijkstra`].
+pub fn dijkstra_distances<N, C, FN, I>(start: N, successors: FN) -> HashMap<N, C>
+where
+    N: Node,
+    C: Cost,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut best = HashMap::new();
+    best_first(start, successors, |_| C::zero(), |_| false, &mut best);
+    best.into_iter()
+        .map(|(node, (cost, _))| (node, cost))
+        .collect()
+}
+
+/// Like [`dijkstra`], but searches the nodes closest to a goal according to
+/// `heuristic` first.
+///
+/// The path found is only the cheapest if `heuristic` never overestimates
+/// the cost of getting from a node to a goal.
+pub fn astar<N, C, FN, FH, I>(
+    start: N,
+    successors: FN,
+    heuristic: FH,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Node,
+    C: Cost,
+    FN: FnMut(&N) -> I,
+    FH: FnMut(&N) -> C,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut best = HashMap::new();
+    let goal = best_first(start, successors, heuristic, is_goal, &mut best)?;
+
+    Some(Path {
+        cost: best[&goal].0.clone(),
+        nodes: walk_back(goal, |node| best[node].1.as_ref()),
+    })
+}
+
+/// Searches from `start` in order of the cost so far plus `heuristic`,
+/// recording the cheapest known cost of every node seen and its parent in
+/// `best`, and returns the first goal found.
+fn best_first<N, C, FN, FH, I>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: impl FnMut(&N) -> bool,
+    best: &mut HashMap<N, (C, Option<N>)>,
+) -> Option<N>
+where
+    N: Node,
+    C: Cost,
+    FN: FnMut(&N) -> I,
+    FH: FnMut(&N) -> C,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut open = OpenSet::new();
+    open.push(start.clone(), C::zero(), heuristic(&start));
+    best.insert(start, (C::zero(), None));
+
+    while let Some(Open { cost, node, .. }) = open.pop() {
+        // A cheaper way to the node was found after this one was queued.
+        if best[&node].0 < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(node);
+        }
+
+        for (next, edge) in successors(&node) {
+            let next_cost = cost.clone() + edge;
+            if best
+                .get(&next)
+                .is_some_and(|(known, _)| *known <= next_cost)
+            {
+                continue;
+            }
+
+            best.insert(next.clone(), (next_cost.clone(), Some(node.clone())));
+            let heuristic = heuristic(&next);
+            open.push(next, next_cost, heuristic);
+        }
+    }
+
+    None
+}
+
+/// Which of the paths to a goal [`dfs_paths`] returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keep {
+    /// Every path tied for the lowest cost.
+    Min,
+    /// Every path tied for the highest cost.
+    Max,
+    /// Every path, in the order they were found.
+    All,
+}
+
+/// Every path from one of `starts` to a goal, found by trying each one in
+/// turn, of which `keep` says which to return.
+///
+/// Unlike the other searches, the graph may depend on how a node was reached:
+/// `successors` and `is_goal` are given the whole path so far, and a node can
+/// be on any number of paths, so it's up to `successors` to not go round in
+/// circles. A path ends at its first goal. Each of `starts` comes with the
+/// cost of the path holding just it, and `fold` adds the cost of an edge to
+/// that of the path up to it.
+///
+/// `prune` is asked about every path before it's extended, along with the
+/// cost of the paths kept so far when keeping the cheapest or dearest ones,
+/// and drops it and everything beyond it if it returns `true`.
+pub fn dfs_paths<N, C, FN, I>(
+    starts: impl IntoIterator<Item = (N, C)>,
+    keep: Keep,
+    successors: FN,
+    fold: impl FnMut(C, C) -> C,
+    is_goal: impl FnMut(&[N]) -> bool,
+    prune: impl FnMut(&[N], &C, Option<&C>) -> bool,
+) -> Vec<Path<N, C>>
+where
+    N: Clone,
+    C: Clone + Ord,
+    FN: FnMut(&[N]) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut dfs = Dfs {
+        keep,
+        successors,
+        fold,
+        is_goal,
+        prune,
+        found: Vec::new(),
+    };
+    for (start, cost) in starts {
+        dfs.descend(&mut vec![start], cost);
+    }
+    dfs.found
+}
+
+/// The callbacks of [`dfs_paths`], and the paths it kept so far.
+struct Dfs<N, C, FN, FF, FG, FP> {
+    keep: Keep,
+    successors: FN,
+    fold: FF,
+    is_goal: FG,
+    prune: FP,
+    found: Vec<Path<N, C>>,
+}
+
+impl<N, C, FN, I, FF, FG, FP> Dfs<N, C, FN, FF, FG, FP>
+where
+    N: Clone,
+    C: Clone + Ord,
+    FN: FnMut(&[N]) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    FF: FnMut(C, C) -> C,
+    FG: FnMut(&[N]) -> bool,
+    FP: FnMut(&[N], &C, Option<&C>) -> bool,
+{
+    /// Follows every extension of `path`, which costs `cost`.
+    fn descend(&mut self, path: &mut Vec<N>, cost: C) {
+        let best = match self.keep {
+            Keep::Min | Keep::Max => self.found.first().map(|path| &path.cost),
+            Keep::All => None,
+        };
+        if (self.prune)(path, &cost, best) {
+            return;
+        }
+        if (self.is_goal)(path) {
+            self.record(path, cost);
+            return;
+        }
+
+        let next = (self.successors)(path).into_iter().collect::<Vec<_>>();
+        for (node, edge) in next {
+            let next_cost = (self.fold)(cost.clone(), edge);
+            path.push(node);
+            self.descend(path, next_cost);
+            path.pop();
+        }
+    }
+
+    fn record(&mut self, path: &[N], cost: C) {
+        let ordering = match (self.keep, self.found.first()) {
+            (Keep::All, _) | (_, None) => Ordering::Equal,
+            (Keep::Min, Some(best)) => best.cost.cmp(&cost),
+            (Keep::Max, Some(best)) => cost.cmp(&best.cost),
+        };
+        match ordering {
+            Ordering::Less => return,
+            Ordering::Greater => self.found.clear(),
+            Ordering::Equal => {}
+        }
+        self.found.push(Path {
+            nodes: path.to_vec(),
+            cost,
+        });
+    }
+}
+
+/// The path from the start to `end`, following `parent` back from `end`.
+fn walk_back<'a, N: Node + 'a>(end: N, parent: impl Fn(&N) -> Option<&'a N>) -> Vec<N> {
+    let mut nodes = vec![end];
+    while let Some(previous) = parent(nodes.last().unwrap()) {
+        nodes.push(previous.clone());
+    }
+    nodes.reverse();
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The open cells of a maze, as `(x, y)`.
+    const MAZE: &str = "\
+        ..#.....\n\
+        .##.###.\n\
+        ....#...\n\
+        .##.#.#.\n\
+        ....#.#.";
+
+    fn neighbours((x, y): (usize, usize)) -> Vec<(usize, usize)> {
+        let rows = MAZE.lines().map(str::as_bytes).collect::<Vec<_>>();
+        [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ]
+        .into_iter()
+        .filter(|&(x, y)| rows.get(y).and_then(|row| row.get(x)) == Some(&b'.'))
+        .collect()
+    }
+
+    #[test]
+    fn test_shortest_paths_agree() {
+        let (start, goal) = ((0, 0), (7, 4));
+
+        let shortest = bfs(start, |&node| neighbours(node), |&node| node == goal).unwrap();
+        assert_eq!(shortest.cost, 15);
+        assert_eq!(shortest.nodes.len(), 16);
+        assert_eq!((shortest.nodes[0], shortest.nodes[15]), (start, goal));
+        assert!(shortest
+            .nodes
+            .windows(2)
+            .all(|pair| neighbours(pair[0]).contains(&pair[1])));
+
+        let weighted = |&node: &(usize, usize)| neighbours(node).into_iter().map(|next| (next, 1));
+        let dijkstra = dijkstra(start, weighted, |&node| node == goal).unwrap();
+        let manhattan = |&(x, y): &(usize, usize)| goal.0.abs_diff(x) + goal.1.abs_diff(y);
+        let astar = astar(start, weighted, manhattan, |&node| node == goal).unwrap();
+        let bidirectional = bidirectional_bfs(
+            start,
+            goal,
+            |&node| neighbours(node),
+            |&node| neighbours(node),
+        )
+        .unwrap();
+        assert_eq!(dijkstra.cost, 15);
+        assert_eq!(astar.cost, 15);
+        assert_eq!(bidirectional.cost, 15);
+        assert_eq!(bidirectional.nodes.len(), 16);
+        assert!(bidirectional
+            .nodes
+            .windows(2)
+            .all(|pair| neighbours(pair[0]).contains(&pair[1])));
+
+        assert_eq!(bfs(start, |&node| neighbours(node), |_| false), None);
+        assert_eq!(
+            bidirectional_bfs(start, goal, |&node| neighbours(node), |_| Vec::new()),
+            None
+        );
+        assert_eq!(
+            bidirectional_bfs(
+                start,
+                start,
+                |&node| neighbours(node),
+                |&node| neighbours(node)
+            )
+            .map(|path| path.cost),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_prefers_cheap_edges() {
+        // 0 -> 1 -> 2 costs 2, while 0 -> 2 directly costs 5.
+        let edges = |&node: &u8| match node {
+            0 => vec![(1, 1), (2, 5)],
+            1 => vec![(2, 1)],
+            _ => vec![],
+        };
+        let path = dijkstra(0, edges, |&node| node == 2).unwrap();
+        assert_eq!(
+            path,
+            Path {
+                nodes: vec![0, 1, 2],
+                cost: 2
+            }
+        );
+        assert_eq!(
+            dijkstra_distances(0, edges),
+            HashMap::from([(0, 0), (1, 1), (2, 2)])
+        );
+    }
+
+    #[test]
+    fn test_astar_agrees_with_astar_search() {
+        // B (2) is reached ever more cheaply while it's still open.
+        let edges = |&node: &u8| match node {
+            0 => vec![(1, 1), (2, 5), (3, 1)],
+            1 => vec![(2, 1)],
+            3 => vec![(2, 0)],
+            2 => vec![(4, 10)],
+            _ => vec![],
+        };
+        let heuristic = |&node: &u8| u32::from(node == 2 || node == 3);
+        let path = astar(0, edges, heuristic, |&node| node == 4).unwrap();
+        assert_eq!(path.cost, 11);
+
+        let mut other = crate::shared::astar::AStar::new();
+        let other = other.solve(0, edges, heuristic, |&node| node == 4).unwrap();
+        assert_eq!(
+            other.iter().map(|&(node, _)| node).collect::<Vec<_>>(),
+            path.nodes
+        );
+    }
+
+    #[test]
+    fn test_distance_maps() {
+        let all = distances((0, 0), |&node| neighbours(node));
+        assert_eq!(all[&(0, 0)], 0);
+        assert_eq!(all[&(7, 4)], 15);
+        assert!(!all.contains_key(&(2, 0)));
+
+        let near = reachable_within((0, 0), 3, |&node| neighbours(node));
+        assert_eq!(near.len(), 6);
+        assert!(near.values().all(|&steps| steps <= 3));
+        assert!(near.iter().all(|(node, steps)| all[node] == *steps));
+    }
+
+    #[test]
+    fn test_dfs_paths() {
+        // Two ways from 0 to 3 through 1 or 2, and a dead end at 4.
+        let edges = |path: &[u8]| match path.last() {
+            Some(0) => vec![(1, 1), (2, 4), (4, 1)],
+            Some(1) => vec![(3, 1)],
+            Some(2) => vec![(3, 2)],
+            _ => vec![],
+        };
+        let goal = |path: &[u8]| path.last() == Some(&3);
+        let paths = |keep, prune: fn(&[u8], &u32, Option<&u32>) -> bool| {
+            dfs_paths([(0, 0)], keep, edges, |cost, edge| cost + edge, goal, prune)
+        };
+
+        let shortest = || Path {
+            nodes: vec![0, 1, 3],
+            cost: 2,
+        };
+        let longest = || Path {
+            nodes: vec![0, 2, 3],
+            cost: 6,
+        };
+        assert_eq!(paths(Keep::Min, |_, _, _| false), [shortest()]);
+        assert_eq!(paths(Keep::Max, |_, _, _| false), [longest()]);
+        assert_eq!(paths(Keep::All, |_, _, _| false), [shortest(), longest()]);
+        assert_eq!(paths(Keep::All, |path, _, _| path.contains(&2)).len(), 1);
+
+        // Cutting off whatever costs no less than the cheapest path so far.
+        let bounded = paths(Keep::Min, |_, cost, best| {
+            best.is_some_and(|best| cost >= best)
+        });
+        assert_eq!(bounded, [shortest()]);
+    }
+}
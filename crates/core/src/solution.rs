@@ -0,0 +1,369 @@
+use std::{
+    iter,
+    time::{Duration, Instant},
+};
+
+use crate::{answer::Answer, error::SolveError, year_2015, year_2016};
+
+/// A single day's puzzle, implemented once per `year_XXXX::day_YY` module.
+///
+/// The associated constants mirror the module-level `TITLE` and `INPUT`
+/// constants so the day can be discovered through the [`REGISTRY`] without
+/// naming the module. `INPUT` is `None` unless the `embed-inputs` feature is
+/// enabled; use an [`InputProvider`](crate::input::InputProvider) to load
+/// inputs either way.
+///
+/// The input is parsed once by [`Solution::parse`], and both parts solve the
+/// same parsed input, so that the two phases can be timed separately.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+    const TITLE: &'static str;
+    const INPUT: Option<&'static str>;
+
+    /// The parsed input, which may borrow from the input itself.
+    type Parsed<'a>;
+
+    /// # Errors
+    ///
+    /// Returns an error if `input` is malformed.
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the puzzle has no solution for the input.
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the puzzle has no solution for the input.
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError>;
+}
+
+/// A type-erased [`Solution`], as stored in the [`REGISTRY`].
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub input: Option<&'static str>,
+    /// Parses an input and solves the given parts of it, see
+    /// [`Entry::run_observed`].
+    pub phases: fn(&str, &[u8], &mut dyn Observer) -> Timed,
+    /// Other implementations of the day, see [`Entry::with_variants`].
+    pub variants: &'static [Variant],
+    /// Generates an input for the day from a seed, see
+    /// [`Entry::with_generator`].
+    pub generate: Option<fn(u64) -> String>,
+}
+
+/// A named alternate implementation of a day, such as a rewrite that is
+/// checked against the original before replacing it.
+#[derive(Debug, Clone, Copy)]
+pub struct Variant {
+    pub name: &'static str,
+    pub phases: fn(&str, &[u8], &mut dyn Observer) -> Timed,
+}
+
+impl Variant {
+    #[must_use]
+    pub const fn new<S: Solution>(name: &'static str) -> Self {
+        Self {
+            name,
+            phases: phases::<S>,
+        }
+    }
+}
+
+/// The name [`Entry::variant`] gives the day's own implementation.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// One phase of solving a day, see [`Observer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Parsing the input.
+    Parse,
+    /// Solving the given part of the parsed input.
+    Part(u8),
+}
+
+/// Watches the phases of [`Entry::run_observed`] start and end, to measure
+/// them in ways other than time.
+///
+/// Every call happens on the thread running the phase, outside of the time
+/// reported for it.
+pub trait Observer {
+    /// Called right before `phase` starts.
+    fn start(&mut self, phase: Phase);
+
+    /// Called right after `phase` ends.
+    fn end(&mut self, phase: Phase);
+
+    /// Called with each part's outcome as soon as it's known, before the
+    /// next part starts, including the parts that failed because parsing did.
+    fn solved(&mut self, _part: &TimedPart) {}
+}
+
+/// Observes nothing.
+impl Observer for () {
+    fn start(&mut self, _: Phase) {}
+
+    fn end(&mut self, _: Phase) {}
+}
+
+/// How long each phase of solving a day took, as returned by [`Entry::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timed {
+    /// How long parsing the input took.
+    pub parse: Duration,
+    /// The parts that were asked for, in the same order.
+    pub parts: Vec<TimedPart>,
+}
+
+/// One part's answer, and how long solving it took after parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedPart {
+    pub part: u8,
+    pub result: Result<Answer, SolveError>,
+    pub elapsed: Duration,
+}
+
+impl Entry {
+    #[must_use]
+    pub const fn new<S: Solution>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            title: S::TITLE,
+            input: S::INPUT,
+            phases: phases::<S>,
+            variants: &[],
+            generate: None,
+        }
+    }
+
+    /// Registers other implementations of the day, which
+    /// [`Entry::variant`] swaps in by name.
+    #[must_use]
+    pub const fn with_variants(self, variants: &'static [Variant]) -> Self {
+        Self { variants, ..self }
+    }
+
+    /// Registers a generator of valid inputs for the day, to compare its
+    /// variants on more than the puzzle input. The same seed must always give
+    /// the same input.
+    #[must_use]
+    pub const fn with_generator(self, generate: fn(u64) -> String) -> Self {
+        Self {
+            generate: Some(generate),
+            ..self
+        }
+    }
+
+    /// The names of every implementation of the day, starting with
+    /// [`DEFAULT_VARIANT`].
+    pub fn variant_names(&self) -> impl Iterator<Item = &'static str> {
+        iter::once(DEFAULT_VARIANT).chain(self.variants.iter().map(|variant| variant.name))
+    }
+
+    /// The day as solved by the variant called `name`, or `None` if it has
+    /// none by that name.
+    #[must_use]
+    pub fn variant(&self, name: &str) -> Option<Self> {
+        if name == DEFAULT_VARIANT {
+            return Some(*self);
+        }
+
+        self.variants
+            .iter()
+            .find(|variant| variant.name == name)
+            .map(|variant| Self {
+                phases: variant.phases,
+                ..*self
+            })
+    }
+
+    /// Parses `input` once and solves each of `parts` against it, timing the
+    /// parsing and every part separately.
+    ///
+    /// If parsing fails, every part holds its error and took no time. A part
+    /// other than 1 or 2 holds [`SolveError::UnknownPart`].
+    #[must_use]
+    pub fn run(&self, input: &str, parts: &[u8]) -> Timed {
+        self.run_observed(input, parts, &mut ())
+    }
+
+    /// Like [`Entry::run`], but tells `observer` as each phase starts and
+    /// ends and as each part is solved. A part doesn't start or end if
+    /// parsing failed.
+    #[must_use]
+    pub fn run_observed(&self, input: &str, parts: &[u8], observer: &mut dyn Observer) -> Timed {
+        (self.phases)(input, parts, observer)
+    }
+
+    /// Runs the given `part` of this day against `input`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::UnknownPart`] if `part` is neither 1 nor 2, or
+    /// whatever error the day itself reports.
+    pub fn solve(&self, part: u8, input: &str) -> Result<Answer, SolveError> {
+        self.run(input, &[part]).parts.swap_remove(0).result
+    }
+}
+
+fn phases<S: Solution>(input: &str, parts: &[u8], observer: &mut dyn Observer) -> Timed {
+    observer.start(Phase::Parse);
+    let start = Instant::now();
+    let parsed = S::parse(input);
+    let parse = start.elapsed();
+    observer.end(Phase::Parse);
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let (result, elapsed) = match &parsed {
+                Err(error) => (Err(error.clone()), Duration::ZERO),
+                Ok(parsed) => {
+                    observer.start(Phase::Part(part));
+                    let start = Instant::now();
+                    let result = match part {
+                        1 => S::part1(parsed),
+                        2 => S::part2(parsed),
+                        _ => Err(SolveError::UnknownPart {
+                            year: S::YEAR,
+                            day: S::DAY,
+                            part,
+                        }),
+                    };
+                    let elapsed = start.elapsed();
+                    observer.end(Phase::Part(part));
+                    (result, elapsed)
+                }
+            };
+
+            let part = TimedPart {
+                part,
+                result,
+                elapsed,
+            };
+            observer.solved(&part);
+            part
+        })
+        .collect();
+
+    Timed { parse, parts }
+}
+
+/// Every registered day, grouped by year in ascending order.
+pub static REGISTRY: &[&[Entry]] = &[year_2015::SOLUTIONS, year_2016::SOLUTIONS];
+
+/// Iterates over every registered day, ordered by year and then by day.
+pub fn solutions() -> impl Iterator<Item = &'static Entry> {
+    REGISTRY.iter().flat_map(|year| year.iter())
+}
+
+/// Iterates over every year that has at least one registered day.
+pub fn years() -> impl Iterator<Item = u16> {
+    REGISTRY
+        .iter()
+        .filter_map(|year| year.first().map(|entry| entry.year))
+}
+
+/// Iterates over the registered days of `year`.
+pub fn solutions_for(year: u16) -> impl Iterator<Item = &'static Entry> {
+    solutions().filter(move |entry| entry.year == year)
+}
+
+/// Looks up the day registered under `year` and `day`.
+#[must_use]
+pub fn find(year: u16, day: u8) -> Option<&'static Entry> {
+    solutions().find(|entry| entry.year == year && entry.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_ordered_and_unique() {
+        let keys = solutions()
+            .map(|entry| (entry.year, entry.day))
+            .collect::<Vec<_>>();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_run_observed() {
+        #[derive(Default)]
+        struct Log(Vec<(&'static str, Phase)>);
+
+        impl Observer for Log {
+            fn start(&mut self, phase: Phase) {
+                self.0.push(("start", phase));
+            }
+
+            fn end(&mut self, phase: Phase) {
+                self.0.push(("end", phase));
+            }
+
+            fn solved(&mut self, part: &TimedPart) {
+                self.0.push(("solved", Phase::Part(part.part)));
+            }
+        }
+
+        let entry = find(2015, 1).unwrap();
+        let mut log = Log::default();
+        let timed = entry.run_observed("(()))", &[2, 3], &mut log);
+        assert_eq!(timed.parts[0].result, Ok(Answer::Signed(5)));
+        assert!(timed.parts[1].result.is_err());
+        assert_eq!(
+            log.0,
+            [
+                ("start", Phase::Parse),
+                ("end", Phase::Parse),
+                ("start", Phase::Part(2)),
+                ("end", Phase::Part(2)),
+                ("solved", Phase::Part(2)),
+                ("start", Phase::Part(3)),
+                ("end", Phase::Part(3)),
+                ("solved", Phase::Part(3)),
+            ]
+        );
+
+        let mut log = Log::default();
+        let timed = entry.run_observed("(()x", &[1], &mut log);
+        assert!(timed.parts[0].result.is_err());
+        assert_eq!(
+            log.0,
+            [
+                ("start", Phase::Parse),
+                ("end", Phase::Parse),
+                ("solved", Phase::Part(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_variants() {
+        let entry = find(2015, 4).unwrap();
+        assert_eq!(
+            entry.variant_names().collect::<Vec<_>>(),
+            [DEFAULT_VARIANT, "md-5"]
+        );
+        let reference = entry.variant("md-5").unwrap();
+        assert_eq!(reference.solve(1, "abcdef"), entry.solve(1, "abcdef"));
+        assert!(entry.variant("nope").is_none());
+        assert_eq!((entry.generate.unwrap())(3), (entry.generate.unwrap())(3));
+    }
+
+    #[test]
+    fn test_registry_lookup() {
+        let entry = find(2015, 1).unwrap();
+        assert_eq!(entry.title, year_2015::day_01::TITLE);
+        assert_eq!(entry.input, year_2015::day_01::INPUT);
+        assert!(find(2015, 26).is_none());
+        assert_eq!(years().collect::<Vec<_>>(), [2015, 2016]);
+        assert_eq!(solutions_for(2016).count(), 25);
+    }
+}
@@ -1,43 +1,75 @@
 use algoritmer::higher_order_functions::Reductions;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Not Quite Lisp";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
-/// Panics if input is empty
-#[must_use]
-pub fn part1(input: &str) -> isize {
-    input
-        .lines()
-        .next()
-        .map(|s| s.chars().map(convert))
-        .unwrap()
-        .sum()
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 1;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<isize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
 }
 
-/// # Panics
-/// Panics if input is empty
-pub fn part2(input: &str) -> isize {
-    (input
+/// # Errors
+/// Returns an error if the input contains anything other than parentheses
+pub fn parse(input: &str) -> Result<Vec<isize>, SolveError> {
+    let line = input
         .lines()
         .next()
-        .map(|s| s.chars().map(convert))
-        .unwrap()
+        .or_malformed(input, input, "expected a line of parentheses")?;
+
+    line.char_indices()
+        .map(|(index, character)| {
+            convert(character).or_malformed(input, &line[index..], "expected `(` or `)`")
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn part1(moves: &[isize]) -> isize {
+    moves.iter().sum()
+}
+
+/// # Errors
+/// Returns an error if Santa never enters the basement
+pub fn part2(moves: &[isize]) -> Result<isize, SolveError> {
+    moves
+        .iter()
+        .copied()
         .reductions(0, std::ops::Add::add)
-        .enumerate()
-        .find(|(_, floor)| *floor == -1)
-        .unwrap()
-        .0)
-        .try_into()
-        .unwrap()
+        .position(|floor| floor == -1)
+        .map(|position| position as isize)
+        .ok_or(SolveError::no_solution("Santa never enters the basement"))
 }
 
-fn convert(character: char) -> isize {
+const fn convert(character: char) -> Option<isize> {
     match character {
-        '(' => 1,
-        ')' => -1,
-        _ => unreachable!(),
+        '(' => Some(1),
+        ')' => Some(-1),
+        _ => None,
     }
 }
 
@@ -47,61 +79,78 @@ mod tests {
 
     #[test]
     fn test_part1_example1() {
-        let result = part1("(())");
+        let result = part1(&parse("(())").unwrap());
         assert_eq!(result, 0);
     }
 
     #[test]
     fn test_part1_example2() {
-        let result = part1("()()");
+        let result = part1(&parse("()()").unwrap());
         assert_eq!(result, 0);
     }
 
     #[test]
     fn test_part1_example3() {
-        let result = part1("(((");
+        let result = part1(&parse("(((").unwrap());
         assert_eq!(result, 3);
     }
 
     #[test]
     fn test_part1_example4() {
-        let result = part1("(()(()(");
+        let result = part1(&parse("(()(()(").unwrap());
         assert_eq!(result, 3);
     }
 
     #[test]
     fn test_part1_example5() {
-        let result = part1("))(");
+        let result = part1(&parse("))(").unwrap());
         assert_eq!(result, -1);
     }
 
     #[test]
     fn test_part1_example6() {
-        let result = part1(")())())");
+        let result = part1(&parse(")())())").unwrap());
         assert_eq!(result, -3);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        let result = part1(INPUT);
+        let input = crate::input::for_test(2015, 1);
+        let result = part1(&parse(&input).unwrap());
         assert_eq!(result, 138);
     }
 
+    #[test]
+    fn test_parse_malformed() {
+        let result = parse("(()x");
+        assert_eq!(
+            result,
+            Err(SolveError::MalformedInput {
+                line: 1,
+                column: 4,
+                message: "expected `(` or `)`".to_string(),
+            })
+        );
+    }
+
     #[test]
     fn test_part2_example1() {
-        let result = part2(")");
+        let result = part2(&parse(")").unwrap()).unwrap();
         assert_eq!(result, 1);
     }
 
     #[test]
     fn test_part2_example2() {
-        let result = part2("()())");
+        let result = part2(&parse("()())").unwrap()).unwrap();
         assert_eq!(result, 5);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        let result = part2(INPUT);
+        let input = crate::input::for_test(2015, 1);
+        let result = part2(&parse(&input).unwrap()).unwrap();
         assert_eq!(result, 1771);
     }
 }
@@ -1,46 +1,73 @@
 use std::cmp::min;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "I Was Told There Would Be No Math";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
-/// * Panics if input is empty
-/// * Panics if string isn't ascii safe
-#[must_use]
-pub fn part1(input: &str) -> isize {
-    let mapping = |v: Vec<isize>| {
-        2 * (v[0] * v[1] + v[0] * v[2] + v[1] * v[2])
-            + min(v[0] * v[1], min(v[0] * v[2], v[1] * v[2]))
-    };
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 2;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<[isize; 3]>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
+/// Returns an error if a line isn't of the form `LxWxH`
+pub fn parse(input: &str) -> Result<Vec<[isize; 3]>, SolveError> {
     input
         .lines()
         .map(|line| {
-            line.split('x')
-                .map(|s: &str| s.parse::<isize>().unwrap())
-                .collect::<Vec<isize>>()
+            let mut dimensions = line.split('x').map(|s: &str| {
+                s.parse::<isize>()
+                    .or_malformed(input, s, "expected a dimension")
+            });
+            let mut next = || {
+                dimensions
+                    .next()
+                    .or_malformed(input, line, "expected three dimensions")?
+            };
+            Ok([next()?, next()?, next()?])
         })
-        .map(mapping)
-        .sum()
+        .collect()
+}
+
+#[must_use]
+pub fn part1(presents: &[[isize; 3]]) -> isize {
+    let mapping = |v: &[isize; 3]| {
+        2 * (v[0] * v[1] + v[0] * v[2] + v[1] * v[2])
+            + min(v[0] * v[1], min(v[0] * v[2], v[1] * v[2]))
+    };
+    presents.iter().map(mapping).sum()
 }
 
-/// # Panics
-/// * Panics if input is empty
-/// * Panics if string isn't ascii safe
 #[must_use]
-pub fn part2(input: &str) -> isize {
+pub fn part2(presents: &[[isize; 3]]) -> isize {
     let mapping =
-        |v: Vec<isize>| 2 * min(v[0] + v[1], min(v[0] + v[2], v[1] + v[2])) + v[0] * v[1] * v[2];
+        |v: &[isize; 3]| 2 * min(v[0] + v[1], min(v[0] + v[2], v[1] + v[2])) + v[0] * v[1] * v[2];
 
-    input
-        .lines()
-        .map(|line| {
-            line.split('x')
-                .map(|s: &str| s.parse::<isize>().unwrap())
-                .collect::<Vec<isize>>()
-        })
-        .map(mapping)
-        .sum()
+    presents.iter().map(mapping).sum()
 }
 
 #[cfg(test)]
@@ -49,37 +76,41 @@ mod tests {
 
     #[test]
     fn test_part1_example1() {
-        let result = part1("2x3x4");
+        let result = part1(&parse("2x3x4").unwrap());
         assert_eq!(result, 58);
     }
 
     #[test]
     fn test_part1_example2() {
-        let result = part1("1x1x10");
+        let result = part1(&parse("1x1x10").unwrap());
         assert_eq!(result, 43);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        let result = part1(INPUT);
+        let input = crate::input::for_test(2015, 2);
+        let result = part1(&parse(&input).unwrap());
         assert_eq!(result, 1_598_415);
     }
 
     #[test]
     fn test_part2_example1() {
-        let result = part2("2x3x4");
+        let result = part2(&parse("2x3x4").unwrap());
         assert_eq!(result, 34);
     }
 
     #[test]
     fn test_part2_example2() {
-        let result = part2("1x1x10");
+        let result = part2(&parse("1x1x10").unwrap());
         assert_eq!(result, 14);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        let result = part2(INPUT);
+        let input = crate::input::for_test(2015, 2);
+        let result = part2(&parse(&input).unwrap());
         assert_eq!(result, 3_812_909);
     }
 }
@@ -1,27 +1,63 @@
 use std::collections::HashSet;
 
-use crate::shared::point::Point;
+use crate::{
+    answer::Answer,
+    error::SolveError,
+    input::embedded,
+    shared::{parse::OrMalformed, point::Point},
+    solution::Solution,
+};
 
 pub const TITLE: &str = "Perfectly Spherical Houses in a Vacuum";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-pub fn part1(input: &str) -> usize {
-    let input = input
-        .chars()
-        .map(Direction::from)
-        .map(Point::from)
-        .collect::<Vec<Point>>();
-    deliver(&input, |_| true)
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 3;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Point>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
+/// Returns an error if the input contains anything other than `^`, `>`, `v` or `<`
+pub fn parse(input: &str) -> Result<Vec<Point>, SolveError> {
+    input
+        .trim_end()
+        .char_indices()
+        .map(|(index, character)| {
+            Direction::try_from(character)
+                .map(Point::from)
+                .or_malformed(input, &input[index..], "expected `^`, `>`, `v` or `<`")
+        })
+        .collect()
 }
 
-pub fn part2(input: &str) -> usize {
-    let input = input
-        .chars()
-        .map(Direction::from)
-        .map(Point::from)
-        .collect::<Vec<Point>>();
-    deliver(&input, |i| i % 2 == 0)
+#[must_use]
+pub fn part1(moves: &[Point]) -> usize {
+    deliver(moves, |_| true)
+}
+
+#[must_use]
+pub fn part2(moves: &[Point]) -> usize {
+    deliver(moves, |i| i % 2 == 0)
 }
 
 fn deliver(input: &[Point], predicate: fn(usize) -> bool) -> usize {
@@ -51,14 +87,16 @@ enum Direction {
     West,
 }
 
-impl From<char> for Direction {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '^' => Self::North,
-            '>' => Self::East,
-            'v' => Self::South,
-            '<' => Self::West,
-            _ => unreachable!(),
+            '^' => Ok(Self::North),
+            '>' => Ok(Self::East),
+            'v' => Ok(Self::South),
+            '<' => Ok(Self::West),
+            _ => Err(value),
         }
     }
 }
@@ -80,49 +118,53 @@ mod tests {
 
     #[test]
     fn test_part1_exmpale1() {
-        let result = part1(">");
+        let result = part1(&parse(">").unwrap());
         assert_eq!(result, 2);
     }
 
     #[test]
     fn test_part1_example2() {
-        let result = part1("^>v<");
+        let result = part1(&parse("^>v<").unwrap());
         assert_eq!(result, 4);
     }
 
     #[test]
     fn test_part1_example3() {
-        let result = part1("^v^v^v^v^v");
+        let result = part1(&parse("^v^v^v^v^v").unwrap());
         assert_eq!(result, 2);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        let result = part1(INPUT);
+        let input = crate::input::for_test(2015, 3);
+        let result = part1(&parse(&input).unwrap());
         assert_eq!(result, 2565);
     }
 
     #[test]
     fn test_part2_example1() {
-        let result = part2("^v");
+        let result = part2(&parse("^v").unwrap());
         assert_eq!(result, 3);
     }
 
     #[test]
     fn test_part2_example2() {
-        let result = part2("^>v<");
+        let result = part2(&parse("^>v<").unwrap());
         assert_eq!(result, 3);
     }
 
     #[test]
     fn test_part2_example3() {
-        let result = part2("^v^v^v^v^v");
+        let result = part2(&parse("^v^v^v^v^v").unwrap());
         assert_eq!(result, 11);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        let result = part2(INPUT);
+        let input = crate::input::for_test(2015, 3);
+        let result = part2(&parse(&input).unwrap());
         assert_eq!(result, 2639);
     }
 }
@@ -4,53 +4,113 @@ use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
 use rayon::prelude::*;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::rng::Rng, solution::Solution,
+};
+
+pub mod reference;
+
 pub const TITLE: &str = "The Ideal Stocking Stuffer";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 4;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
+/// Returns an error if the secret key doesn't fit in a single MD5 block
+pub fn parse(input: &str) -> Result<&str, SolveError> {
+    let prefix = input.trim();
+    if prefix.len() > MAX_PREFIX_LEN {
+        return Err(SolveError::malformed(
+            input,
+            prefix.get(MAX_PREFIX_LEN..).unwrap_or(prefix),
+            format!("secret key is longer than {MAX_PREFIX_LEN} bytes"),
+        ));
+    }
+
+    Ok(prefix)
+}
+
+#[must_use]
+pub fn part1(prefix: &str) -> u32 {
+    search(prefix, 0xffff_f000)
+}
 
 #[must_use]
-pub fn part1(input: &str) -> u32 {
-    let shared = parse(input);
-    shared.first.load(Ordering::Relaxed)
+pub fn part2(prefix: &str) -> u32 {
+    search(prefix, 0xffff_ff00)
 }
 
+/// Generates a secret key of eight lowercase letters.
 #[must_use]
-pub fn part2(input: &str) -> u32 {
-    let shared = parse(input);
-    shared.second.load(Ordering::Relaxed)
+pub fn generate(seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    (0..8)
+        .map(|_| char::from(b"abcdefghijklmnopqrstuvwxyz"[rng.below(26)]))
+        .collect()
 }
 
-struct Solution {
-    prefix: String,
+/// The longest key that still leaves room for a ten digit suffix and the MD5
+/// padding in one 64 byte block.
+const MAX_PREFIX_LEN: usize = 64 - 9 - 10;
+
+struct Shared<'a> {
+    prefix: &'a str,
+    mask: u32,
     done: AtomicBool,
     counter: AtomicU32,
-    first: AtomicU32,
-    second: AtomicU32,
+    found: AtomicU32,
 }
 
-fn parse(input: &str) -> Solution {
-    let shared = Solution {
-        prefix: input.trim().to_string(),
+/// Finds the lowest number whose hash, appended to `prefix`, has no bits of
+/// `mask` set.
+fn search(prefix: &str, mask: u32) -> u32 {
+    let shared = Shared {
+        prefix,
+        mask,
         done: AtomicBool::new(false),
         counter: AtomicU32::new(1000),
-        first: AtomicU32::new(u32::MAX),
-        second: AtomicU32::new(u32::MAX),
+        found: AtomicU32::new(u32::MAX),
     };
 
     // Handle the first 999 numbers specially as the number of digits varies.
     (1..1000).into_par_iter().for_each(|n| {
-        let (mut buffer, size) = format_string(&shared.prefix, n);
+        let (mut buffer, size) = format_string(shared.prefix, n);
         check_hash(&mut buffer, size, n, &shared);
     });
 
     // Use as many cores as possible to parallelize the remaining search.
-    rayon::scope(|scope| {
-        for _ in 0..rayon::current_num_threads() {
-            scope.spawn(|_| worker(&shared));
-        }
-    });
+    if !shared.done.load(Ordering::Relaxed) {
+        rayon::scope(|scope| {
+            for _ in 0..rayon::current_num_threads() {
+                scope.spawn(|_| worker(&shared));
+            }
+        });
+    }
 
-    shared
+    shared.found.load(Ordering::Relaxed)
 }
 
 fn format_string(prefix: &str, n: u32) -> ([u8; 64], usize) {
@@ -63,7 +123,7 @@ fn format_string(prefix: &str, n: u32) -> ([u8; 64], usize) {
     (buffer, size)
 }
 
-fn check_hash(buffer: &mut [u8], size: usize, n: u32, shared: &Solution) {
+fn check_hash(buffer: &mut [u8], size: usize, n: u32, shared: &Shared) {
     let (result, ..) = {
         let mut buffer: &mut [u8] = buffer;
         let end = buffer.len() - 8;
@@ -168,18 +228,16 @@ fn check_hash(buffer: &mut [u8], size: usize, n: u32, shared: &Solution) {
         (a0.to_be(), b0.to_be(), c0.to_be(), d0.to_be())
     };
 
-    if result & 0xffff_ff00 == 0 {
-        shared.second.fetch_min(n, Ordering::Relaxed);
+    if result & shared.mask == 0 {
+        shared.found.fetch_min(n, Ordering::Relaxed);
         shared.done.store(true, Ordering::Relaxed);
-    } else if result & 0xffff_f000 == 0 {
-        shared.first.fetch_min(n, Ordering::Relaxed);
     }
 }
 
-fn worker(shared: &Solution) {
+fn worker(shared: &Shared) {
     while !shared.done.load(Ordering::Relaxed) {
         let offset = shared.counter.fetch_add(1000, Ordering::Relaxed);
-        let (mut buffer, size) = format_string(&shared.prefix, offset);
+        let (mut buffer, size) = format_string(shared.prefix, offset);
 
         for n in 0..1000 {
             // Format macro is very slow, so update digits directly
@@ -231,17 +289,21 @@ mod tests {
 
     #[test]
     fn test_part1_examples() {
-        assert_eq!(part1("abcdef"), 609_043);
-        assert_eq!(part1("pqrstuv"), 1_048_970);
+        assert_eq!(part1(parse("abcdef").unwrap()), 609_043);
+        assert_eq!(part1(parse("pqrstuv").unwrap()), 1_048_970);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 254_575);
+        let input = crate::input::for_test(2015, 4);
+        assert_eq!(part1(parse(&input).unwrap()), 254_575);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 1_038_736);
+        let input = crate::input::for_test(2015, 4);
+        assert_eq!(part2(parse(&input).unwrap()), 1_038_736);
     }
 }
@@ -0,0 +1,63 @@
+use md5::{Digest, Md5};
+
+use crate::{answer::Answer, error::SolveError, solution::Solution};
+
+/// The same search on the `md-5` crate, one number after the other, to check
+/// the hand-written MD5 and the parallel search of [`super::Puzzle`] against.
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = super::Puzzle::YEAR;
+    const DAY: u8 = super::Puzzle::DAY;
+    const TITLE: &'static str = super::TITLE;
+    const INPUT: Option<&'static str> = super::INPUT;
+
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        super::parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        search(parsed, 5).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        search(parsed, 6).map(Answer::from)
+    }
+}
+
+/// Finds the lowest positive number whose hash, appended to `prefix`, starts
+/// with `zeros` hexadecimal zeros.
+///
+/// # Panics
+///
+/// Panics if `zeros` isn't from 1 to 8, as only the first four bytes of the
+/// hash are looked at.
+fn search(prefix: &str, zeros: u32) -> Result<u32, SolveError> {
+    assert!(
+        (1..=8).contains(&zeros),
+        "can only look for 1 to 8 zeros, not {zeros}"
+    );
+    (1..=u32::MAX)
+        .find(|n| {
+            let hash = Md5::digest(format!("{prefix}{n}"));
+            u32::from_be_bytes(hash[..4].try_into().unwrap()) >> (32 - 4 * zeros) == 0
+        })
+        .ok_or(SolveError::no_solution(
+            "no number gives a hash with enough leading zeros",
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search() {
+        assert_eq!(search("abcdef", 5), Ok(609_043));
+        assert_eq!(search("pqrstuv", 5), Ok(1_048_970));
+        assert_eq!(search("abcdef", 1), Ok(31));
+    }
+}
@@ -1,21 +1,69 @@
 use itertools::Itertools;
 
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
+
 pub const TITLE: &str = "Doesn't He Have Intern-Elves For This?";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 5;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
 
-pub fn part1(input: &str) -> usize {
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
+/// Returns an error if a line contains anything other than lowercase letters
+pub fn parse(input: &str) -> Result<Vec<&str>, SolveError> {
     input
         .lines()
+        .map(|line| {
+            line.find(|c: char| !c.is_ascii_lowercase())
+                .map_or(Ok(line), |index| {
+                    Err(SolveError::malformed(
+                        input,
+                        &line[index..],
+                        "expected a lowercase letter",
+                    ))
+                })
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn part1(strings: &[&str]) -> usize {
+    strings
+        .iter()
+        .copied()
         .filter(three_vowels)
         .filter(consecutive_characters)
         .filter(|s| !bad_strings(s))
         .count()
 }
 
-pub fn part2(input: &str) -> usize {
-    input
-        .lines()
+#[must_use]
+pub fn part2(strings: &[&str]) -> usize {
+    strings
+        .iter()
+        .copied()
         .filter(repeated_pair)
         .filter(repeated_either_side_of_one_character)
         .count()
@@ -61,28 +109,32 @@ mod tests {
 
     #[test]
     fn test_part1_examples() {
-        assert_eq!(part1("ugknbfddgicrmopn"), 1);
-        assert_eq!(part1("aaa"), 1);
-        assert_eq!(part1("jchzalrnumimnmhp"), 0);
-        assert_eq!(part1("haegwjzuvuyypxyu"), 0);
-        assert_eq!(part1("dvszwmarrgswjxmb"), 0);
+        assert_eq!(part1(&parse("ugknbfddgicrmopn").unwrap()), 1);
+        assert_eq!(part1(&parse("aaa").unwrap()), 1);
+        assert_eq!(part1(&parse("jchzalrnumimnmhp").unwrap()), 0);
+        assert_eq!(part1(&parse("haegwjzuvuyypxyu").unwrap()), 0);
+        assert_eq!(part1(&parse("dvszwmarrgswjxmb").unwrap()), 0);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 258);
+        let input = crate::input::for_test(2015, 5);
+        assert_eq!(part1(&parse(&input).unwrap()), 258);
     }
 
     #[test]
     fn test_part2_examples() {
-        assert_eq!(part2("qjhvhtzxzqqjkmpb"), 1);
-        assert_eq!(part2("xxyxx"), 1);
-        assert_eq!(part2("uurcxstgmygtbstg"), 0);
-        assert_eq!(part2("ieodomkazucvgmuy"), 0);
+        assert_eq!(part2(&parse("qjhvhtzxzqqjkmpb").unwrap()), 1);
+        assert_eq!(part2(&parse("xxyxx").unwrap()), 1);
+        assert_eq!(part2(&parse("uurcxstgmygtbstg").unwrap()), 0);
+        assert_eq!(part2(&parse("ieodomkazucvgmuy").unwrap()), 0);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 53);
+        let input = crate::input::for_test(2015, 5);
+        assert_eq!(part2(&parse(&input).unwrap()), 53);
     }
 }
@@ -2,45 +2,78 @@ use std::cmp::{max, min};
 
 use regex::{Matches, Regex};
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Probably a Fire Hazard";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 6;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<LightSwitch>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
+/// # Errors
+/// Returns an error if an invalid instruction is given.
+///
 /// # Panics
 /// * Panics if an invalid pattern is given.
+pub fn parse(input: &str) -> Result<Vec<LightSwitch>, SolveError> {
+    let points_regex = Regex::new(r"\d+").unwrap();
+    parse_switches(input, &points_regex)
+}
+
 #[must_use]
-pub fn part1(input: &str) -> usize {
+pub fn part1(switches: &[LightSwitch]) -> usize {
     let mut lights = vec![[false; 1000]; 1000].into_boxed_slice();
-    let points_regex = Regex::new(r"\d+").unwrap();
-    for light_switch in parse_switches(input, &points_regex) {
+    for light_switch in switches {
         match light_switch {
             LightSwitch::TurnOn(p1, p2) => {
-                update_bools(&p1, &p2, &mut lights, |_| true);
+                update_bools(p1, p2, &mut lights, |_| true);
             }
             LightSwitch::TurnOff(p1, p2) => {
-                update_bools(&p1, &p2, &mut lights, |_| false);
+                update_bools(p1, p2, &mut lights, |_| false);
             }
             LightSwitch::Toggle(p1, p2) => {
-                update_bools(&p1, &p2, &mut lights, |l| !l);
+                update_bools(p1, p2, &mut lights, |l| !l);
             }
         }
     }
     lights.iter().flatten().filter(|&x| *x).count()
 }
 
-/// # Panics
-/// * Panics if an invalid pattern is given.
 #[must_use]
-pub fn part2(input: &str) -> u32 {
+pub fn part2(switches: &[LightSwitch]) -> u32 {
     let mut lights = vec![[Light(0); 1000]; 1000].into_boxed_slice();
-    let points_regex = Regex::new(r"\d+").unwrap();
-    for light_switch in parse_switches(input, &points_regex) {
+    for light_switch in switches {
         match light_switch {
             LightSwitch::TurnOn(p1, p2) => {
-                update_lights(&p1, &p2, &mut lights, |l| l + 1);
+                update_lights(p1, p2, &mut lights, |l| l + 1);
             }
             LightSwitch::TurnOff(p1, p2) => {
-                update_lights(&p1, &p2, &mut lights, |l| {
+                update_lights(p1, p2, &mut lights, |l| {
                     if l > 0 {
                         return l - 1;
                     }
@@ -49,28 +82,45 @@ pub fn part2(input: &str) -> u32 {
                 });
             }
             LightSwitch::Toggle(p1, p2) => {
-                update_lights(&p1, &p2, &mut lights, |l| l + 2);
+                update_lights(p1, p2, &mut lights, |l| l + 2);
             }
         }
     }
     lights.iter().flatten().map(|l| u32::from(l.0)).sum()
 }
 
-struct Point {
+#[derive(Debug)]
+pub struct Point {
     x: usize,
     y: usize,
 }
 
 impl Point {
-    fn from_matches(matches: &mut Matches) -> Self {
-        Self {
-            x: matches.next().unwrap().as_str().parse().unwrap(),
-            y: matches.next().unwrap().as_str().parse().unwrap(),
-        }
+    fn from_matches(input: &str, line: &str, matches: &mut Matches) -> Result<Self, SolveError> {
+        let mut coordinate = || {
+            let found = matches
+                .next()
+                .or_malformed(input, line, "expected two coordinate pairs")?
+                .as_str();
+            match found.parse() {
+                Ok(value) if value < 1000 => Ok(value),
+                _ => Err(SolveError::malformed(
+                    input,
+                    found,
+                    "expected a coordinate between 0 and 999",
+                )),
+            }
+        };
+
+        Ok(Self {
+            x: coordinate()?,
+            y: coordinate()?,
+        })
     }
 }
 
-enum LightSwitch {
+#[derive(Debug)]
+pub enum LightSwitch {
     TurnOn(Point, Point),
     TurnOff(Point, Point),
     Toggle(Point, Point),
@@ -79,30 +129,34 @@ enum LightSwitch {
 #[derive(Copy, Clone)]
 struct Light(u8);
 
-fn parse_switches(input: &str, regex: &Regex) -> Vec<LightSwitch> {
+fn parse_switches(input: &str, regex: &Regex) -> Result<Vec<LightSwitch>, SolveError> {
     let mut result = Vec::new();
     for line in input.lines() {
         if line.starts_with("turn on ") {
-            let points = parse_points(regex, line);
+            let points = parse_points(input, regex, line)?;
             result.push(LightSwitch::TurnOn(points.0, points.1));
         } else if line.starts_with("turn off ") {
-            let points = parse_points(regex, line);
+            let points = parse_points(input, regex, line)?;
             result.push(LightSwitch::TurnOff(points.0, points.1));
         } else if line.starts_with("toggle ") {
-            let points = parse_points(regex, line);
+            let points = parse_points(input, regex, line)?;
             result.push(LightSwitch::Toggle(points.0, points.1));
         } else {
-            unreachable!()
+            return Err(SolveError::malformed(
+                input,
+                line,
+                "expected `turn on`, `turn off` or `toggle`",
+            ));
         }
     }
-    result
+    Ok(result)
 }
 
-fn parse_points(regex: &Regex, line: &str) -> (Point, Point) {
+fn parse_points(input: &str, regex: &Regex, line: &str) -> Result<(Point, Point), SolveError> {
     let mut matches = regex.find_iter(line);
-    let point1 = Point::from_matches(&mut matches);
-    let point2 = Point::from_matches(&mut matches);
-    (point1, point2)
+    let point1 = Point::from_matches(input, line, &mut matches)?;
+    let point2 = Point::from_matches(input, line, &mut matches)?;
+    Ok((point1, point2))
 }
 
 fn update_bools(p1: &Point, p2: &Point, lights: &mut Box<[[bool; 1000]]>, f: fn(bool) -> bool) {
@@ -129,32 +183,42 @@ mod test {
     fn test_part1_examples() {
         assert_eq!(
             part1(
-                &[
-                    "turn on 0,0 through 999,999",
-                    "toggle 0,0 through 999,0",
-                    "turn off 499,499 through 500,500"
-                ]
-                .join("\n")
+                &parse(
+                    &[
+                        "turn on 0,0 through 999,999",
+                        "toggle 0,0 through 999,0",
+                        "turn off 499,499 through 500,500"
+                    ]
+                    .join("\n")
+                )
+                .unwrap()
             ),
             998_996
         );
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 400_410);
+        let input = crate::input::for_test(2015, 6);
+        assert_eq!(part1(&parse(&input).unwrap()), 400_410);
     }
 
     #[test]
     fn test_part2_examples() {
         assert_eq!(
-            part2(&["turn on 0,0 through 0,0", "toggle 0,0 through 999,999",].join("\n")),
+            part2(
+                &parse(&["turn on 0,0 through 0,0", "toggle 0,0 through 999,999",].join("\n"))
+                    .unwrap()
+            ),
             2_000_001
         );
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 15_343_601);
+        let input = crate::input::for_test(2015, 6);
+        assert_eq!(part2(&parse(&input).unwrap()), 15_343_601);
     }
 }
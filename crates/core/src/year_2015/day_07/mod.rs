@@ -1,92 +1,147 @@
 use std::collections::HashMap;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Some Assembly Required";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-#[must_use]
-pub fn part1(input: &str) -> u16 {
-    parse(input).0
-}
+#[derive(Debug)]
+pub struct Puzzle;
 
-#[must_use]
-pub fn part2(input: &str) -> u16 {
-    parse(input).1
-}
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 7;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
 
-enum Gate<'a> {
-    Wire(&'a str),
-    Not(&'a str),
-    And(&'a str, &'a str),
-    Or(&'a str, &'a str),
-    LeftShift(&'a str, u16),
-    RightShift(&'a str, u16),
-}
+    type Parsed<'a> = Circuit<'a>;
 
-fn signal<'a>(
-    key: &'a str,
-    circuit: &HashMap<&'a str, Gate<'a>>,
-    cache: &mut HashMap<&'a str, u16>,
-) -> u16 {
-    if let Some(result) = cache.get(key) {
-        return *result;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
     }
 
-    let result = if key.chars().next().unwrap().is_ascii_digit() {
-        key.parse().unwrap()
-    } else {
-        match circuit[key] {
-            Gate::Wire(w) => signal(w, circuit, cache),
-            Gate::Not(w) => !signal(w, circuit, cache),
-            Gate::And(l, r) => signal(l, circuit, cache) & signal(r, circuit, cache),
-            Gate::Or(l, r) => signal(l, circuit, cache) | signal(r, circuit, cache),
-            Gate::LeftShift(w, n) => signal(w, circuit, cache) << n,
-            Gate::RightShift(w, n) => signal(w, circuit, cache) >> n,
-        }
-    };
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
 
-    cache.insert(key, result);
-    result
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
 }
 
-/// # Panics
-/// * Panics if `input` is empty
-#[must_use]
-fn parse(input: &str) -> (u16, u16) {
+/// # Errors
+/// Returns an error if an instruction can't be parsed
+pub fn parse(input: &str) -> Result<Circuit<'_>, SolveError> {
     let mut tokens = input.split_ascii_whitespace();
-    let mut circuit = HashMap::new();
+    let mut gates = HashMap::new();
+    let mut next = |after: &str| {
+        tokens
+            .next()
+            .or_malformed(input, after, "unexpected end of instruction")
+    };
 
-    while let (Some(first), Some(second)) = (tokens.next(), tokens.next()) {
+    while let Ok(first) = next(input) {
+        let second = next(first)?;
         let gate = if first == "NOT" {
-            let _third = tokens.next().unwrap();
+            let _third = next(second)?;
             Gate::Not(second)
         } else if second == "->" {
             Gate::Wire(first)
         } else {
-            let third = tokens.next().unwrap();
-            let _fourth = tokens.next().unwrap();
+            let third = next(second)?;
+            let _fourth = next(third)?;
 
             match second {
                 "AND" => Gate::And(first, third),
                 "OR" => Gate::Or(first, third),
-                "LSHIFT" => Gate::LeftShift(first, third.parse().unwrap()),
-                "RSHIFT" => Gate::RightShift(first, third.parse().unwrap()),
-                _ => unreachable!(),
+                "LSHIFT" => Gate::LeftShift(
+                    first,
+                    third
+                        .parse()
+                        .or_malformed(input, third, "expected a shift")?,
+                ),
+                "RSHIFT" => Gate::RightShift(
+                    first,
+                    third
+                        .parse()
+                        .or_malformed(input, third, "expected a shift")?,
+                ),
+                _ => return Err(SolveError::malformed(input, second, "unknown gate")),
             }
         };
 
-        let wire = tokens.next().unwrap();
-        circuit.insert(wire, gate);
+        let wire = next(first)?;
+        gates.insert(wire, gate);
     }
 
-    let mut cache = HashMap::new();
-    let result1 = signal("a", &circuit, &mut cache);
+    Ok(Circuit { input, gates })
+}
 
-    cache.clear();
-    cache.insert("b", result1);
-    let result2 = signal("a", &circuit, &mut cache);
+/// # Errors
+/// Returns an error if wire `a` has no signal
+pub fn part1(circuit: &Circuit) -> Result<u16, SolveError> {
+    signal(circuit.input, "a", &circuit.gates, &mut HashMap::new())
+}
 
-    (result1, result2)
+/// # Errors
+/// Returns an error if wire `a` has no signal
+pub fn part2(circuit: &Circuit) -> Result<u16, SolveError> {
+    let mut cache = HashMap::from([("b", part1(circuit)?)]);
+    signal(circuit.input, "a", &circuit.gates, &mut cache)
+}
+
+/// The gates of a circuit, keyed by the wire they drive.
+#[derive(Debug)]
+pub struct Circuit<'a> {
+    input: &'a str,
+    gates: HashMap<&'a str, Gate<'a>>,
+}
+
+#[derive(Debug)]
+enum Gate<'a> {
+    Wire(&'a str),
+    Not(&'a str),
+    And(&'a str, &'a str),
+    Or(&'a str, &'a str),
+    LeftShift(&'a str, u16),
+    RightShift(&'a str, u16),
+}
+
+fn signal<'a>(
+    input: &str,
+    key: &'a str,
+    circuit: &HashMap<&'a str, Gate<'a>>,
+    cache: &mut HashMap<&'a str, u16>,
+) -> Result<u16, SolveError> {
+    if let Some(result) = cache.get(key) {
+        return Ok(*result);
+    }
+
+    let result = if key.starts_with(|c: char| c.is_ascii_digit()) {
+        key.parse()
+            .or_malformed(input, key, "expected a 16-bit signal")?
+    } else {
+        match circuit
+            .get(key)
+            .or_malformed(input, key, "wire has no input")?
+        {
+            Gate::Wire(w) => signal(input, w, circuit, cache)?,
+            Gate::Not(w) => !signal(input, w, circuit, cache)?,
+            Gate::And(l, r) => {
+                signal(input, l, circuit, cache)? & signal(input, r, circuit, cache)?
+            }
+            Gate::Or(l, r) => signal(input, l, circuit, cache)? | signal(input, r, circuit, cache)?,
+            Gate::LeftShift(w, n) => signal(input, w, circuit, cache)? << n,
+            Gate::RightShift(w, n) => signal(input, w, circuit, cache)? >> n,
+        }
+    };
+
+    cache.insert(key, result);
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -94,12 +149,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 46065);
+        let input = crate::input::for_test(2015, 7);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 46065);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 14134);
+        let input = crate::input::for_test(2015, 7);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 14134);
     }
 }
@@ -1,11 +1,36 @@
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
+
 pub const TITLE: &str = "Matchsticks";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
-/// * Panics if an invalid pattern is given.
-#[must_use]
-pub fn part1(input: &str) -> usize {
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 8;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Literal<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
+/// Returns an error if a line contains an invalid escape sequence.
+pub fn parse(input: &str) -> Result<Vec<Literal<'_>>, SolveError> {
     input
         .lines()
         .filter(|line| line.len() >= 2)
@@ -14,7 +39,9 @@ pub fn part1(input: &str) -> usize {
 
             let mut state = State::Regular;
             let mut result = Vec::with_capacity(line.len() - 2);
-            for byte in trimmed.bytes() {
+            for (index, byte) in trimmed.bytes().enumerate() {
+                let invalid =
+                    |message: &str| Err(SolveError::malformed(input, &trimmed[index..], message));
                 let new_state: State = match state {
                     State::Regular => {
                         if byte == b'\\' {
@@ -31,7 +58,7 @@ pub fn part1(input: &str) -> usize {
                             result.push(byte);
                             State::Regular
                         } else {
-                            panic!("invalid character escape sequence");
+                            return invalid("invalid character escape sequence");
                         }
                     }
                     State::Hex01 => {
@@ -40,7 +67,7 @@ pub fn part1(input: &str) -> usize {
                         } else if byte.is_ascii_lowercase() {
                             State::Hex02(byte - (b'a' - 10))
                         } else {
-                            panic!("expected hex escape sequence");
+                            return invalid("expected hex escape sequence");
                         }
                     }
                     State::Hex02(first) => {
@@ -49,7 +76,7 @@ pub fn part1(input: &str) -> usize {
                         } else if byte.is_ascii_lowercase() {
                             byte - (b'a' - 10)
                         } else {
-                            panic!("expected hex escape sequence");
+                            return invalid("expected hex escape sequence");
                         };
                         result.push((first << 4) | second);
                         State::Regular
@@ -58,24 +85,45 @@ pub fn part1(input: &str) -> usize {
                 state = new_state;
             }
 
-            (line.to_string(), result)
+            Ok(Literal {
+                code: line,
+                len: result.len(),
+            })
         })
-        .map(|(source, parsed)| source.len() - parsed.len())
+        .collect()
+}
+
+#[must_use]
+pub fn part1(literals: &[Literal]) -> usize {
+    literals
+        .iter()
+        .map(|literal| literal.code.len() - literal.len)
         .sum()
 }
 
 #[must_use]
-pub fn part2(input: &str) -> usize {
-    input
-        .lines()
-        .filter(|line| line.len() >= 2)
-        .map(|line| {
-            let count = line.bytes().filter(|c| *c == b'"' || *c == b'\\').count();
+pub fn part2(literals: &[Literal]) -> usize {
+    literals
+        .iter()
+        .map(|literal| {
+            let count = literal
+                .code
+                .bytes()
+                .filter(|c| *c == b'"' || *c == b'\\')
+                .count();
             count + 2
         })
         .sum()
 }
 
+/// A string literal as written in the code, and how many characters it
+/// holds in memory.
+#[derive(Debug)]
+pub struct Literal<'a> {
+    code: &'a str,
+    len: usize,
+}
+
 enum State {
     Regular,
     EscapeDetected,
@@ -88,12 +136,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 1350);
+        let input = crate::input::for_test(2015, 8);
+        assert_eq!(part1(&parse(&input).unwrap()), 1350);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 2085);
+        let input = crate::input::for_test(2015, 8);
+        assert_eq!(part2(&parse(&input).unwrap()), 2085);
     }
 }
@@ -1,31 +1,66 @@
 use std::collections::HashMap;
 
-use permutohedron::Heap;
+use crate::{
+    answer::Answer,
+    error::SolveError,
+    input::embedded,
+    shared::{
+        parse::OrMalformed,
+        search::{self, Keep},
+    },
+    solution::Solution,
+};
 
 pub const TITLE: &str = "All in a Single Night";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
-/// Panics if `input` is empty
-#[must_use]
-pub fn part1(input: &str) -> usize {
-    let data: (Place, Routes) = transform(input);
-    *route_lengths(data).iter().min().unwrap()
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 9;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = (Place, Routes);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
+
+/// # Errors
+/// Returns an error if `input` is empty or malformed
+pub fn parse(input: &str) -> Result<(Place, Routes), SolveError> {
+    transform(input)
 }
 
-/// # Panics
-/// Panics if `input` is empty
-#[must_use]
-pub fn part2(input: &str) -> usize {
-    let data: (Place, Routes) = transform(input);
-    *route_lengths(data).iter().max().unwrap()
+/// # Errors
+/// Returns an error if no route visits every location
+pub fn part1(data: &(Place, Routes)) -> Result<usize, SolveError> {
+    route_length(data, Keep::Min).ok_or(SolveError::no_solution("no route visits every location"))
 }
 
-type Place = usize;
+/// # Errors
+/// Returns an error if no route visits every location
+pub fn part2(data: &(Place, Routes)) -> Result<usize, SolveError> {
+    route_length(data, Keep::Max).ok_or(SolveError::no_solution("no route visits every location"))
+}
+
+pub type Place = usize;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
-struct Connection {
+pub struct Connection {
     a: Place,
     b: Place,
 }
@@ -40,46 +75,80 @@ impl Connection {
     }
 }
 
-type Routes = HashMap<Connection, usize>;
+pub type Routes = HashMap<Connection, usize>;
 
-fn transform(input: &str) -> (Place, Routes) {
+fn transform(input: &str) -> Result<(Place, Routes), SolveError> {
     let mut place_names = HashMap::new();
-    let results = input
+    let results: Routes = input
         .lines()
-        .filter_map(|line| {
-            let to_idx = line.find(" to ")?;
-            let eq_idx = line.find(" = ")?;
+        .map(|line| {
+            let to_idx = line.find(" to ").or_malformed(
+                input,
+                line,
+                "expected `<from> to <to> = <distance>`",
+            )?;
+            let eq_idx = line.find(" = ").or_malformed(
+                input,
+                line,
+                "expected `<from> to <to> = <distance>`",
+            )?;
             let fr = &line[0..to_idx];
-            let to = &line[to_idx + 4..eq_idx];
+            let to = line.get(to_idx + 4..eq_idx).or_malformed(
+                input,
+                line,
+                "expected `<from> to <to> = <distance>`",
+            )?;
 
             let l = place_names.len() as Place;
             let fr: Place = *place_names.entry(fr).or_insert(l);
             let l = place_names.len() as Place;
             let to: Place = *place_names.entry(to).or_insert(l);
 
-            let dist: usize = line[eq_idx + 3..].parse().ok()?;
-            Some((Connection::new(fr, to), dist))
+            let distance = &line[eq_idx + 3..];
+            let dist: usize =
+                distance
+                    .parse()
+                    .or_malformed(input, distance, "expected a distance")?;
+            Ok((Connection::new(fr, to), dist))
         })
-        .collect();
-    (place_names.len() as Place, results)
+        .collect::<Result<_, SolveError>>()?;
+
+    if results.is_empty() {
+        return Err(SolveError::malformed(
+            input,
+            input,
+            "expected at least one route",
+        ));
+    }
+
+    Ok((place_names.len() as Place, results))
 }
 
-fn route_lengths((place_count, routes): (Place, Routes)) -> Vec<usize> {
-    let mut data = (0..place_count).collect::<Vec<_>>();
-    let heap = Heap::new(&mut data);
-    heap.map(|permutation| {
-        permutation
-            .iter()
-            .take(permutation.len() - 1)
-            .zip(permutation.iter().skip(1))
-            .map(|(&from, &to)| {
-                routes
-                    .get(&Connection::new(from, to))
-                    .expect("missing route")
-            })
-            .sum()
-    })
-    .collect()
+/// The length of the shortest or longest route visiting every location once.
+///
+/// Every route is tried, apart from those that would need a connection the
+/// input doesn't list and, when looking for the shortest, those already
+/// longer than the shortest found so far.
+fn route_length((place_count, routes): &(Place, Routes), keep: Keep) -> Option<usize> {
+    search::dfs_paths(
+        (0..*place_count).map(|place| (place, 0)),
+        keep,
+        |route: &[Place]| {
+            let last = *route.last().unwrap();
+            (0..*place_count)
+                .filter(|place| !route.contains(place))
+                .filter_map(|place| Some((place, *routes.get(&Connection::new(last, place))?)))
+                .collect::<Vec<_>>()
+        },
+        |length, distance| length + distance,
+        |route| route.len() == *place_count,
+        // Distances are never negative, so a route only gets longer.
+        |_, length, shortest| {
+            keep == Keep::Min && shortest.is_some_and(|shortest| length > shortest)
+        },
+    )
+    .pop()
+    .map(|route| route.cost)
 }
 
 #[cfg(test)]
@@ -87,12 +156,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 117);
+        let input = crate::input::for_test(2015, 9);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 117);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 909);
+        let input = crate::input::for_test(2015, 9);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 909);
     }
 }
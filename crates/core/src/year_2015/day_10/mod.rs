@@ -1,52 +1,93 @@
-use anyhow::anyhow;
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
 
 pub const TITLE: &str = "Elves Look, Elves Say";
 
-pub const INPUT: &str = "3113322113";
+pub const INPUT: Option<&str> = embedded!("3113322113");
 
-/// # Panics
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 10;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
 ///
-/// Panics if `input` is empty.
+/// Returns an error if `input` is empty or contains anything other than digits.
+pub fn parse(input: &str) -> Result<&str, SolveError> {
+    let sequence = input.trim();
+    if sequence.is_empty() {
+        return Err(SolveError::malformed(
+            input,
+            sequence,
+            "look and say requires at least 1 digit",
+        ));
+    }
+    if let Some(index) = sequence.find(|c: char| !c.is_ascii_digit()) {
+        return Err(SolveError::malformed(
+            input,
+            &sequence[index..],
+            "expected a digit",
+        ));
+    }
+
+    Ok(sequence)
+}
+
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    apply_n(input, 40).unwrap()
+pub fn part1(sequence: &str) -> usize {
+    apply_n(sequence, 40)
 }
 
-/// # Panics
-///
-/// Panics if `input` is empty.
 #[must_use]
-pub fn part2(input: &str) -> usize {
-    apply_n(input, 50).unwrap()
+pub fn part2(sequence: &str) -> usize {
+    apply_n(sequence, 50)
 }
 
-fn apply_n(input: &str, times: usize) -> anyhow::Result<usize> {
-    let mut s = input.to_owned();
+fn apply_n(sequence: &str, times: usize) -> usize {
+    let mut s = sequence.to_owned();
     for _ in 0..times {
-        s = look_and_say(&s)?;
+        s = look_and_say(&s);
     }
-    anyhow::Ok(s.len())
+    s.len()
 }
 
-fn look_and_say(input: &str) -> anyhow::Result<String> {
+fn look_and_say(input: &str) -> String {
     use std::fmt::Write;
     let mut result = String::new();
     let mut chars = input.chars();
-    let mut curr = chars
-        .next()
-        .ok_or_else(|| anyhow!("look_and_say requires at least 1 character"))?;
+    let Some(mut curr) = chars.next() else {
+        return result;
+    };
     let mut count = 1;
     for next in chars {
         if next == curr {
             count += 1;
         } else {
-            write!(result, "{count}{curr}")?;
+            write!(result, "{count}{curr}").unwrap();
             curr = next;
             count = 1;
         }
     }
-    write!(result, "{count}{curr}")?;
-    Ok(result)
+    write!(result, "{count}{curr}").unwrap();
+    result
 }
 
 #[cfg(test)]
@@ -54,12 +95,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 329_356);
+        let input = crate::input::for_test(2015, 10);
+        assert_eq!(part1(parse(&input).unwrap()), 329_356);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 4_666_278);
+        let input = crate::input::for_test(2015, 10);
+        assert_eq!(part2(parse(&input).unwrap()), 4_666_278);
     }
 }
@@ -1,25 +1,65 @@
-use anyhow::anyhow;
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
 
 pub const TITLE: &str = "Corporate Policy";
 
-pub const INPUT: &str = "hepxcrrq";
+pub const INPUT: Option<&str> = embedded!("hepxcrrq");
 
-/// # Panics
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 11;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
 ///
-/// Panics if `input` is invalid.
+/// Returns an error if `input` is not a lowercase password.
+pub fn parse(input: &str) -> Result<Vec<u8>, SolveError> {
+    let password = input.trim();
+    if password.is_empty() {
+        return Err(SolveError::malformed(
+            input,
+            password,
+            "expected a password",
+        ));
+    }
+    if let Some(index) = password.find(|c: char| !c.is_ascii_lowercase()) {
+        return Err(SolveError::malformed(
+            input,
+            &password[index..],
+            "expected a lowercase letter",
+        ));
+    }
+    Ok(password.as_bytes().to_vec())
+}
+
 #[must_use]
-pub fn part1(input: &str) -> String {
-    let mut pw = validate_input(input).unwrap();
+pub fn part1(password: &[u8]) -> String {
+    let mut pw = password.to_vec();
     cycle_password_until_valid(&mut pw);
     unsafe { String::from_utf8_unchecked(pw) }
 }
 
-/// # Panics
-///
-/// Panics if `input` is invalid.
 #[must_use]
-pub fn part2(input: &str) -> String {
-    let mut pw = validate_input(input).unwrap();
+pub fn part2(password: &[u8]) -> String {
+    let mut pw = password.to_vec();
     cycle_password_until_valid(&mut pw);
     cycle_password_until_valid(&mut pw);
     unsafe { String::from_utf8_unchecked(pw) }
@@ -77,27 +117,21 @@ fn cycle_password_until_valid(pw: &mut [u8]) {
     }
 }
 
-fn validate_input(input: &str) -> anyhow::Result<Vec<u8>> {
-    let pw = input.as_bytes().to_vec();
-    for c in &pw {
-        if !(&b'a'..=&b'z').contains(&c) {
-            return Err(anyhow!("invalid input in puzzle"));
-        }
-    }
-    Ok(pw)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), "hepxxyzz");
+        let input = crate::input::for_test(2015, 11);
+        assert_eq!(part1(&parse(&input).unwrap()), "hepxxyzz");
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), "heqaabcc");
+        let input = crate::input::for_test(2015, 11);
+        assert_eq!(part2(&parse(&input).unwrap()), "heqaabcc");
     }
 }
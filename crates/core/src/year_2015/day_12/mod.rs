@@ -1,95 +1,88 @@
-use itertools::Itertools;
 use serde_json::Value;
 
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
+
 pub const TITLE: &str = "JSAbacusFramework.io";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-#[must_use]
-pub fn part1(input: &str) -> isize {
-    extract_numbers(input).sum()
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 12;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Value;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to convert input to JSON
+/// Returns an error if unable to convert input to JSON
+pub fn parse(input: &str) -> Result<Value, SolveError> {
+    serde_json::from_str(input).map_err(|error| SolveError::MalformedInput {
+        line: error.line(),
+        column: error.column(),
+        message: error.to_string(),
+    })
+}
+
 #[must_use]
-pub fn part2(input: &str) -> isize {
-    let mut json = serde_json::from_str(input).unwrap();
+pub fn part1(json: &Value) -> i64 {
+    sum(json, false)
+}
 
-    prune_red(&mut json);
-    part1(&serde_json::to_string(&json).unwrap())
+#[must_use]
+pub fn part2(json: &Value) -> i64 {
+    sum(json, true)
 }
 
-fn prune_red(json: &mut Value) {
+/// Adds up every number in `json`, leaving out objects with a `"red"` value
+/// if `skip_red` is set.
+fn sum(json: &Value, skip_red: bool) -> i64 {
     match json {
+        Value::Number(number) => number.as_i64().unwrap_or_default(),
+        Value::Array(array) => array.iter().map(|value| sum(value, skip_red)).sum(),
         Value::Object(map) => {
-            for (_, value) in map.iter_mut() {
-                if let Some(s) = value.as_str() {
-                    if s == "red" {
-                        map.clear();
-                        return;
-                    }
-                }
-            }
-            for (_, value) in map.iter_mut() {
-                prune_red(value);
-            }
-        }
-        Value::Array(array) => {
-            for value in array.iter_mut() {
-                prune_red(value);
+            if skip_red && map.values().any(|value| value == "red") {
+                0
+            } else {
+                map.values().map(|value| sum(value, skip_red)).sum()
             }
         }
-        _ => {}
+        _ => 0,
     }
 }
 
-fn extract_numbers(input: &str) -> impl Iterator<Item = isize> + '_ {
-    input.chars().peekable().batching(|it| loop {
-        match it.next() {
-            Some(x) if x == '-' || x.is_ascii_digit() => {
-                let is_negative = if x == '-' {
-                    if let Some(&x) = it.peek() {
-                        if !x.is_ascii_digit() {
-                            continue;
-                        }
-                    }
-                    true
-                } else {
-                    false
-                };
-
-                let mut nr = if is_negative {
-                    0
-                } else {
-                    x as isize - '0' as isize
-                };
-                for x in it.by_ref() {
-                    if !x.is_ascii_digit() {
-                        break;
-                    }
-                    nr = nr * 10 + (x as isize) - ('0' as isize);
-                }
-                return Some(if is_negative { -nr } else { nr });
-            }
-            Some(_) => {}
-            None => break None,
-        }
-    })
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 111_754);
+        let input = crate::input::for_test(2015, 12);
+        assert_eq!(part1(&parse(&input).unwrap()), 111_754);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 65402);
+        let input = crate::input::for_test(2015, 12);
+        assert_eq!(part2(&parse(&input).unwrap()), 65402);
     }
 }
@@ -1,73 +1,112 @@
 use std::collections::{HashMap, HashSet};
 
-use anyhow::anyhow;
-
 use permutohedron::Heap;
 use regex::RegexBuilder;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Knights of the Dinner Table";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 13;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = (People<'a>, Happiness<'a>);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
-/// # Panics
-///
-/// * Panics if input is empty
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    let input = &input;
-    compute_total_happiness(parse_happiness(input).unwrap())
-        .try_into()
-        .unwrap()
+pub fn part1((people, happiness): &(People, Happiness)) -> isize {
+    compute_total_happiness(people, happiness)
 }
 
-/// # Panics
-///
-/// * Panics if input is empty
 #[must_use]
-pub fn part2(input: &str) -> isize {
-    let (mut people, mut happiness) = parse_happiness(input).unwrap();
+pub fn part2((people, happiness): &(People, Happiness)) -> isize {
+    let mut people = people.clone();
+    let mut happiness = happiness.clone();
     for person in &people {
         happiness.insert(("", person), 0);
         happiness.insert((person, ""), 0);
     }
     people.insert("");
-    compute_total_happiness((people, happiness))
+    compute_total_happiness(&people, &happiness)
 }
 
-type People<'a> = HashSet<&'a str>;
-type Happiness<'a> = HashMap<(&'a str, &'a str), isize>;
+pub type People<'a> = HashSet<&'a str>;
+pub type Happiness<'a> = HashMap<(&'a str, &'a str), isize>;
 
-fn parse_happiness<'a>(input: &'a str) -> anyhow::Result<(People<'a>, Happiness<'a>)> {
+/// # Errors
+///
+/// * Returns an error if input is empty or malformed
+///
+/// # Panics
+/// * Panics if an invalid pattern is given.
+pub fn parse(input: &str) -> Result<(People<'_>, Happiness<'_>), SolveError> {
     let re = RegexBuilder::new(r"^(?P<f>[[:alpha:]]+) would (?P<n>gain|lose) (?P<a>\d+) happiness units by sitting next to (?P<t>[[:alpha:]]+)\.").multi_line(true).build().unwrap();
 
-    let happiness: Happiness<'a> = re
-        .captures_iter(input)
-        .map(|m| {
+    let happiness: Happiness<'_> = input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let m = re.captures(line).or_malformed(
+                input,
+                line,
+                "expected `<name> would gain|lose <amount> happiness units by sitting next to <name>.`",
+            )?;
             let from = m.name("f").unwrap().as_str();
             let is_negative = &m["n"] == "lose";
-            let amount: isize = m["a"].parse().unwrap();
+            let amount = m.name("a").unwrap().as_str();
+            let amount: isize = amount
+                .parse()
+                .or_malformed(input, amount, "expected an amount")?;
             let to = m.name("t").unwrap().as_str();
-            ((from, to), if is_negative { -amount } else { amount })
+            Ok(((from, to), if is_negative { -amount } else { amount }))
         })
-        .collect();
+        .collect::<Result<_, SolveError>>()?;
 
     if happiness.is_empty() {
-        return Err(anyhow!("expected any input"));
+        return Err(SolveError::malformed(input, input, "expected any input"));
     }
 
-    let mut people = HashSet::new();
-    for (f, t) in happiness.keys() {
-        people.insert(*f);
-        if !happiness.contains_key(&(t, f)) {
-            return Err(anyhow!("happiness should be specified both ways"));
+    let people = happiness.keys().map(|&(f, _)| f).collect::<People<'_>>();
+    for f in &people {
+        for t in &people {
+            if f != t && !happiness.contains_key(&(f, t)) {
+                return Err(SolveError::malformed(
+                    input,
+                    f,
+                    format!("happiness towards {t} should be specified"),
+                ));
+            }
         }
     }
 
     Ok((people, happiness))
 }
 
-fn compute_total_happiness<'a>((people, happiness): (People<'a>, Happiness<'a>)) -> isize {
-    let mut people = people.into_iter().collect::<Vec<_>>();
+fn compute_total_happiness(people: &People, happiness: &Happiness) -> isize {
+    let mut people = people.iter().copied().collect::<Vec<_>>();
     let heap = Heap::new(&mut people);
     heap.map(|permutation| {
         permutation
@@ -96,12 +135,16 @@ mod test {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 709);
+        let input = crate::input::for_test(2015, 13);
+        assert_eq!(part1(&parse(&input).unwrap()), 709);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 668);
+        let input = crate::input::for_test(2015, 13);
+        assert_eq!(part2(&parse(&input).unwrap()), 668);
     }
 }
@@ -2,33 +2,58 @@ use std::collections::HashMap;
 
 use regex::RegexBuilder;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Reindeer Olympics";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 14;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Reindeers<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
+
+/// # Errors
 ///
-/// Panics if input is empty.
-#[must_use]
-pub fn part1(input: &str) -> usize {
-    parse_reindeers(input)
-        .unwrap()
-        .into_values()
-        .map(|mobility| compute_at_time(&mobility, 2503))
+/// Returns an error if there are no reindeer.
+pub fn part1(reindeers: &Reindeers) -> Result<usize, SolveError> {
+    reindeers
+        .values()
+        .map(|mobility| compute_at_time(mobility, 2503))
         .max()
-        .unwrap()
+        .ok_or(SolveError::no_solution("there are no reindeer"))
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if input is empty.
-#[must_use]
-pub fn part2_with_time(input: &str, time: usize) -> usize {
-    let mut states = parse_reindeers(input)
-        .unwrap()
-        .into_values()
+/// Returns an error if there are no reindeer.
+pub fn part2_with_time(reindeers: &Reindeers, time: usize) -> Result<usize, SolveError> {
+    let mut states = reindeers
+        .values()
         .map(|mobility| State {
-            mobility,
+            mobility: mobility.clone(),
             points: 0,
             distance: 0,
         })
@@ -41,7 +66,11 @@ pub fn part2_with_time(input: &str, time: usize) -> usize {
         }
 
         // Award points
-        let highest = states.iter().map(|state| state.distance).max().unwrap();
+        let highest = states
+            .iter()
+            .map(|state| state.distance)
+            .max()
+            .unwrap_or_default();
         for state in &mut states {
             if state.distance == highest {
                 state.points += 1;
@@ -49,18 +78,24 @@ pub fn part2_with_time(input: &str, time: usize) -> usize {
         }
     }
 
-    states.into_iter().map(|x| x.points).max().unwrap()
+    states
+        .into_iter()
+        .map(|x| x.points)
+        .max()
+        .ok_or(SolveError::no_solution("there are no reindeer"))
 }
 
-#[must_use]
-pub fn part2(input: &str) -> usize {
-    part2_with_time(input, 2503)
+/// # Errors
+///
+/// Returns an error if there are no reindeer.
+pub fn part2(reindeers: &Reindeers) -> Result<usize, SolveError> {
+    part2_with_time(reindeers, 2503)
 }
 
-type Reindeers<'a> = HashMap<&'a str, Mobility>;
+pub type Reindeers<'a> = HashMap<&'a str, Mobility>;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Mobility {
+pub struct Mobility {
     speed: usize,
     duration: usize,
     rest_time: usize,
@@ -73,15 +108,39 @@ struct State {
     distance: usize,
 }
 
-fn parse_reindeers(input: &str) -> anyhow::Result<Reindeers<'_>> {
+/// # Errors
+///
+/// Returns an error if input is empty or malformed.
+///
+/// # Panics
+/// * Panics if an invalid pattern is given.
+pub fn parse(input: &str) -> Result<Reindeers<'_>, SolveError> {
     let re = RegexBuilder::new(r"^(?P<n>[[:alpha:]]+) can fly (?P<s>\d+) km/s for (?P<d>\d+) seconds, but then must rest for (?P<r>\d+) seconds\.").multi_line(true).build().unwrap();
 
-    re.captures_iter(input)
-        .map(|m| {
+    let reindeers = input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let m = re.captures(line).or_malformed(
+                input,
+                line,
+                "expected `<name> can fly <speed> km/s for <duration> seconds, but then must rest for <rest> seconds.`",
+            )?;
+            let number = |group: &str| {
+                let value = m.name(group).unwrap().as_str();
+                value.parse().or_malformed(input, value, "expected a number")
+            };
             let name = m.name("n").unwrap().as_str();
-            let speed = m["s"].parse()?;
-            let duration = m["d"].parse()?;
-            let rest_time = m["r"].parse()?;
+            let speed = number("s")?;
+            let duration = number("d")?;
+            let rest_time = number("r")?;
+            if duration + rest_time == 0 {
+                return Err(SolveError::malformed(
+                    input,
+                    line,
+                    "reindeer must fly or rest for at least one second",
+                ));
+            }
             Ok((
                 name,
                 Mobility {
@@ -91,7 +150,17 @@ fn parse_reindeers(input: &str) -> anyhow::Result<Reindeers<'_>> {
                 },
             ))
         })
-        .collect()
+        .collect::<Result<Reindeers<'_>, SolveError>>()?;
+
+    if reindeers.is_empty() {
+        return Err(SolveError::malformed(
+            input,
+            input,
+            "expected at least one reindeer",
+        ));
+    }
+
+    Ok(reindeers)
 }
 
 const fn compute_at_time(mobility: &Mobility, time: usize) -> usize {
@@ -111,12 +180,19 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 2640);
+        let input = crate::input::for_test(2015, 14);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 2640);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2_with_time(INPUT, 2503), 1102);
+        let input = crate::input::for_test(2015, 14);
+        assert_eq!(
+            part2_with_time(&parse(&input).unwrap(), 2503).unwrap(),
+            1102
+        );
     }
 }
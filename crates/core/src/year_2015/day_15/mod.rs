@@ -1,18 +1,42 @@
-use anyhow::anyhow;
 use regex::RegexBuilder;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Science for Hungry People";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 15;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Ingredient>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
-/// # Panics
-///
-/// Panics if input is empty.
 #[must_use]
-pub fn part1(input: &str) -> isize {
-    let ingredients = parse_ingredients(input);
+pub fn part1(ingredients: &[Ingredient]) -> isize {
     let mut max = 0;
-    for_each_combination(&ingredients, |a, b, c, d| {
+    for_each_combination(ingredients, |a, b, c, d| {
         let capacity = isize::max(
             0,
             a * ingredients[0].capacity
@@ -46,19 +70,14 @@ pub fn part1(input: &str) -> isize {
         if value > max {
             max = value;
         }
-    })
-    .unwrap();
+    });
     max
 }
 
-/// # Panics
-///
-/// Panics if input is empty.
 #[must_use]
-pub fn part2(input: &str) -> isize {
-    let ingredients = parse_ingredients(input);
+pub fn part2(ingredients: &[Ingredient]) -> isize {
     let mut max = 0;
-    for_each_combination(&ingredients, |a, b, c, d| {
+    for_each_combination(ingredients, |a, b, c, d| {
         let capacity = isize::max(
             0,
             a * ingredients[0].capacity
@@ -98,13 +117,12 @@ pub fn part2(input: &str) -> isize {
         if calories == 500 && value > max {
             max = value;
         }
-    })
-    .unwrap();
+    });
     max
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Ingredient {
+pub struct Ingredient {
     capacity: isize,
     durability: isize,
     flavor: isize,
@@ -112,26 +130,53 @@ struct Ingredient {
     calories: isize,
 }
 
-fn parse_ingredients(input: &str) -> Vec<Ingredient> {
+/// # Errors
+///
+/// Returns an error if input doesn't list exactly four ingredients.
+///
+/// # Panics
+/// * Panics if an invalid pattern is given.
+pub fn parse(input: &str) -> Result<Vec<Ingredient>, SolveError> {
     let re = RegexBuilder::new(r"^(?P<n>[[:alpha:]]+): capacity (?P<capacity>-?\d+), durability (?P<durability>-?\d+), flavor (?P<flavor>-?\d+), texture (?P<texture>-?\d+), calories (?P<calories>-?\d+)").multi_line(true).build().unwrap();
-    re.captures_iter(input)
-        .map(|m| Ingredient {
-            capacity: m["capacity"].parse().unwrap(),
-            durability: m["durability"].parse().unwrap(),
-            flavor: m["flavor"].parse().unwrap(),
-            texture: m["texture"].parse().unwrap(),
-            calories: m["calories"].parse().unwrap(),
+    let ingredients = input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let m = re.captures(line).or_malformed(
+                input,
+                line,
+                "expected `<name>: capacity <n>, durability <n>, flavor <n>, texture <n>, calories <n>`",
+            )?;
+            let number = |group: &str| {
+                let value = m.name(group).unwrap().as_str();
+                value.parse().or_malformed(input, value, "expected a number")
+            };
+            Ok(Ingredient {
+                capacity: number("capacity")?,
+                durability: number("durability")?,
+                flavor: number("flavor")?,
+                texture: number("texture")?,
+                calories: number("calories")?,
+            })
         })
-        .collect()
+        .collect::<Result<Vec<_>, SolveError>>()?;
+
+    if ingredients.len() != 4 {
+        return Err(SolveError::malformed(
+            input,
+            input,
+            "can only handle 4 ingredients",
+        ));
+    }
+
+    Ok(ingredients)
 }
 
-fn for_each_combination<F>(ingredients: &[Ingredient], mut f: F) -> anyhow::Result<()>
+fn for_each_combination<F>(ingredients: &[Ingredient], mut f: F)
 where
     F: FnMut(isize, isize, isize, isize),
 {
-    if ingredients.len() != 4 {
-        return Err(anyhow!("can only handle 4 ingredients"));
-    }
+    debug_assert_eq!(ingredients.len(), 4);
 
     for a in 0..101 {
         let remainder = 100 - a;
@@ -144,7 +189,6 @@ where
             }
         }
     }
-    Ok(())
 }
 
 #[cfg(test)]
@@ -152,12 +196,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 222_870);
+        let input = crate::input::for_test(2015, 15);
+        assert_eq!(part1(&parse(&input).unwrap()), 222_870);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 117_936);
+        let input = crate::input::for_test(2015, 15);
+        assert_eq!(part2(&parse(&input).unwrap()), 117_936);
     }
 }
@@ -1,33 +1,72 @@
 use std::collections::HashMap;
 
-use anyhow::anyhow;
 use regex::Regex;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Aunt Sue";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 16;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Sue>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
 
 /// # Errors
 ///
-/// This function will return an error if `input` is empty
-pub fn part1(input: &str) -> anyhow::Result<usize> {
-    let mut sues: Vec<Sue> = input
-        .lines()
-        .map(parse_line)
-        .collect::<anyhow::Result<_>>()?;
-    sues.retain(|sue| {
-        for &(key, value) in &KNOWN_PROPS {
-            if let Some(&sue_value) = sue.properties.get(key) {
-                if sue_value != value {
-                    return false;
+/// This function will return an error if `input` is malformed
+///
+/// # Panics
+/// * Panics if an invalid pattern is given.
+pub fn parse(input: &str) -> Result<Vec<Sue>, SolveError> {
+    input.lines().map(|line| parse_line(input, line)).collect()
+}
+
+/// # Errors
+///
+/// This function will return an error if no single Sue matches
+pub fn part1(sues: &[Sue]) -> Result<usize, SolveError> {
+    let sues = sues
+        .iter()
+        .filter(|sue| {
+            for &(key, value) in &KNOWN_PROPS {
+                if let Some(&sue_value) = sue.properties.get(key) {
+                    if sue_value != value {
+                        return false;
+                    }
                 }
             }
-        }
-        true
-    });
+            true
+        })
+        .collect::<Vec<_>>();
 
     if sues.len() != 1 {
-        return Err(anyhow!("no sues matching the properties found"));
+        return Err(SolveError::no_solution(
+            "no sues matching the properties found",
+        ));
     }
 
     Ok(sues[0].index)
@@ -35,60 +74,67 @@ pub fn part1(input: &str) -> anyhow::Result<usize> {
 
 /// # Errors
 ///
-/// This function will return an error if `input` is empty
-pub fn part2(input: &str) -> anyhow::Result<usize> {
-    let mut sues: Vec<Sue> = input
-        .lines()
-        .map(parse_line)
-        .collect::<anyhow::Result<_>>()?;
-    sues.retain(|sue| {
-        for &(key, value) in &KNOWN_PROPS {
-            if let Some(&sue_value) = sue.properties.get(key) {
-                if !match key {
-                    "cats" | "trees" => sue_value > value,
-                    "pomeranians" | "goldfish" => sue_value < value,
-                    _ => sue_value == value,
-                } {
-                    return false;
+/// This function will return an error if no single Sue matches
+pub fn part2(sues: &[Sue]) -> Result<usize, SolveError> {
+    let sues = sues
+        .iter()
+        .filter(|sue| {
+            for &(key, value) in &KNOWN_PROPS {
+                if let Some(&sue_value) = sue.properties.get(key) {
+                    if !match key {
+                        "cats" | "trees" => sue_value > value,
+                        "pomeranians" | "goldfish" => sue_value < value,
+                        _ => sue_value == value,
+                    } {
+                        return false;
+                    }
                 }
             }
-        }
-        true
-    });
+            true
+        })
+        .collect::<Vec<_>>();
 
     if sues.len() != 1 {
-        return Err(anyhow!("no sues matching the properties found"));
+        return Err(SolveError::no_solution(
+            "no sues matching the properties found",
+        ));
     }
 
     Ok(sues[0].index)
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Sue {
+pub struct Sue {
     index: usize,
     properties: HashMap<String, usize>,
 }
 
-fn parse_line(line: &str) -> anyhow::Result<Sue> {
+fn parse_line(input: &str, line: &str) -> Result<Sue, SolveError> {
     let main = Regex::new(r"^Sue (?P<index>\d+): (?P<props>.+)$").unwrap();
     let prop = Regex::new(r"(?P<key>[[:alpha:]]+): (?P<value>\d+)(, )?").unwrap();
 
     let captures = main
         .captures(line)
-        .ok_or_else(|| anyhow!("invalid format"))?;
-    let index = captures["index"].parse().unwrap();
+        .or_malformed(input, line, "invalid format")?;
+    let index = captures.name("index").unwrap().as_str();
+    let index = index
+        .parse()
+        .or_malformed(input, index, "expected a number")?;
     let props = captures.name("props").unwrap().as_str();
     let properties = prop
         .captures_iter(props)
         .map(|capture| {
+            let value = capture.name("value").unwrap().as_str();
             Ok((
                 capture.name("key").unwrap().as_str().to_owned(),
-                capture["value"].parse()?,
+                value
+                    .parse()
+                    .or_malformed(input, value, "expected a number")?,
             ))
         })
-        .collect::<anyhow::Result<HashMap<String, usize>>>()?;
+        .collect::<Result<HashMap<String, usize>, SolveError>>()?;
 
-    anyhow::Ok(Sue { index, properties })
+    Ok(Sue { index, properties })
 }
 
 const KNOWN_PROPS: [(&str, usize); 10] = [
@@ -109,12 +155,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT).unwrap(), 373);
+        let input = crate::input::for_test(2015, 16);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 373);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT).unwrap(), 260);
+        let input = crate::input::for_test(2015, 16);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 260);
     }
 }
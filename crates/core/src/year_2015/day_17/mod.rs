@@ -1,33 +1,78 @@
 use std::{cmp::Ordering, collections::HashMap};
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "No Such Thing as Too Much";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 17;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if `input` is empty
+/// Returns an error if a line isn't a container size
+pub fn parse(input: &str) -> Result<Vec<usize>, SolveError> {
+    let mut sizes = input
+        .lines()
+        .map(|x| {
+            x.parse()
+                .or_malformed(input, x, "expected a container size")
+        })
+        .collect::<Result<Vec<usize>, _>>()?;
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(sizes)
+}
+
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    combinations(input, 150).unwrap()
+pub fn part1(sizes: &[usize]) -> usize {
+    combinations(sizes, 150)
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if `input` is empty
-#[must_use]
-pub fn part2(input: &str) -> usize {
-    minimum_combinations(input, 150).unwrap()
+/// Returns an error if no combination holds the eggnog
+pub fn part2(sizes: &[usize]) -> Result<usize, SolveError> {
+    minimum_combinations(sizes, 150)
 }
 
-fn combinations(input: &str, total: usize) -> anyhow::Result<usize> {
-    fn visit(
-        total: usize,
-        idx: usize,
-        sizes: &Vec<usize>,
-        combinations: &mut usize,
-        previous: usize,
-    ) {
+/// Given `sizes` in descending order, returns how many of the largest
+/// containers a combination can start with and still reach `total`, or `None`
+/// if all containers together are too small.
+fn upper_bound(sizes: &[usize], total: usize) -> Option<usize> {
+    let mut sum = 0;
+    (1..=sizes.len()).rev().find(|&i| {
+        sum += sizes[i - 1];
+        sum >= total
+    })
+}
+
+fn combinations(sizes: &[usize], total: usize) -> usize {
+    fn visit(total: usize, idx: usize, sizes: &[usize], combinations: &mut usize, previous: usize) {
         let current = sizes[idx];
         match (previous + current).cmp(&total) {
             Ordering::Less => {
@@ -42,37 +87,24 @@ fn combinations(input: &str, total: usize) -> anyhow::Result<usize> {
         }
     }
 
-    let mut sizes: Vec<usize> = input
-        .lines()
-        .map(|x| Ok(x.parse()?))
-        .collect::<anyhow::Result<_>>()?;
-    sizes.sort_unstable_by(|a, b| b.cmp(a));
     let mut combinations = 0;
 
-    let upper = {
-        let mut sum = 0;
-        let mut i = sizes.len();
-        loop {
-            sum += sizes[i - 1];
-            if sum >= total {
-                break i;
-            }
-            i -= 1;
-        }
+    let Some(upper) = upper_bound(sizes, total) else {
+        return 0;
     };
 
     for i in 0..upper {
-        visit(total, i, &sizes, &mut combinations, 0);
+        visit(total, i, sizes, &mut combinations, 0);
     }
 
-    anyhow::Ok(combinations)
+    combinations
 }
 
-fn minimum_combinations(input: &str, total: usize) -> anyhow::Result<usize> {
+fn minimum_combinations(sizes: &[usize], total: usize) -> Result<usize, SolveError> {
     fn visit(
         total: usize,
         idx: usize,
-        sizes: &Vec<usize>,
+        sizes: &[usize],
         combinations: &mut HashMap<usize, usize>,
         previous: usize,
         count: usize,
@@ -91,30 +123,19 @@ fn minimum_combinations(input: &str, total: usize) -> anyhow::Result<usize> {
         }
     }
 
-    let mut sizes: Vec<usize> = input
-        .lines()
-        .map(|x| Ok(x.parse()?))
-        .collect::<anyhow::Result<_>>()?;
-    sizes.sort_unstable_by(|a, b| b.cmp(a));
     let mut combinations = HashMap::new();
 
-    let upper = {
-        let mut sum = 0;
-        let mut i = sizes.len();
-        loop {
-            sum += sizes[i - 1];
-            if sum >= total {
-                break i;
-            }
-            i -= 1;
-        }
-    };
+    let upper = upper_bound(sizes, total).unwrap_or(0);
 
     for i in 0..upper {
-        visit(total, i, &sizes, &mut combinations, 0, 0);
+        visit(total, i, sizes, &mut combinations, 0, 0);
     }
 
-    Ok(combinations.into_iter().min_by_key(|v| v.0).unwrap().1)
+    combinations
+        .into_iter()
+        .min_by_key(|v| v.0)
+        .map(|v| v.1)
+        .ok_or(SolveError::no_solution("no combination of containers fits"))
 }
 
 #[cfg(test)]
@@ -122,12 +143,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 654);
+        let input = crate::input::for_test(2015, 17);
+        assert_eq!(part1(&parse(&input).unwrap()), 654);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 57);
+        let input = crate::input::for_test(2015, 17);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 57);
     }
 }
@@ -4,34 +4,60 @@ use std::{
     str::FromStr,
 };
 
-use anyhow::{anyhow, Error};
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
 
 pub const TITLE: &str = "Like a GIF For Your Yard";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 18;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = LightGrid;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
 ///
-/// Panics if `input` is invalid
+/// Returns an error if `input` is not a rectangular grid of `#` and `.`
+pub fn parse(input: &str) -> Result<LightGrid, SolveError> {
+    input.parse()
+}
+
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    let mut grid: LightGrid = input.parse().unwrap();
+pub fn part1(grid: &LightGrid) -> usize {
+    let mut grid = grid.clone();
     for _ in 0..100 {
         grid = grid.transform_1();
     }
     grid.count_on()
 }
 
-/// # Panics
-///
-/// Panics if `input` is invalid
 #[must_use]
-pub fn part2(input: &str) -> usize {
-    let mut grid: LightGrid = input.parse().unwrap();
+pub fn part2(grid: &LightGrid) -> usize {
+    let mut grid = grid.clone();
+    let (w, h) = grid.size;
     grid[(0, 0)] = true;
-    grid[(99, 0)] = true;
-    grid[(0, 99)] = true;
-    grid[(99, 99)] = true;
+    grid[(w - 1, 0)] = true;
+    grid[(0, h - 1)] = true;
+    grid[(w - 1, h - 1)] = true;
 
     for _ in 0..100 {
         grid = grid.transform_2();
@@ -41,7 +67,7 @@ pub fn part2(input: &str) -> usize {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct LightGrid {
+pub struct LightGrid {
     size: (usize, usize),
     data: Vec<bool>,
 }
@@ -156,25 +182,31 @@ impl Display for LightGrid {
 }
 
 impl FromStr for LightGrid {
-    type Err = Error;
+    type Err = SolveError;
 
-    fn from_str(s: &str) -> anyhow::Result<Self> {
+    fn from_str(s: &str) -> Result<Self, SolveError> {
         let lines: Vec<_> = s.lines().collect();
-        if lines.is_empty() {
-            return Err(anyhow!("empty string"));
+        if lines.is_empty() || lines[0].is_empty() {
+            return Err(SolveError::malformed(s, s, "empty string"));
         }
         let h = lines.len();
         let w = lines[0].len();
-        if !lines.iter().skip(1).all(|x| x.len() == w) {
-            return Err(anyhow!("inconsistent width"));
+        if let Some(line) = lines.iter().skip(1).find(|x| x.len() != w) {
+            return Err(SolveError::malformed(s, line, "inconsistent width"));
         }
         let mut data = Vec::with_capacity(w * h);
         for line in lines {
-            for c in line.chars() {
+            for (index, c) in line.char_indices() {
                 match c {
                     '#' => data.push(true),
                     '.' => data.push(false),
-                    _ => return Err(anyhow!("invalid character, expected # or .")),
+                    _ => {
+                        return Err(SolveError::malformed(
+                            s,
+                            &line[index..],
+                            "invalid character, expected # or .",
+                        ))
+                    }
                 }
             }
         }
@@ -187,12 +219,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 814);
+        let input = crate::input::for_test(2015, 18);
+        assert_eq!(part1(&parse(&input).unwrap()), 814);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 924);
+        let input = crate::input::for_test(2015, 18);
+        assert_eq!(part2(&parse(&input).unwrap()), 924);
     }
 }
@@ -1,23 +1,110 @@
 use std::collections::{HashMap, HashSet};
 
-use anyhow::anyhow;
+use crate::{
+    answer::Answer,
+    error::SolveError,
+    input::embedded,
+    shared::{ida_star::IdaStar, parse::OrMalformed},
+    solution::Solution,
+};
 
 pub const TITLE: &str = "Medicine for Rudolph";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 19;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Machine<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
+
+/// # Errors
 ///
-/// Panics if input is invalid
-#[must_use]
-pub fn part1(input: &str) -> usize {
-    let (rules, molecule) = parse_input(input).unwrap();
+/// Returns an error if input is invalid
+pub fn parse(input: &str) -> Result<Machine<'_>, SolveError> {
+    let (rules, molecule) = parse_input(input)?;
+
+    let mut atom_map = HashMap::new();
+    atom_map.insert("e", 0);
+    let mut reductions = rules
+        .iter()
+        .map(|&(from, into)| {
+            let from_atoms = string_to_molecule(input, from, &mut atom_map)?;
+            if from_atoms.len() != 1 {
+                return Err(SolveError::malformed(
+                    input,
+                    from,
+                    "mapping can only be from one atom to a molecule",
+                ));
+            }
+            let into = string_to_molecule(input, into, &mut atom_map)?;
+            Ok((into, from_atoms[0]))
+        })
+        .collect::<Result<Vec<_>, SolveError>>()?;
+    reductions.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+    let atoms = string_to_molecule(input, molecule, &mut atom_map)?;
+
+    // Every replacement of the puzzle input turns one atom into either two,
+    // or into `ARnB(YC)*Ar`. Weighing `Rn` and `Ar` as nothing and `Y` as -1
+    // makes undoing any of them take exactly 1 off the molecule's weight.
+    let mut weights = vec![1; atom_map.len()];
+    for (&name, &atom) in &atom_map {
+        weights[usize::from(atom)] = match name {
+            "Rn" | "Ar" => 0,
+            "Y" => -1,
+            _ => 1,
+        };
+    }
+    let weigh = |molecule: &[Atom]| {
+        molecule
+            .iter()
+            .map(|&atom| weights[usize::from(atom)])
+            .sum::<isize>()
+    };
+    let shrinks = reductions
+        .iter()
+        .map(|(into, from)| weigh(into) - weights[usize::from(*from)])
+        .collect::<HashSet<_>>();
+    let max_shrink = shrinks.iter().max().copied().unwrap_or_default().max(1) as usize;
+    let uniform = shrinks == HashSet::from([1]);
 
+    Ok(Machine {
+        rules,
+        molecule,
+        reductions,
+        atoms,
+        weights,
+        max_shrink,
+        uniform,
+    })
+}
+
+#[must_use]
+pub fn part1(machine: &Machine) -> usize {
+    let molecule = machine.molecule;
     let mut combinations = HashSet::new();
 
-    for (from, to) in rules {
-        for i in 0..=(molecule.len() - from.len()) {
-            if &molecule[i..i + from.len()] == from {
+    for &(from, to) in &machine.rules {
+        for i in 0..=molecule.len().saturating_sub(from.len()) {
+            if molecule.as_bytes()[i..].starts_with(from.as_bytes()) {
                 let mut substitution =
                     String::with_capacity(molecule.len() - from.len() + to.len());
                 substitution.push_str(&molecule[0..i]);
@@ -31,83 +118,163 @@ pub fn part1(input: &str) -> usize {
     combinations.len()
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if input is invalid
-#[must_use]
-pub fn part2(input: &str) -> usize {
-    let (rules, molecule) = parse_input(input).unwrap();
-
-    let mut atom_map = HashMap::new();
-    atom_map.insert("e", 0);
-    let mut rules = rules
-        .into_iter()
-        .map(|(from, into)| {
-            let from = string_to_molecule(from, &mut atom_map)?;
-            if from.len() != 1 {
-                return Err(anyhow!("mapping can only be from one atom to a molecule",));
-            }
-            let into = string_to_molecule(into, &mut atom_map)?;
-            Ok((into, from[0]))
-        })
-        .collect::<anyhow::Result<Vec<_>>>()
-        .unwrap();
-    rules.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
-    let molecule = string_to_molecule(molecule, &mut atom_map).unwrap();
-
+/// Returns an error if the molecule can't be made from an electron
+pub fn part2(machine: &Machine) -> Result<usize, SolveError> {
     let mut astar = crate::shared::astar::AStar::new();
     astar
         .solve(
-            molecule,
-            |molecule| {
-                let mut candidates = arrayvec::ArrayVec::<Molecule, 32>::new();
-                for (into, from) in &rules {
-                    let Some(idx) = find_substr(molecule.as_slice(), into) else {
-                        continue;
-                    };
-
-                    let mut new_molecule = Molecule::with_capacity(molecule.len() + 1 - into.len());
-
-                    new_molecule.extend_from_slice(&molecule[0..idx]);
-                    new_molecule.push(*from);
-                    new_molecule.extend_from_slice(&molecule[idx + into.len()..]);
-                    candidates.push(new_molecule);
-                }
-                candidates.into_iter().map(|a| (a, 1))
-            },
-            |molecule| molecule.len().max(1) - 1,
+            machine.atoms.clone(),
+            |molecule| reduce(&machine.reductions, molecule),
+            |molecule| heuristic(machine, molecule),
+            |molecule| molecule.len() == 1 && molecule[0] == 0,
+        )
+        .and_then(|path| path.last().map(|&(_, steps)| steps))
+        .ok_or(SolveError::no_solution("no solution found for input"))
+}
+
+/// Part 2 with iterative-deepening A* instead, which keeps a lot fewer
+/// molecules around.
+fn part2_iterative_deepening(machine: &Machine) -> Result<usize, SolveError> {
+    let mut ida_star = IdaStar::new();
+    ida_star
+        .solve(
+            machine.atoms.clone(),
+            |molecule| reduce(&machine.reductions, molecule),
+            |molecule| heuristic(machine, molecule),
             |molecule| molecule.len() == 1 && molecule[0] == 0,
         )
-        .map(|path| path.last().unwrap().1)
-        .ok_or_else(|| anyhow!("no solution found for input"))
-        .unwrap()
+        .and_then(|path| path.last().map(|&(_, steps)| steps))
+        .ok_or(SolveError::no_solution("no solution found for input"))
 }
 
-fn parse_input(input: &str) -> anyhow::Result<(Vec<(&str, &str)>, &str)> {
+/// What the searches of part 2 go by to find the fewest replacements making
+/// `molecule` from an electron.
+///
+/// When undoing any replacement takes exactly 1 off the molecule's weight,
+/// as it does for the puzzle input, every way of making the molecule takes
+/// the same number of them, so the searches can't find too many whatever
+/// they go by. They go by the molecule's length then, which leads them to
+/// the electron far quicker than [`steps_left`], even though it may
+/// overestimate. Otherwise they need [`steps_left`], which doesn't.
+fn heuristic(machine: &Machine, molecule: &Molecule) -> usize {
+    if machine.uniform {
+        molecule.len().saturating_sub(1)
+    } else {
+        steps_left(machine, molecule)
+    }
+}
+
+/// The fewest replacements `molecule` could be made in from an electron.
+///
+/// Undoing a replacement takes at most [`Machine::max_shrink`] off the
+/// molecule's weight, which has to come down to that of the electron, so
+/// this never overestimates whatever the weights are.
+fn steps_left(machine: &Machine, molecule: &Molecule) -> usize {
+    let weight = molecule
+        .iter()
+        .map(|&atom| machine.weights[usize::from(atom)])
+        .sum::<isize>();
+    let electron = machine.weights[0];
+    ((weight - electron).max(0) as usize).div_ceil(machine.max_shrink)
+}
+
+/// The molecules one replacement, undone at its first place, away from
+/// `molecule`.
+fn reduce(
+    reductions: &[(Molecule, Atom)],
+    molecule: &Molecule,
+) -> impl Iterator<Item = (Molecule, usize)> {
+    let mut candidates = arrayvec::ArrayVec::<Molecule, 32>::new();
+    for (into, from) in reductions {
+        let Some(idx) = find_substr(molecule.as_slice(), into) else {
+            continue;
+        };
+
+        let mut new_molecule = Molecule::with_capacity(molecule.len() + 1 - into.len());
+
+        new_molecule.extend_from_slice(&molecule[0..idx]);
+        new_molecule.push(*from);
+        new_molecule.extend_from_slice(&molecule[idx + into.len()..]);
+        candidates.push(new_molecule);
+    }
+    candidates.into_iter().map(|a| (a, 1))
+}
+
+/// Solves part 2 with [`IdaStar`], to check the A* search of [`Puzzle`]
+/// against.
+#[derive(Debug)]
+pub struct IterativeDeepening;
+
+impl Solution for IterativeDeepening {
+    const YEAR: u16 = Puzzle::YEAR;
+    const DAY: u8 = Puzzle::DAY;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Machine<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2_iterative_deepening(parsed).map(Answer::from)
+    }
+}
+
+type Rules<'a> = Vec<(&'a str, &'a str)>;
+
+/// The replacements and the medicine molecule, both as written and as atoms.
+#[derive(Debug)]
+pub struct Machine<'a> {
+    rules: Rules<'a>,
+    molecule: &'a str,
+    /// The replacements reversed, from the longest molecule to the shortest.
+    reductions: Vec<(Molecule, Atom)>,
+    atoms: Molecule,
+    /// What each atom adds to the weight of a molecule, see [`steps_left`].
+    weights: Vec<isize>,
+    /// The most that undoing a replacement takes off a molecule's weight.
+    max_shrink: usize,
+    /// Whether undoing any replacement takes exactly 1 off a molecule's
+    /// weight.
+    uniform: bool,
+}
+
+fn parse_input(input: &str) -> Result<(Rules<'_>, &str), SolveError> {
+    let end = &input[input.len()..];
     let mut iter = input.lines();
     let mut rules = Vec::new();
     loop {
         let line = iter
             .next()
-            .ok_or_else(|| anyhow!("unexpected end of input"))?;
+            .or_malformed(input, end, "unexpected end of input")?;
         if line.is_empty() {
             break;
         }
         let mut parts = line.split(" => ");
-        let from = parts.next().ok_or_else(|| anyhow!("expected input atom"))?;
+        let from = parts
+            .next()
+            .or_malformed(input, line, "expected input atom")?;
         let into = parts
             .next()
-            .ok_or_else(|| anyhow!("expected output atom"))?;
-        if parts.next().is_some() {
-            return Err(anyhow!("expected end of line"));
+            .or_malformed(input, line, "expected output atom")?;
+        if let Some(rest) = parts.next() {
+            return Err(SolveError::malformed(input, rest, "expected end of line"));
         }
         rules.push((from, into));
     }
     let molecule = iter
         .next()
-        .ok_or_else(|| anyhow!("unexpected end of input"))?;
-    if iter.next().is_some() {
-        return Err(anyhow!("expected end of input"));
+        .or_malformed(input, end, "unexpected end of input")?;
+    if let Some(rest) = iter.next() {
+        return Err(SolveError::malformed(input, rest, "expected end of input"));
     }
     Ok((rules, molecule))
 }
@@ -118,26 +285,26 @@ type Molecule = Vec<Atom>;
 /// Turns a string into a molecule, which is a more efficient in-memory representation
 /// The maximum amount of unique atoms (including the electron) is 255.
 fn string_to_molecule<'s>(
+    input: &str,
     s: &'s str,
     atom_map: &mut HashMap<&'s str, Atom>,
-) -> anyhow::Result<Molecule> {
+) -> Result<Molecule, SolveError> {
     let mut molecule = Molecule::new();
     let mut idx = 0;
     let mut bytes = s.bytes().peekable();
     while let Some(byte) = bytes.next() {
+        let invalid = |message: &str| Err(SolveError::malformed(input, &s[idx..], message));
         if !byte.is_ascii_alphabetic() {
-            return Err(anyhow!("non-alphabetic character in molecules"));
+            return invalid("non-alphabetic character in molecules");
         }
         let atom_length = if byte == b'e' {
             if s != "e" {
-                return Err(anyhow!("electron isn't allowed within a complex molecule",));
+                return invalid("electron isn't allowed within a complex molecule");
             }
             1
         } else {
             if !byte.is_ascii_uppercase() {
-                return Err(anyhow!(
-                    "expected atom to start with an uppercase character",
-                ));
+                return invalid("expected atom to start with an uppercase character");
             }
             let mut len = 1;
             while let Some(next_char) = bytes.peek().copied() {
@@ -154,7 +321,7 @@ fn string_to_molecule<'s>(
         idx += atom_length;
         let atom_map_len = atom_map.len();
         if atom_map_len == 256 {
-            return Err(anyhow!("too many unique atoms"));
+            return Err(SolveError::malformed(input, slice, "too many unique atoms"));
         }
         let atom = *atom_map
             .entry(slice)
@@ -175,12 +342,40 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 518);
+        let input = crate::input::for_test(2015, 19);
+        assert_eq!(part1(&parse(&input).unwrap()), 518);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 200);
+        let input = crate::input::for_test(2015, 19);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 200);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
+    fn test_heuristic() {
+        let machine = parse("e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOHOHO\n").unwrap();
+        assert!(!machine.uniform);
+        assert_eq!(part2(&machine), Ok(6));
+        assert_eq!(part2_iterative_deepening(&machine), Ok(6));
+
+        let input = crate::input::for_test(2015, 19);
+        let machine = parse(&input).unwrap();
+        assert!(machine.uniform);
+        assert_eq!(steps_left(&machine, &machine.atoms), 200);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
+    fn test_part2_iterative_deepening() {
+        let input = crate::input::for_test(2015, 19);
+        assert_eq!(
+            part2_iterative_deepening(&parse(&input).unwrap()).unwrap(),
+            200
+        );
     }
 }
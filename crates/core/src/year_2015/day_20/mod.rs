@@ -1,19 +1,54 @@
 use std::sync::LazyLock;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Infinite Elves and Infinite Houses";
 
-pub const INPUT: &str = "36000000";
+pub const INPUT: Option<&str> = embedded!("36000000");
 
-/// # Panics
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 20;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(*parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(*parsed).into())
+    }
+}
+
+/// # Errors
 ///
-/// Panics if `input` is invalid
+/// Returns an error if `input` is not a number of presents
+pub fn parse(input: &str) -> Result<u64, SolveError> {
+    let presents = input.trim();
+    presents
+        .parse()
+        .or_malformed(input, presents, "expected a number of presents")
+}
+
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    let input: u64 = input.parse().unwrap();
+pub fn part1(presents: u64) -> usize {
     for house_number in 1.. {
         // The amount of presents is the sum of all the positive integer divisors.
         // Multiplied by 10.
-        if fold_divisors(house_number, |acc, div| acc + div) * 10 >= input {
+        if fold_divisors(house_number, |acc, div| acc + div) * 10 >= presents {
             return house_number;
         }
     }
@@ -22,11 +57,9 @@ pub fn part1(input: &str) -> usize {
 
 /// # Panics
 ///
-/// Panics if `input` is invalid
+/// Panics if the house number no longer fits in a `usize`
 #[must_use]
-pub fn part2(input: &str) -> u64 {
-    let input: u64 = input.parse().unwrap();
-
+pub fn part2(presents: u64) -> u64 {
     for house_number in 1u64.. {
         // The amount of presents is the sum of all the positive integer divisors
         // where those divisors (the elves) haven't previously visited 50 house
@@ -38,7 +71,7 @@ pub fn part2(input: &str) -> u64 {
                 acc
             }
         }) * 11
-            >= input
+            >= presents
         {
             return house_number;
         }
@@ -96,12 +129,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 831_600);
+        let input = crate::input::for_test(2015, 20);
+        assert_eq!(part1(parse(&input).unwrap()), 831_600);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 884_520);
+        let input = crate::input::for_test(2015, 20);
+        assert_eq!(part2(parse(&input).unwrap()), 884_520);
     }
 }
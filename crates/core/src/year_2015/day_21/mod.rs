@@ -1,44 +1,72 @@
 use std::sync::LazyLock;
 
-use anyhow::anyhow;
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
 
 pub const TITLE: &str = "RPG Simulator 20XX";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 21;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Unit;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
 
 /// # Errors
 ///
-/// This function will return an error if no loadout exists that would let the player defeat the boss.
-pub fn part1(input: &str) -> anyhow::Result<usize> {
-    let boss = parse_input(input)?;
+/// This function will return an error if no loadout exists that would let the player defeat the
+/// boss.
+pub fn part1(boss: &Unit) -> Result<usize, SolveError> {
     let mut loadouts = loadouts().collect::<Vec<_>>();
     loadouts.sort_unstable_by(|a, b| a.cost.cmp(&b.cost));
 
     for loadout in loadouts {
-        if does_player_win_fight(&boss, &loadout) {
+        if does_player_win_fight(boss, &loadout) {
             return Ok(loadout.cost);
         }
     }
 
-    Err(anyhow!(
+    Err(SolveError::no_solution(
         "no loadout exists that would let the player defeat the boss",
     ))
 }
 
 /// # Errors
 ///
-/// This function will return an error if no loadout exists that would let the player defeat the boss.
-pub fn part2(input: &str) -> anyhow::Result<usize> {
-    let boss = parse_input(input)?;
+/// This function will return an error if no loadout exists where the boss wins.
+pub fn part2(boss: &Unit) -> Result<usize, SolveError> {
     loadouts()
-        .filter(|loadout| !does_player_win_fight(&boss, loadout))
+        .filter(|loadout| !does_player_win_fight(boss, loadout))
         .map(|loadout| loadout.cost)
         .max()
-        .ok_or_else(|| anyhow!("no loadout exists where the boss wins"))
+        .ok_or(SolveError::no_solution(
+            "no loadout exists where the boss wins",
+        ))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Unit {
+pub struct Unit {
     hp: usize,
     damage: usize,
     armor: usize,
@@ -148,7 +176,13 @@ const fn does_player_win_fight(boss: &Unit, loadout: &Loadout) -> bool {
     boss_damage_before_death < player.hp
 }
 
-fn parse_input(input: &str) -> anyhow::Result<Unit> {
+/// # Errors
+///
+/// This function will return an error if the boss stats are malformed.
+///
+/// # Panics
+/// * Panics if an invalid pattern is given.
+pub fn parse(input: &str) -> Result<Unit, SolveError> {
     use regex::Regex;
     static RE: LazyLock<Regex> = LazyLock::new(|| {
         Regex::new(
@@ -156,10 +190,20 @@ fn parse_input(input: &str) -> anyhow::Result<Unit> {
         )
         .unwrap()
     });
-    let captures = RE.captures(input).ok_or_else(|| anyhow!("invalid input"))?;
-    let hp = captures["hp"].parse()?;
-    let damage = captures["damage"].parse()?;
-    let armor = captures["armor"].parse()?;
+    let captures = RE.captures(input.trim_end()).or_malformed(
+        input,
+        input,
+        "expected `Hit Points`, `Damage` and `Armor` lines",
+    )?;
+    let number = |group: &str| {
+        let value = captures.name(group).unwrap().as_str();
+        value
+            .parse()
+            .or_malformed(input, value, "expected a number")
+    };
+    let hp = number("hp")?;
+    let damage = number("damage")?;
+    let armor = number("armor")?;
 
     Ok(Unit { hp, damage, armor })
 }
@@ -170,12 +214,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT).unwrap(), 111);
+        let input = crate::input::for_test(2015, 21);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 111);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT).unwrap(), 188);
+        let input = crate::input::for_test(2015, 21);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 188);
     }
 }
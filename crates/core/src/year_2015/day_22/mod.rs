@@ -1,55 +1,65 @@
 use std::sync::LazyLock;
 
-use anyhow::anyhow;
 use arrayvec::ArrayVec;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Wizard Simulator 20XX";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 22;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Boss;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
+
+/// # Errors
 ///
-/// Panics if unable to parse input
-pub fn part1(input: &str) -> usize {
-    let boss = parse_input(input).unwrap();
-    let state = State {
-        player: Player { hp: 50, mana: 500 },
-        boss,
-        effects: Effects {
-            shield: 0,
-            poison: 0,
-            recharge: 0,
-        },
-    };
+/// Returns an error if the player can't win
+pub fn part1(boss: &Boss) -> Result<usize, SolveError> {
+    let state = State::new(*boss);
 
     // Use A* because I don't feel like coding up Dijkstra right now
     let mut astar = crate::shared::astar::AStar::new();
-    let path = astar
+    astar
         .solve(state, State::next_states, |_| 0, |state| state.boss.hp <= 0)
-        .ok_or_else(|| anyhow!("no solution found"))
-        .unwrap();
-    path.last().unwrap().1
+        .and_then(|path| path.last().map(|&(_, cost)| cost))
+        .ok_or(SolveError::no_solution(
+            "no sequence of spells beats the boss",
+        ))
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part2(input: &str) -> usize {
-    let boss = parse_input(input).unwrap();
-    let state = State {
-        player: Player { hp: 50, mana: 500 },
-        boss,
-        effects: Effects {
-            shield: 0,
-            poison: 0,
-            recharge: 0,
-        },
-    };
+/// Returns an error if the player can't win
+pub fn part2(boss: &Boss) -> Result<usize, SolveError> {
+    let state = State::new(*boss);
 
     // Use A* because I don't feel like coding up Dijkstra right now
     let mut astar = crate::shared::astar::AStar::new();
-    let path = astar
+    astar
         .solve(
             state,
             |state| {
@@ -64,9 +74,10 @@ pub fn part2(input: &str) -> usize {
             |_| 0,
             |state| state.boss.hp <= 0,
         )
-        .ok_or_else(|| anyhow!("no solution found"))
-        .unwrap();
-    path.last().unwrap().1
+        .and_then(|path| path.last().map(|&(_, cost)| cost))
+        .ok_or(SolveError::no_solution(
+            "no sequence of spells beats the boss",
+        ))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -76,7 +87,7 @@ struct Player {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Boss {
+pub struct Boss {
     hp: i16,
     damage: i16,
 }
@@ -110,6 +121,19 @@ impl Effects {
 }
 
 impl State {
+    /// The start of the fight, before any spell was cast.
+    const fn new(boss: Boss) -> Self {
+        Self {
+            player: Player { hp: 50, mana: 500 },
+            boss,
+            effects: Effects {
+                shield: 0,
+                poison: 0,
+                recharge: 0,
+            },
+        }
+    }
+
     fn execute_boss_turn(&mut self) {
         // Perform effects
         if self.effects.poison != 0 {
@@ -210,15 +234,31 @@ impl State {
     }
 }
 
-fn parse_input(input: &str) -> anyhow::Result<Boss> {
+/// # Errors
+///
+/// Returns an error if unable to parse input
+///
+/// # Panics
+/// * Panics if an invalid pattern is given.
+pub fn parse(input: &str) -> Result<Boss, SolveError> {
     use regex::Regex;
     static RE: LazyLock<Regex> = LazyLock::new(|| {
         Regex::new(r"^Hit Points: (?P<hp>\d+)\r?\nDamage: (?P<damage>\d+)$").unwrap()
     });
 
-    let captures = RE.captures(input).ok_or_else(|| anyhow!("invalid input"))?;
-    let hp = captures["hp"].parse()?;
-    let damage = captures["damage"].parse()?;
+    let captures = RE.captures(input.trim_end()).or_malformed(
+        input,
+        input,
+        "expected `Hit Points` and `Damage` lines",
+    )?;
+    let number = |group: &str| {
+        let value = captures.name(group).unwrap().as_str();
+        value
+            .parse()
+            .or_malformed(input, value, "expected a number")
+    };
+    let hp = number("hp")?;
+    let damage = number("damage")?;
 
     Ok(Boss { hp, damage })
 }
@@ -228,12 +268,38 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 953);
+        let input = crate::input::for_test(2015, 22);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 953);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 1289);
+        let input = crate::input::for_test(2015, 22);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 1289);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
+    fn test_cheapest_wins() {
+        let input = crate::input::for_test(2015, 22);
+        let boss = parse(&input).unwrap();
+        let mut astar = crate::shared::astar::AStar::new();
+        let cost = astar.solve_all(
+            State::new(boss),
+            State::next_states,
+            |_| 0,
+            |state| state.boss.hp <= 0,
+        );
+        assert_eq!(cost, Ok(Some(953)));
+        assert_eq!(astar.count_optimal_paths(), 31);
+
+        let paths = astar.optimal_paths().collect::<Vec<_>>();
+        assert_eq!(paths.len(), 31);
+        assert!(paths
+            .iter()
+            .all(|path| path.last().is_some_and(|&(_, cost)| cost == 953)));
     }
 }
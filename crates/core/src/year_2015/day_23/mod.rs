@@ -1,23 +1,44 @@
-use anyhow::anyhow;
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
 
 pub const TITLE: &str = "Opening the Turing Lock";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 23;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    solve(input, 0).unwrap()
+pub fn part1(instructions: &[Instruction]) -> usize {
+    solve(instructions, 0)
 }
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part2(input: &str) -> usize {
-    solve(input, 1).unwrap()
+pub fn part2(instructions: &[Instruction]) -> usize {
+    solve(instructions, 1)
 }
 
 fn access(regs: &mut [usize; 2], reg: Register) -> &mut usize {
@@ -27,8 +48,7 @@ fn access(regs: &mut [usize; 2], reg: Register) -> &mut usize {
     }
 }
 
-fn solve(input: &str, start_a: usize) -> anyhow::Result<usize> {
-    let instructions = parse_input(input)?;
+fn solve(instructions: &[Instruction], start_a: usize) -> usize {
     let mut ip = 0isize;
     let regs = &mut [start_a, 0];
 
@@ -65,17 +85,17 @@ fn solve(input: &str, start_a: usize) -> anyhow::Result<usize> {
         }
         // println!("     [{:>6 } {:>6 }]", regs[0], regs[1]);
     }
-    Ok(regs[1])
+    regs[1]
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Register {
+pub enum Register {
     A,
     B,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Instruction {
+pub enum Instruction {
     Half(Register),
     Tripple(Register),
     Increment(Register),
@@ -84,41 +104,53 @@ enum Instruction {
     JumpIfOne(Register, isize),
 }
 
-fn parse_input(input: &str) -> anyhow::Result<Vec<Instruction>> {
-    fn parse_register(s: &str) -> anyhow::Result<Register> {
-        if s.len() == 1 {
-            match s.as_bytes()[0] {
-                b'a' => return Ok(Register::A),
-                b'b' => return Ok(Register::B),
-                _ => {}
-            }
-        }
-        Err(anyhow!("invalid register"))
-    }
+/// # Errors
+///
+/// Returns an error if unable to parse input
+///
+/// # Panics
+/// * Panics if a conditional jump has no register.
+pub fn parse(input: &str) -> Result<Vec<Instruction>, SolveError> {
+    let parse_register = |s: &str| match s {
+        "a" => Ok(Register::A),
+        "b" => Ok(Register::B),
+        _ => Err(SolveError::malformed(input, s, "invalid register")),
+    };
+    let parse_offset = |s: &str| s.parse().or_malformed(input, s, "invalid offset");
     input
         .lines()
         .map(|line| {
-            if line.len() < 5 {
-                return Err(anyhow!("invalid instruction length"));
+            let (Some(name), Some(operands)) = (line.get(0..4), line.get(4..)) else {
+                return Err(SolveError::malformed(
+                    input,
+                    line,
+                    "invalid instruction length",
+                ));
+            };
+            if operands.is_empty() {
+                return Err(SolveError::malformed(
+                    input,
+                    line,
+                    "invalid instruction length",
+                ));
             }
-            let constructor = match &line[0..4] {
-                "hlf " => return Ok(Instruction::Half(parse_register(&line[4..])?)),
-                "tpl " => return Ok(Instruction::Tripple(parse_register(&line[4..])?)),
-                "inc " => return Ok(Instruction::Increment(parse_register(&line[4..])?)),
-                "jmp " => return Ok(Instruction::Jump(line[4..].parse()?)),
+            let constructor = match name {
+                "hlf " => return Ok(Instruction::Half(parse_register(operands)?)),
+                "tpl " => return Ok(Instruction::Tripple(parse_register(operands)?)),
+                "inc " => return Ok(Instruction::Increment(parse_register(operands)?)),
+                "jmp " => return Ok(Instruction::Jump(parse_offset(operands)?)),
                 "jie " => Instruction::JumpIfEven,
                 "jio " => Instruction::JumpIfOne,
-                _ => return Err(anyhow!("invalid instruction")),
+                _ => return Err(SolveError::malformed(input, line, "invalid instruction")),
             };
-            let mut parts = line[4..].split(", ");
+            let mut parts = operands.split(", ");
             let reg = parse_register(parts.next().unwrap())?;
             let offset = parts
                 .next()
-                .ok_or_else(|| anyhow!("expected offset"))?
-                .parse()?;
-            Ok(constructor(reg, offset))
+                .or_malformed(input, operands, "expected offset")?;
+            Ok(constructor(reg, parse_offset(offset)?))
         })
-        .collect::<anyhow::Result<_>>()
+        .collect()
 }
 
 #[cfg(test)]
@@ -126,12 +158,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 255);
+        let input = crate::input::for_test(2015, 23);
+        assert_eq!(part1(&parse(&input).unwrap()), 255);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 334);
+        let input = crate::input::for_test(2015, 23);
+        assert_eq!(part2(&parse(&input).unwrap()), 334);
     }
 }
@@ -1,23 +1,63 @@
-use anyhow::anyhow;
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
 
 pub const TITLE: &str = "It Hangs in the Balance";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 24;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
+
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part1(input: &str) -> u64 {
-    solve(input, 3).unwrap()
+/// Returns an error if unable to parse input
+pub fn parse(input: &str) -> Result<Vec<u64>, SolveError> {
+    let mut nrs = input
+        .lines()
+        .map(|nr| {
+            nr.parse()
+                .or_malformed(input, nr, "expected a package weight")
+        })
+        .collect::<Result<Vec<u64>, _>>()?;
+    nrs.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(nrs)
+}
+
+/// # Errors
+///
+/// Returns an error if the packages can't be balanced
+pub fn part1(weights: &[u64]) -> Result<u64, SolveError> {
+    solve(weights, 3)
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part2(input: &str) -> u64 {
-    solve(input, 4).unwrap()
+/// Returns an error if the packages can't be balanced
+pub fn part2(weights: &[u64]) -> Result<u64, SolveError> {
+    solve(weights, 4)
 }
 
 fn difference<A, B, I>(a: A, mut b: B) -> Difference<A, B, I>
@@ -60,16 +100,14 @@ fn compute_quantum_entanglement(slice: &[u64]) -> u64 {
     slice.iter().fold(1, |acc, nr| acc * (*nr))
 }
 
-fn solve(input: &str, buckets: u64) -> anyhow::Result<u64> {
-    let mut nrs = input
-        .lines()
-        .map(|nr| Ok(nr.parse()?))
-        .collect::<anyhow::Result<Vec<u64>>>()?;
-    nrs.sort_unstable_by(|a, b| b.cmp(a));
-
+/// Given the package weights in descending order, returns the quantum
+/// entanglement of the smallest first group.
+fn solve(nrs: &[u64], buckets: u64) -> Result<u64, SolveError> {
     let total_weight = nrs.iter().sum::<u64>();
     if total_weight % buckets != 0 {
-        return Err(anyhow!("sum of the numbers has to be a multiple of 3"));
+        return Err(SolveError::no_solution(
+            "sum of the weights has to be a multiple of the group count",
+        ));
     }
 
     let mut combinations = Vec::new();
@@ -82,7 +120,7 @@ fn solve(input: &str, buckets: u64) -> anyhow::Result<u64> {
         fn backtrack(
             stack: &mut Vec<u64>,
             combinations: &mut Vec<Vec<u64>>,
-            nrs: &Vec<u64>,
+            nrs: &[u64],
             weight_per_bucket: u64,
             number_count: usize,
             start_idx: usize,
@@ -116,7 +154,7 @@ fn solve(input: &str, buckets: u64) -> anyhow::Result<u64> {
         backtrack(
             &mut backtrack_stack,
             &mut combinations,
-            &nrs,
+            nrs,
             weight_per_bucket,
             number_count,
             0,
@@ -160,7 +198,9 @@ fn solve(input: &str, buckets: u64) -> anyhow::Result<u64> {
         }
     }
 
-    Err(anyhow!("no solution found"))
+    Err(SolveError::no_solution(
+        "the packages can't be split into equal groups",
+    ))
 }
 
 #[cfg(test)]
@@ -168,12 +208,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 11_846_773_891);
+        let input = crate::input::for_test(2015, 24);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 11_846_773_891);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 80_393_059);
+        let input = crate::input::for_test(2015, 24);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 80_393_059);
     }
 }
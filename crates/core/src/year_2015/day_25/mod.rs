@@ -1,15 +1,38 @@
-use anyhow::anyhow;
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
 
 pub const TITLE: &str = "Let It Snow";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 25;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = (u64, u64);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part1(input: &str) -> u64 {
-    let (row, column) = parse_input(input).unwrap();
+pub fn part1(&(row, column): &(u64, u64)) -> u64 {
     let (row, column) = (row - 1, column - 1); // Make 0 based
 
     let idx = get_grid_index(row, column) + 1;
@@ -21,8 +44,9 @@ pub fn part1(input: &str) -> u64 {
     nr
 }
 
+/// Day 25 has no second puzzle.
 #[must_use]
-pub const fn part2(_: &str) -> u64 {
+pub const fn part2(_: &(u64, u64)) -> u64 {
     0
 }
 
@@ -30,30 +54,44 @@ const fn get_grid_index(row: u64, column: u64) -> u64 {
     (row + column + 1) * (row + column) / 2 + column
 }
 
-fn parse_input(input: &str) -> anyhow::Result<(u64, u64)> {
+/// # Errors
+///
+/// Returns an error if unable to parse input
+pub fn parse(full_input: &str) -> Result<(u64, u64), SolveError> {
     const PREFIX: &str =
         "To continue, please consult the code grid in the manual.  Enter the code at row ";
     const MID: &str = ", column ";
     const SUFFIX: &str = ".";
 
+    let coordinate = |s: &str| match s.parse() {
+        Ok(value) if value >= 1 => Ok(value),
+        _ => Err(SolveError::malformed(
+            full_input,
+            s,
+            "expected a positive number",
+        )),
+    };
+
+    let input = full_input.trim_end();
     if input.len() < PREFIX.len() + MID.len() + SUFFIX.len() + 2 {
-        return Err(anyhow!("input too short"));
+        return Err(SolveError::malformed(full_input, input, "input too short"));
     }
 
-    if &input[0..PREFIX.len()] != PREFIX {
-        return Err(anyhow!("invalid prefix"));
-    }
-    let input = &input[PREFIX.len()..];
-    let idx = input.find(MID).ok_or_else(|| anyhow!("no mid found"))?;
-    let row = input[0..idx].parse()?;
+    let input = input
+        .strip_prefix(PREFIX)
+        .or_malformed(full_input, input, "invalid prefix")?;
+    let idx = input
+        .find(MID)
+        .or_malformed(full_input, input, "no mid found")?;
+    let row = coordinate(&input[0..idx])?;
     let input = &input[idx + MID.len()..];
     let idx = input
         .find(SUFFIX)
-        .ok_or_else(|| anyhow!("no suffix found"))?;
-    let column = input[0..idx].parse()?;
+        .or_malformed(full_input, input, "no suffix found")?;
+    let column = coordinate(&input[0..idx])?;
     let input = &input[idx + SUFFIX.len()..];
     if !input.is_empty() {
-        return Err(anyhow!("input not empty"));
+        return Err(SolveError::malformed(full_input, input, "input not empty"));
     }
 
     Ok((row, column))
@@ -64,7 +102,9 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 19_980_801);
+        let input = crate::input::for_test(2015, 25);
+        assert_eq!(part1(&parse(&input).unwrap()), 19_980_801);
     }
 }
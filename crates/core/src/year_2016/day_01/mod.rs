@@ -1,63 +1,103 @@
 use std::collections::HashSet;
 
-use crate::shared::point::Point;
+use crate::{
+    answer::Answer,
+    error::SolveError,
+    input::embedded,
+    shared::{parse::OrMalformed, point::Point},
+    solution::Solution,
+};
 
 pub const TITLE: &str = "No Time for a Taxicab";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 1;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<(char, i32)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
 
-/// # Panics
-/// Panics if input is empty
 #[must_use]
-pub fn part1(input: &str) -> i32 {
-    let words = parse(input);
+pub fn part1(steps: &[(char, i32)]) -> i32 {
     let mut position = Point::ORIGIN;
     let mut direction = Point::UP;
 
-    for step in words {
-        direction = if step.starts_with('L') {
+    for &(turn, distance) in steps {
+        direction = if turn == 'L' {
             direction.counter_clockwise()
         } else {
             direction.clockwise()
         };
 
-        position += direction * step[1..].parse::<i32>().unwrap();
+        position += direction * distance;
     }
 
     position.manhattan(Point::ORIGIN)
 }
 
-/// # Panics
-/// Panics if input is empty
-#[must_use]
-pub fn part2(input: &str) -> i32 {
-    let words = parse(input);
+/// # Errors
+/// Returns an error if no location is visited twice
+pub fn part2(steps: &[(char, i32)]) -> Result<i32, SolveError> {
     let mut position = Point::ORIGIN;
     let mut direction = Point::UP;
     let mut visited = HashSet::with_capacity(1000);
 
-    for step in words {
-        direction = if step.starts_with('L') {
+    for &(turn, distance) in steps {
+        direction = if turn == 'L' {
             direction.counter_clockwise()
         } else {
             direction.clockwise()
         };
 
-        let m = step[1..].parse::<i32>().unwrap();
-
-        for _ in 0..m {
+        for _ in 0..distance {
             position += direction;
             if !visited.insert(position) {
-                return position.manhattan(Point::ORIGIN);
+                return Ok(position.manhattan(Point::ORIGIN));
             }
         }
     }
 
-    unreachable!()
+    Err(SolveError::no_solution("no location is visited twice"))
 }
 
-fn parse(input: &str) -> Vec<String> {
-    input.split(',').map(str::trim).map(str::to_owned).collect()
+/// # Errors
+/// Returns an error if a step isn't of the form `L<n>` or `R<n>`
+pub fn parse(input: &str) -> Result<Vec<(char, i32)>, SolveError> {
+    input
+        .split(',')
+        .map(str::trim)
+        .map(|step| {
+            let turn = step
+                .chars()
+                .next()
+                .filter(|turn| matches!(turn, 'L' | 'R'))
+                .or_malformed(input, step, "expected a turn `L` or `R`")?;
+            let distance =
+                step[1..]
+                    .parse()
+                    .or_malformed(input, &step[1..], "expected a distance")?;
+            Ok((turn, distance))
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -65,12 +105,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 231);
+        let input = crate::input::for_test(2016, 1);
+        assert_eq!(part1(&parse(&input).unwrap()), 231);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 147);
+        let input = crate::input::for_test(2016, 1);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 147);
     }
 }
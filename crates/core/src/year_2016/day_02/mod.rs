@@ -1,20 +1,48 @@
-use crate::shared::{grid::Grid, point::Point};
+use crate::{
+    answer::Answer,
+    error::SolveError,
+    input::embedded,
+    shared::{grid::Grid, point::Point},
+    solution::Solution,
+};
 
 pub const TITLE: &str = "Bathroom Security";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 2;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Vec<Point>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
-/// # Panics
-/// Panics if input is empty
 #[must_use]
-pub fn part1(input: &str) -> String {
+pub fn part1(lines: &[Vec<Point>]) -> String {
     let digits = Grid::parse("123\n456\n789");
     let mut position = Point::ORIGIN;
     let mut result = String::new();
 
-    for line in input.lines() {
-        for b in line.bytes() {
-            let next = position + Point::from(b);
+    for line in lines {
+        for &step in line {
+            let next = position + step;
             if next.x.abs() <= 1 && next.y.abs() <= 1 {
                 position = next;
             }
@@ -25,17 +53,15 @@ pub fn part1(input: &str) -> String {
     result
 }
 
-/// # Panics
-/// Panics if input is empty
 #[must_use]
-pub fn part2(input: &str) -> String {
+pub fn part2(lines: &[Vec<Point>]) -> String {
     let digits = Grid::parse("##1##\n#234#\n56789\n#ABC#\n##D##");
     let mut position = Point::new(-2, 0);
     let mut result = String::new();
 
-    for line in input.lines() {
-        for b in line.bytes() {
-            let next = position + Point::from(b);
+    for line in lines {
+        for &step in line {
+            let next = position + step;
             if next.manhattan(Point::ORIGIN) <= 2 {
                 position = next;
             }
@@ -46,17 +72,41 @@ pub fn part2(input: &str) -> String {
     result
 }
 
+/// # Errors
+/// Returns an error if an instruction isn't one of `U`, `D`, `L` or `R`
+pub fn parse(input: &str) -> Result<Vec<Vec<Point>>, SolveError> {
+    input
+        .lines()
+        .map(|line| {
+            line.char_indices()
+                .map(|(index, c)| match c {
+                    'U' | 'D' | 'L' | 'R' => Ok(Point::from(c as u8)),
+                    _ => Err(SolveError::malformed(
+                        input,
+                        &line[index..],
+                        "expected `U`, `D`, `L` or `R`",
+                    )),
+                })
+                .collect()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), "92435");
+        let input = crate::input::for_test(2016, 2);
+        assert_eq!(part1(&parse(&input).unwrap()), "92435");
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), "C1A88");
+        let input = crate::input::for_test(2016, 2);
+        assert_eq!(part2(&parse(&input).unwrap()), "C1A88");
     }
 }
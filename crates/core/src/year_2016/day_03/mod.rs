@@ -1,34 +1,72 @@
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Squares With Three Sides";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 3;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
-/// # Panics
-/// Panics if input is empty
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    count(
-        input
-            .lines()
-            .flat_map(|s| s.split(' '))
-            .flat_map(str::parse),
-    )
+pub fn part1(sides: &[u32]) -> usize {
+    count(sides.iter().copied())
 }
 
-/// # Panics
-/// Panics if input is empty
 #[must_use]
-pub fn part2(input: &str) -> usize {
-    let input = input
-        .lines()
-        .flat_map(|s| s.split(' '))
-        .flat_map(str::parse)
-        .collect::<Vec<_>>();
-    let first = count(input.iter().copied().step_by(3));
-    let second = count(input.iter().copied().skip(1).step_by(3));
-    let third = count(input.iter().copied().skip(2).step_by(3));
+pub fn part2(sides: &[u32]) -> usize {
+    let first = count(sides.iter().copied().step_by(3));
+    let second = count(sides.iter().copied().skip(1).step_by(3));
+    let third = count(sides.iter().copied().skip(2).step_by(3));
     first + second + third
 }
 
+/// # Errors
+/// Returns an error if a line doesn't hold three side lengths
+pub fn parse(input: &str) -> Result<Vec<u32>, SolveError> {
+    let mut sides = Vec::new();
+    for line in input.lines() {
+        let before = sides.len();
+        for side in line.split_whitespace() {
+            sides.push(
+                side.parse()
+                    .or_malformed(input, side, "expected a side length")?,
+            );
+        }
+        if sides.len() - before != 3 {
+            return Err(SolveError::malformed(
+                input,
+                line,
+                "expected three side lengths",
+            ));
+        }
+    }
+    Ok(sides)
+}
+
 #[inline]
 fn count(iter: impl Iterator<Item = u32>) -> usize {
     iter.array_chunks::<3>()
@@ -41,12 +79,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 993);
+        let input = crate::input::for_test(2016, 3);
+        assert_eq!(part1(&parse(&input).unwrap()), 993);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 1849);
+        let input = crate::input::for_test(2016, 3);
+        assert_eq!(part2(&parse(&input).unwrap()), 1849);
     }
 }
@@ -1,6 +1,35 @@
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Security Through Obscurity";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 4;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Room<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
 
 #[derive(Debug)]
 pub struct Room<'a> {
@@ -8,37 +37,36 @@ pub struct Room<'a> {
     sector_id: u32,
 }
 
-/// # Panics
-/// Panics if input is empty
 #[must_use]
-pub fn part1(input: &str) -> u32 {
-    let rooms = parse(input).unwrap();
+pub fn part1(rooms: &[Room]) -> u32 {
     rooms.iter().map(|room| room.sector_id).sum()
 }
 
-/// # Panics
-/// Panics if input is empty
-#[must_use]
-pub fn part2(input: &str) -> u32 {
-    let rooms = parse(input).unwrap();
-    for Room { name, sector_id } in rooms {
+/// # Errors
+/// Returns an error if the North Pole objects aren't stored anywhere
+pub fn part2(rooms: &[Room]) -> Result<u32, SolveError> {
+    for &Room { name, sector_id } in rooms {
         // Check if the name has the correct format
         if name.len() == 24 && name.as_bytes()[9] == b'-' && name.as_bytes()[16] == b'-' {
             let decrypted_name = decrypt_name(name, sector_id);
             if decrypted_name == "northpole object storage" {
-                return sector_id;
+                return Ok(sector_id);
             }
         }
     }
-    unreachable!()
+    Err(SolveError::no_solution(
+        "no room holds the North Pole objects",
+    ))
 }
 
-fn parse(input: &str) -> anyhow::Result<Vec<Room<'_>>> {
+/// # Errors
+/// Returns an error if a room doesn't parse
+pub fn parse(input: &str) -> Result<Vec<Room<'_>>, SolveError> {
     let mut valid_rooms = Vec::new();
     let to_index = |b: u8| (b - b'a') as usize;
 
     for line in input.lines() {
-        let (name, sector_id, checksum) = split_line(line)?;
+        let (name, sector_id, checksum) = split_line(input, line)?;
 
         let (freq, fof, highest_freq) = calculate_frequencies(name, to_index);
 
@@ -56,12 +84,33 @@ fn parse(input: &str) -> anyhow::Result<Vec<Room<'_>>> {
     Ok(valid_rooms)
 }
 
-fn split_line(line: &str) -> anyhow::Result<(&str, u32, &[u8])> {
-    let size = line.len();
-    let name = &line[..size - 11];
-    let sector_id = line[size - 10..size - 7].parse::<u32>()?;
-    let checksum = line[size - 6..size - 1].as_bytes();
-    Ok((name, sector_id, checksum))
+fn split_line<'a>(input: &str, line: &'a str) -> Result<(&'a str, u32, &'a [u8]), SolveError> {
+    let (name, rest) =
+        line.rsplit_once('-')
+            .or_malformed(input, line, "expected `name-sector[checksum]`")?;
+    let (sector_id, checksum) = rest
+        .strip_suffix(']')
+        .and_then(|rest| rest.split_once('['))
+        .or_malformed(input, rest, "expected `sector[checksum]`")?;
+    let sector_id =
+        sector_id
+            .parse::<u32>()
+            .or_malformed(input, sector_id, "expected a sector id")?;
+    if checksum.len() != 5 || !checksum.bytes().all(|b| b.is_ascii_lowercase()) {
+        return Err(SolveError::malformed(
+            input,
+            checksum,
+            "expected five lowercase letters",
+        ));
+    }
+    if let Some(at) = name.find(|c: char| c != '-' && !c.is_ascii_lowercase()) {
+        return Err(SolveError::malformed(
+            input,
+            &name[at..],
+            "expected lowercase letters and dashes",
+        ));
+    }
+    Ok((name, sector_id, checksum.as_bytes()))
 }
 
 fn calculate_frequencies(
@@ -136,12 +185,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 158_835);
+        let input = crate::input::for_test(2016, 4);
+        assert_eq!(part1(&parse(&input).unwrap()), 158_835);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 993);
+        let input = crate::input::for_test(2016, 4);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 993);
     }
 }
@@ -1,18 +1,42 @@
 use md5::{Digest, Md5};
 
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
+
 pub const TITLE: &str = "How About a Nice Game of Chess?";
 
-pub const INPUT: &str = "wtnhxymk";
+pub const INPUT: Option<&str> = embedded!("wtnhxymk");
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 5;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
-/// # Panics
-/// Panics if input is empty
 #[must_use]
-pub fn part1(input: &str) -> String {
+pub fn part1(door_id: &str) -> String {
     let mut count = 0usize;
     let mut index = 0u64;
     let mut password = 0u64;
     while count < 8 {
-        let hash = md5_prefix(input, index) / 256;
+        let hash = md5_prefix(door_id, index) / 256;
         if hash / 16 == 0 {
             count += 1;
             password = 16 * password + (hash % 16);
@@ -23,15 +47,13 @@ pub fn part1(input: &str) -> String {
     format!("{password:08x}")
 }
 
-/// # Panics
-/// Panics if input is empty
 #[must_use]
-pub fn part2(input: &str) -> String {
+pub fn part2(door_id: &str) -> String {
     let mut pmask = 0u8;
     let mut index = 0u64;
     let mut password = 0u64;
     while pmask < 255 {
-        let hash = md5_prefix(input, index);
+        let hash = md5_prefix(door_id, index);
         let pfix = hash & 0xFFFF_F000;
         let posn = (hash & 0x0000_0F00) / 256;
         let pval = (hash & 0x0000_00F0) / 16;
@@ -48,6 +70,16 @@ pub fn part2(input: &str) -> String {
     format!("{password:08x}")
 }
 
+/// # Errors
+/// Returns an error if the door id is empty
+pub fn parse(input: &str) -> Result<&str, SolveError> {
+    let id = input.trim();
+    if id.is_empty() {
+        return Err(SolveError::malformed(input, id, "expected a door id"));
+    }
+    Ok(id)
+}
+
 // Return the first four bytes of the MD5 hash.
 fn md5_prefix(base: &str, idx: u64) -> u64 {
     let salt = format!("{base}{idx}");
@@ -70,12 +102,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), "2414bc77");
+        let input = crate::input::for_test(2016, 5);
+        assert_eq!(part1(parse(&input).unwrap()), "2414bc77");
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), "437e60fc");
+        let input = crate::input::for_test(2016, 5);
+        assert_eq!(part2(parse(&input).unwrap()), "437e60fc");
     }
 }
@@ -1,53 +1,95 @@
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Signals and Noise";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
-/// Panics if input is empty
-#[must_use]
-pub fn part1(input: &str) -> String {
-    let freq = calculate_frequency(input);
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 6;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<[usize; 26]>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
+#[must_use]
+pub fn part1(freq: &[[usize; 26]]) -> String {
     freq.iter()
-        .map(|freq| {
-            freq.iter()
-                .enumerate()
+        .filter_map(|freq| {
+            (b'a'..=b'z')
+                .zip(freq)
                 .filter(|(_, f)| **f > 0)
                 .max_by_key(|(_, f)| **f)
-                .unwrap()
         })
-        .map(|(index, _)| (u8::try_from(index).unwrap() + b'a') as char)
+        .map(|(letter, _)| letter as char)
         .collect()
 }
 
-/// # Panics
-/// Panics if input is empty
 #[must_use]
-pub fn part2(input: &str) -> String {
-    let freq = calculate_frequency(input);
-
+pub fn part2(freq: &[[usize; 26]]) -> String {
     freq.iter()
-        .map(|freq| {
-            freq.iter()
-                .enumerate()
+        .filter_map(|freq| {
+            (b'a'..=b'z')
+                .zip(freq)
                 .filter(|(_, f)| **f > 0)
                 .min_by_key(|(_, f)| **f)
-                .unwrap()
         })
-        .map(|(index, _)| (u8::try_from(index).unwrap() + b'a') as char)
+        .map(|(letter, _)| letter as char)
         .collect()
 }
 
-#[must_use]
-fn calculate_frequency(input: &str) -> Vec<[usize; 26]> {
-    let width = input.lines().next().unwrap().len();
+/// Counts how often each letter appears at each position of the messages.
+///
+/// # Errors
+/// Returns an error if the messages are empty or not all lowercase letters of the same length
+pub fn parse(input: &str) -> Result<Vec<[usize; 26]>, SolveError> {
+    let width = input
+        .lines()
+        .next()
+        .or_malformed(input, input, "expected at least one message")?
+        .len();
     let mut freq = vec![[0; 26]; width];
 
-    for (i, b) in input.bytes().filter(u8::is_ascii_lowercase).enumerate() {
-        freq[i % width][(b - b'a') as usize] += 1;
+    for line in input.lines() {
+        if line.len() != width {
+            return Err(SolveError::malformed(
+                input,
+                line,
+                format!("expected a message of {width} letters"),
+            ));
+        }
+        for (i, b) in line.bytes().enumerate() {
+            if !b.is_ascii_lowercase() {
+                return Err(SolveError::malformed(
+                    input,
+                    &line[i..],
+                    "expected a lowercase letter",
+                ));
+            }
+            freq[i][(b - b'a') as usize] += 1;
+        }
     }
 
-    freq
+    Ok(freq)
 }
 
 #[cfg(test)]
@@ -55,12 +97,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), "qoclwvah");
+        let input = crate::input::for_test(2016, 6);
+        assert_eq!(part1(&parse(&input).unwrap()), "qoclwvah");
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), "ryrgviuv");
+        let input = crate::input::for_test(2016, 6);
+        assert_eq!(part2(&parse(&input).unwrap()), "ryrgviuv");
     }
 }
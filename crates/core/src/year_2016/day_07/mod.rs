@@ -1,15 +1,55 @@
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
+
 pub const TITLE: &str = "Internet Protocol Version 7";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 7;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
+/// Returns an error if an address holds anything but lowercase letters and brackets
+pub fn parse(input: &str) -> Result<&str, SolveError> {
+    input
+        .find(|c: char| !matches!(c, 'a'..='z' | '[' | ']' | '\n'))
+        .map_or(Ok(input), |at| {
+            Err(SolveError::malformed(
+                input,
+                &input[at..],
+                "expected a lowercase letter or bracket",
+            ))
+        })
+}
 
 #[must_use]
-pub fn part1(input: &str) -> usize {
+pub fn part1(addresses: &str) -> usize {
     let mut count = 0;
     let mut inside = false;
     let mut positive = false;
     let mut negative = false;
 
-    for w in input.as_bytes().windows(4) {
+    for w in addresses.as_bytes().windows(4) {
         if w[0].is_ascii_lowercase() {
             if is_palindrome(w) {
                 if inside {
@@ -40,7 +80,7 @@ pub fn part1(input: &str) -> usize {
 }
 
 #[must_use]
-pub fn part2(input: &str) -> usize {
+pub fn part2(addresses: &str) -> usize {
     let mut count = 0;
     let mut version = 0;
     let mut inside = false;
@@ -48,7 +88,7 @@ pub fn part2(input: &str) -> usize {
     let mut aba = [usize::MAX; 676];
     let mut bab = [usize::MAX; 676];
 
-    for w in input.as_bytes().windows(3) {
+    for w in addresses.as_bytes().windows(3) {
         if w[1].is_ascii_lowercase() {
             if w[0] == w[2] && w[0] != w[1] {
                 let (first, second) = bytes_to_indices(w);
@@ -100,12 +140,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 118);
+        let input = crate::input::for_test(2016, 7);
+        assert_eq!(part1(parse(&input).unwrap()), 118);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 260);
+        let input = crate::input::for_test(2016, 7);
+        assert_eq!(part2(parse(&input).unwrap()), 260);
     }
 }
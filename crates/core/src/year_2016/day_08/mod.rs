@@ -1,79 +1,135 @@
 use itertools::Itertools;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Two-Factor Authentication";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-#[must_use]
-/// # Panics
-/// Panics if input is empty
-pub fn part1(input: &str) -> usize {
-    let mut display = [[false; 50]; 6];
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 8;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Operation>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(Answer::Art(part2(parsed).trim_start().to_string()))
+    }
+}
 
-    for line in input.lines() {
-        let words: Vec<&str> = line.split_whitespace().collect();
-        if words[0] == "rect" {
-            let dims: Vec<usize> = words[1].split('x').map(|s| s.parse().unwrap()).collect();
-            let width = dims[0];
-            let height = dims[1];
-            for display in display.iter_mut().take(height) {
-                for display in display.iter_mut().take(width) {
-                    *display = true;
+/// # Errors
+/// Returns an error if an operation doesn't parse or falls outside the screen
+pub fn parse(input: &str) -> Result<Vec<Operation>, SolveError> {
+    input
+        .lines()
+        .map(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                ["rect", dims] => {
+                    let (width, height) = dims
+                        .split_once('x')
+                        .and_then(|(width, height)| {
+                            Some((width.parse().ok()?, height.parse().ok()?))
+                        })
+                        .filter(|&(width, height)| width <= 50 && height <= 6)
+                        .or_malformed(input, dims, "expected `AxB` within the screen")?;
+                    Ok(Operation::Rect(width, height))
                 }
-            }
-        } else if words[0] == "rotate" {
-            let row = words[1] == "row";
-            let pos: usize = words[2].split('=').nth(1).unwrap().parse().unwrap();
-            let by: usize = words[4].parse().unwrap();
-            for _ in 0..by {
-                if row {
-                    let tmp = display[pos][49];
-                    for i in (1..50).rev() {
-                        display[pos][i] = display[pos][i - 1];
-                    }
-                    display[pos][0] = tmp;
-                } else {
-                    let tmp = display[5][pos];
-                    for i in (1..6).rev() {
-                        display[i][pos] = display[i - 1][pos];
-                    }
-                    display[0][pos] = tmp;
+                ["rotate", axis @ ("row" | "column"), pos, "by", by] => {
+                    let row = *axis == "row";
+                    let pos: usize = pos
+                        .split_once('=')
+                        .and_then(|(_, pos)| pos.parse().ok())
+                        .filter(|&pos| pos < if row { 6 } else { 50 })
+                        .or_malformed(input, pos, "expected a position within the screen")?;
+                    let by: usize = by.parse().or_malformed(input, by, "expected a number")?;
+                    Ok(if row {
+                        Operation::RotateRow(pos, by)
+                    } else {
+                        Operation::RotateColumn(pos, by)
+                    })
                 }
+                _ => Err(SolveError::malformed(input, line, "unknown operation")),
             }
-        }
-    }
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn part1(operations: &[Operation]) -> usize {
+    let display = run(operations);
     display.iter().flatten().filter(|&b| *b).count()
 }
 
 #[must_use]
-/// # Panics
-/// Panics if input is empty
-pub fn part2(input: &str) -> String {
+pub fn part2(operations: &[Operation]) -> String {
+    let display = run(operations);
+
+    let mut result = display
+        .iter()
+        .map(|bools| {
+            bools
+                .iter()
+                .map(|flag| if *flag { "#" } else { "." })
+                .collect::<String>()
+        })
+        .join("\n");
+
+    result.insert(0, '\n');
+
+    result
+}
+
+/// An operation on the screen, with every position already known to be on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Turns on a rectangle of the given width and height in the top left.
+    Rect(usize, usize),
+    /// Shifts a row right by the given amount.
+    RotateRow(usize, usize),
+    /// Shifts a column down by the given amount.
+    RotateColumn(usize, usize),
+}
+
+fn run(operations: &[Operation]) -> [[bool; 50]; 6] {
     let mut display = [[false; 50]; 6];
 
-    for line in input.lines() {
-        let words: Vec<&str> = line.split_whitespace().collect();
-        if words[0] == "rect" {
-            let dims: Vec<usize> = words[1].split('x').map(|s| s.parse().unwrap()).collect();
-            let width = dims[0];
-            let height = dims[1];
-            for display in display.iter_mut().take(height) {
-                for display in display.iter_mut().take(width) {
-                    *display = true;
+    for &operation in operations {
+        match operation {
+            Operation::Rect(width, height) => {
+                for display in display.iter_mut().take(height) {
+                    for display in display.iter_mut().take(width) {
+                        *display = true;
+                    }
                 }
             }
-        } else if words[0] == "rotate" {
-            let row = words[1] == "row";
-            let pos: usize = words[2].split('=').nth(1).unwrap().parse().unwrap();
-            let by: usize = words[4].parse().unwrap();
-            for _ in 0..by {
-                if row {
+            Operation::RotateRow(pos, by) => {
+                for _ in 0..by {
                     let tmp = display[pos][49];
                     for i in (1..50).rev() {
                         display[pos][i] = display[pos][i - 1];
                     }
                     display[pos][0] = tmp;
-                } else {
+                }
+            }
+            Operation::RotateColumn(pos, by) => {
+                for _ in 0..by {
                     let tmp = display[5][pos];
                     for i in (1..6).rev() {
                         display[i][pos] = display[i - 1][pos];
@@ -84,19 +140,7 @@ pub fn part2(input: &str) -> String {
         }
     }
 
-    let mut result = display
-        .iter()
-        .map(|bools| {
-            bools
-                .iter()
-                .map(|flag| if *flag { "#" } else { "." })
-                .collect::<String>()
-        })
-        .join("\n");
-
-    result.insert(0, '\n');
-
-    result
+    display
 }
 
 #[cfg(test)]
@@ -104,12 +148,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 123);
+        let input = crate::input::for_test(2016, 8);
+        assert_eq!(part1(&parse(&input).unwrap()), 123);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), "\n.##..####.###..#..#.###..####.###....##.###...###.\n#..#.#....#..#.#..#.#..#....#.#..#....#.#..#.#....\n#..#.###..###..#..#.#..#...#..###.....#.#..#.#....\n####.#....#..#.#..#.###...#...#..#....#.###...##..\n#..#.#....#..#.#..#.#....#....#..#.#..#.#.......#.\n#..#.#....###...##..#....####.###...##..#....###..");
+        let input = crate::input::for_test(2016, 8);
+        assert_eq!(part2(&parse(&input).unwrap()), "\n.##..####.###..#..#.###..####.###....##.###...###.\n#..#.#....#..#.#..#.#..#....#.#..#....#.#..#.#....\n#..#.###..###..#..#.#..#...#..###.....#.#..#.#....\n####.#....#..#.#..#.###...#...#..#....#.###...##..\n#..#.#....#..#.#..#.#....#....#..#.#..#.#.......#.\n#..#.#....###...##..#....####.###...##..#....###..");
     }
 }
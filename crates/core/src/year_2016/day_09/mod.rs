@@ -1,70 +1,126 @@
-use anyhow::anyhow;
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
 
 pub const TITLE: &str = "Explosives in Cyberspace";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 9;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Compressed;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
+/// Returns an error if a marker doesn't parse or reaches past the end of the file
+pub fn parse(input: &str) -> Result<Compressed, SolveError> {
+    compressed(input, input.trim())
+}
 
 #[must_use]
-/// # Panics
-/// Panics if input is empty
-pub fn part1(input: &str) -> u64 {
-    uncompressed_size(input.trim().as_bytes(), false).unwrap()
+pub fn part1(file: &Compressed) -> u64 {
+    file.uncompressed_size(false)
 }
 
 #[must_use]
-/// # Panics
-/// Panics if input is empty
-pub fn part2(input: &str) -> u64 {
-    uncompressed_size(input.trim().as_bytes(), true).unwrap()
+pub fn part2(file: &Compressed) -> u64 {
+    file.uncompressed_size(true)
+}
+
+/// A compressed file: the characters outside any marker, and the markers.
+#[derive(Debug)]
+pub struct Compressed {
+    plain: u64,
+    markers: Vec<Marker>,
+}
+
+/// A marker, along with the section it repeats parsed as a file of its own.
+#[derive(Debug)]
+struct Marker {
+    len: u64,
+    repetitions: u64,
+    section: Compressed,
+}
+
+impl Compressed {
+    fn uncompressed_size(&self, recursive: bool) -> u64 {
+        self.plain
+            + self
+                .markers
+                .iter()
+                .map(|marker| {
+                    marker.repetitions
+                        * if recursive {
+                            marker.section.uncompressed_size(true)
+                        } else {
+                            marker.len
+                        }
+                })
+                .sum::<u64>()
+    }
 }
 
-fn uncompressed_size(text: &[u8], recursive: bool) -> anyhow::Result<u64> {
-    let error_mapper_uf8 = |_| anyhow!("Invalid input");
-    let error_mapper_parse = |_| anyhow!("Invalid input");
+fn compressed(input: &str, text: &str) -> Result<Compressed, SolveError> {
     let mut start_parenthesis_idx = None;
-    let mut uncompressed_len = 0;
+    let mut file = Compressed {
+        plain: 0,
+        markers: Vec::new(),
+    };
 
     let mut i = 0;
     while i < text.len() {
-        let c = text[i];
+        let c = text.as_bytes()[i];
         if c == b'(' {
             start_parenthesis_idx = Some(i);
         } else if c == b')' {
             if let Some(from) = start_parenthesis_idx {
                 let inside_parenthesis = &text[from + 1..i];
-                let parts = inside_parenthesis
-                    .split(|&c| c == b'x')
-                    .collect::<Vec<&[u8]>>();
-                if parts.len() != 2 {
-                    return Err(anyhow!("Invalid input"));
-                }
-                let chars_to_take = std::str::from_utf8(parts[0])
-                    .map_err(error_mapper_uf8)?
-                    .parse::<u64>()
-                    .map_err(error_mapper_parse)?;
-                let repetitions = std::str::from_utf8(parts[1])
-                    .map_err(error_mapper_uf8)?
-                    .parse::<u64>()
-                    .map_err(error_mapper_parse)?;
-                uncompressed_len += repetitions
-                    * if recursive {
-                        uncompressed_size(
-                            &text[i + 1..i + 1 + usize::try_from(chars_to_take).unwrap()],
-                            true,
-                        )?
-                    } else {
-                        chars_to_take
-                    };
-                i += usize::try_from(chars_to_take).unwrap();
+                let (chars_to_take, repetitions) = inside_parenthesis
+                    .split_once('x')
+                    .and_then(|(chars, times)| {
+                        Some((chars.parse::<usize>().ok()?, times.parse::<u64>().ok()?))
+                    })
+                    .or_malformed(input, inside_parenthesis, "expected an `AxB` marker")?;
+                let repeated = text.get(i + 1..i + 1 + chars_to_take).or_malformed(
+                    input,
+                    &text[i..],
+                    "marker reaches past the end of the file",
+                )?;
+                file.markers.push(Marker {
+                    len: chars_to_take as u64,
+                    repetitions,
+                    section: compressed(input, repeated)?,
+                });
+                i += chars_to_take;
                 start_parenthesis_idx = None;
             }
         } else if start_parenthesis_idx.is_none() {
-            uncompressed_len += 1;
+            file.plain += 1;
         }
         i += 1;
     }
 
-    Ok(uncompressed_len)
+    Ok(file)
 }
 
 #[cfg(test)]
@@ -72,12 +128,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 74532);
+        let input = crate::input::for_test(2016, 9);
+        assert_eq!(part1(&parse(&input).unwrap()), 74532);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 11_558_231_665);
+        let input = crate::input::for_test(2016, 9);
+        assert_eq!(part2(&parse(&input).unwrap()), 11_558_231_665);
     }
 }
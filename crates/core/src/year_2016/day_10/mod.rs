@@ -1,14 +1,41 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use anyhow::Result;
-
 use itertools::Itertools;
 use sscanf::scanf;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Balance Bots";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 10;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Factory;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
 
 #[derive(Debug)]
 enum Target {
@@ -17,11 +44,11 @@ enum Target {
 }
 
 impl Target {
-    fn new(s: &str, i: i32) -> Self {
+    fn new(s: &str, i: i32) -> Option<Self> {
         match s {
-            "bot" => Self::Bot(i),
-            "output" => Self::Output(i),
-            _ => panic!(),
+            "bot" => Some(Self::Bot(i)),
+            "output" => Some(Self::Output(i)),
+            _ => None,
         }
     }
 }
@@ -41,50 +68,85 @@ impl FromStr for Bot {
             s,
             "bot {i32} gives low to {str} {i32} and high to {str} {i32}"
         )
-        .unwrap();
+        .map_err(|_| ())?;
 
         Ok(Self {
             recieved_chip,
-            lo: Target::new(target_lo, lo),
-            hi: Target::new(target_hi, hi),
+            lo: Target::new(target_lo, lo).ok_or(())?,
+            hi: Target::new(target_hi, hi).ok_or(())?,
         })
     }
 }
 
-fn get_instructions(input: &str) -> HashMap<i32, Bot> {
+fn get_instructions(input: &str) -> Result<HashMap<i32, Bot>, SolveError> {
     input
         .lines()
         .filter(|l| l.starts_with("bot"))
-        .map(|l| l.parse::<Bot>().unwrap())
-        .map(|i| (i.recieved_chip, i))
-        .collect::<HashMap<_, _>>()
+        .map(|l| {
+            l.parse::<Bot>()
+                .ok()
+                .or_malformed(input, l, "expected a bot instruction")
+        })
+        .map_ok(|i| (i.recieved_chip, i))
+        .collect()
 }
 
-fn get_initial_state(input: &str) -> HashMap<i32, Vec<i32>> {
-    input
+fn get_initial_state(input: &str) -> Result<HashMap<i32, Vec<i32>>, SolveError> {
+    Ok(input
         .lines()
         .filter(|l| l.starts_with("value"))
-        .filter_map(|l| scanf!(l, "value {i32} goes to bot {i32}").ok())
+        .map(|l| {
+            scanf!(l, "value {i32} goes to bot {i32}")
+                .ok()
+                .or_malformed(input, l, "expected `value X goes to bot Y`")
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
         .map(|(value, bot)| (bot, value))
-        .into_group_map()
+        .into_group_map())
 }
 
-/// # Panics
+/// The bots' instructions, and the chips each bot starts out with.
+#[derive(Debug)]
+pub struct Factory {
+    instructions: HashMap<i32, Bot>,
+    initial_state: HashMap<i32, Vec<i32>>,
+}
+
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part1(input: &str) -> i32 {
-    let instructions = get_instructions(input);
-    let mut state = get_initial_state(input);
+/// Returns an error if unable to parse input
+pub fn parse(input: &str) -> Result<Factory, SolveError> {
+    Ok(Factory {
+        instructions: get_instructions(input)?,
+        initial_state: get_initial_state(input)?,
+    })
+}
+
+fn instruction(instructions: &HashMap<i32, Bot>, bot: i32) -> Result<&Bot, SolveError> {
+    instructions.get(&bot).ok_or(SolveError::no_solution(
+        "a bot holding two chips has no instruction",
+    ))
+}
+
+/// # Errors
+///
+/// Returns an error if no bot ever compares chips 17 and 61
+pub fn part1(factory: &Factory) -> Result<i32, SolveError> {
+    let instructions = &factory.instructions;
+    let mut state = factory.initial_state.clone();
     loop {
         let current = state.clone();
+        if current.values().all(|v| v.len() != 2) {
+            return Err(SolveError::no_solution("no bot compares chips 17 and 61"));
+        }
         // For every bot with 2 microchips, give it to the bot next in line based on its instruction.
         for (bot, microchips) in current.into_iter().filter(|(_, v)| v.len() == 2) {
             let (lo, hi) = (
                 microchips[0].min(microchips[1]),
                 microchips[0].max(microchips[1]),
             );
-            let instruction = &instructions[&bot];
+            let instruction = instruction(instructions, bot)?;
             state.remove(&bot);
             // Ignore output bins.
             if let Target::Bot(bot) = instruction.lo {
@@ -94,29 +156,33 @@ pub fn part1(input: &str) -> i32 {
                 state.entry(bot).or_default().push(hi);
             }
             if lo == 17 && hi == 61 {
-                return bot;
+                return Ok(bot);
             }
         }
     }
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part2(input: &str) -> i32 {
-    let instructions = get_instructions(input);
-    let mut state = get_initial_state(input);
+/// Returns an error if outputs 0, 1 and 2 don't all get a chip
+pub fn part2(factory: &Factory) -> Result<i32, SolveError> {
+    let instructions = &factory.instructions;
+    let mut state = factory.initial_state.clone();
     let mut bins = HashMap::new();
     while !bins.contains_key(&0) || !bins.contains_key(&1) || !bins.contains_key(&2) {
         let current = state.clone();
+        if current.values().all(|v| v.len() != 2) {
+            return Err(SolveError::no_solution(
+                "outputs 0, 1 and 2 never all get a chip",
+            ));
+        }
         // For every bot with 2 microchips, give it to the bot next in line based on its instruction.
         for (bot, microchips) in current.into_iter().filter(|(_, v)| v.len() == 2) {
             let (lo, hi) = (
                 microchips[0].min(microchips[1]),
                 microchips[0].max(microchips[1]),
             );
-            let instruction = &instructions[&bot];
+            let instruction = instruction(instructions, bot)?;
             state.remove(&bot);
             match instruction.lo {
                 Target::Bot(bot) => state.entry(bot).or_default().push(lo),
@@ -132,7 +198,7 @@ pub fn part2(input: &str) -> i32 {
             }
         }
     }
-    bins[&0] * bins[&1] * bins[&2]
+    Ok(bins[&0] * bins[&1] * bins[&2])
 }
 
 #[cfg(test)]
@@ -140,12 +206,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 56);
+        let input = crate::input::for_test(2016, 10);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 56);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 7847);
+        let input = crate::input::for_test(2016, 10);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 7847);
     }
 }
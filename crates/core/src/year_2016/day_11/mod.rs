@@ -1,16 +1,44 @@
-use anyhow::anyhow;
-use anyhow::Result;
+use crate::{
+    answer::Answer,
+    error::SolveError,
+    input::embedded,
+    shared::{parse::OrMalformed, rng::Rng, search},
+    solution::Solution,
+};
 
 pub const TITLE: &str = "Radioisotope Thermoelectric Generators";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 11;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Facility;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
 
 use std::cmp::Ordering;
-use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
-#[derive(Clone, Default, Eq, PartialEq, Hash, Copy)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Copy)]
 struct Floor {
     generators: u8,
     microchips: u8,
@@ -41,8 +69,8 @@ impl Floor {
         !(contains_generator && contains_unshielded_microchip)
     }
 
-    const fn count_items(self) -> u32 {
-        self.generators.count_ones() + self.microchips.count_ones()
+    const fn count_items(self) -> usize {
+        (self.generators.count_ones() + self.microchips.count_ones()) as usize
     }
 }
 
@@ -99,19 +127,39 @@ impl PartialOrd for State {
     }
 }
 
-fn parse_input(input: &str, part2: bool) -> Result<[Floor; 4]> {
+/// The items on each floor, and how many isotopes they're made of.
+#[derive(Debug)]
+pub struct Facility {
+    floors: [Floor; 4],
+    isotopes: u8,
+}
+
+/// # Errors
+///
+/// Returns an error if `input` is malformed.
+pub fn parse(input: &str) -> Result<Facility, SolveError> {
     let mut name_to_id = HashMap::new();
     let mut current_id = 0_u8;
     let mut initial_floors = [Floor::default(); 4];
 
     for (floor_idx, line) in input.lines().enumerate() {
+        if floor_idx == 4 {
+            return Err(SolveError::malformed(
+                input,
+                line,
+                "expected only four floors",
+            ));
+        }
         let words = line.split(' ').collect::<Vec<_>>();
         for (word_idx, &word) in words.iter().enumerate() {
             let (isotope_name, microchip) = if word_idx > 0 && word.starts_with("microchip") {
                 let isotope_name = words[word_idx - 1]
                     .strip_suffix("-compatible")
-                    .ok_or("Invalid syntax - not $ISOTYPE-compatible before 'microchip'")
-                    .map_err(|error| anyhow!(error))?;
+                    .or_malformed(
+                        input,
+                        words[word_idx - 1],
+                        "expected `<isotope>-compatible`",
+                    )?;
                 (isotope_name, true)
             } else if word_idx > 0 && word.starts_with("generator") {
                 let isotope_name = words[word_idx - 1];
@@ -127,7 +175,11 @@ fn parse_input(input: &str, part2: bool) -> Result<[Floor; 4]> {
                     current_id - 1
                 });
             if isotope_id == 6 {
-                return Err(anyhow!("Too many isotopes - max supported is 5"));
+                return Err(SolveError::malformed(
+                    input,
+                    isotope_name,
+                    "too many isotopes - max supported is 5",
+                ));
             }
             let bit_mask = 1 << isotope_id;
 
@@ -139,150 +191,259 @@ fn parse_input(input: &str, part2: bool) -> Result<[Floor; 4]> {
         }
     }
 
-    if part2 {
-        let elerium_id = current_id + 1;
-        let dilithium_id = current_id + 2;
-        initial_floors[0].add_item(true, elerium_id);
-        initial_floors[0].add_item(false, elerium_id);
-        initial_floors[0].add_item(true, dilithium_id);
-        initial_floors[0].add_item(false, dilithium_id);
-    }
-
-    Ok(initial_floors)
+    Ok(Facility {
+        floors: initial_floors,
+        isotopes: current_id,
+    })
 }
 
-fn solve(input: &str, part2: bool) -> Result<u32> {
-    let initial_floors = parse_input(input, part2)?;
-    let mut to_visit = BinaryHeap::new();
-    let mut visited_states = HashSet::new();
-
-    let initial_state = State {
-        // "When you enter the containment area, you and the elevator will start on the first floor":
+/// The state everything starts in: "When you enter the containment area, you
+/// and the elevator will start on the first floor".
+const fn initial_state(floors: [Floor; 4]) -> State {
+    State {
         current_floor: 0,
-        floors: initial_floors,
-    };
+        floors,
+    }
+}
 
-    to_visit.push(Reverse((0, 0, initial_state.clone())));
-    visited_states.insert(initial_state);
+/// Whether everything is on the fourth floor.
+fn is_done(state: &State) -> bool {
+    state
+        .floors
+        .iter()
+        .take(3)
+        .all(|floor| floor.count_items() == 0)
+}
 
-    while let Some(Reverse((_, visited_state_cost, visited_state))) = to_visit.pop() {
-        if visited_state
-            .floors
-            .iter()
-            .take(3)
-            .all(|floor| floor.count_items() == 0)
+/// Every state one move of the elevator, with one or two items, away from
+/// `state`, that leaves no microchip fried.
+fn moves(state: &State) -> Vec<State> {
+    let mut moves = Vec::new();
+
+    for direction in [-1, 1] {
+        let new_floor = state.current_floor + direction;
+        if !(0..=3).contains(&new_floor) {
+            continue;
+        }
+        if direction == -1
+            && state
+                .floors
+                .iter()
+                .take(state.current_floor as usize)
+                .all(|floor| floor.count_items() == 0)
         {
-            // If floor 0-3 is empty we're done.
-            return Ok(visited_state_cost);
+            // Do not bring anything down if every floor beneath current is empty.
+            continue;
         }
 
-        for direction in [-1, 1] {
-            let new_floor = visited_state.current_floor + direction;
-            if !(0..=3).contains(&new_floor) {
-                continue;
-            }
-            if direction == -1
-                && visited_state
-                    .floors
-                    .iter()
-                    .take(visited_state.current_floor as usize)
-                    .all(|floor| floor.count_items() == 0)
-            {
-                // Do not bring anything down if every floor beneath current is empty.
-                continue;
-            }
-
-            let current_floor = visited_state.floors[visited_state.current_floor as usize];
-            for first_moved_is_chip in [true, false] {
-                for first_offset in 0..8 {
-                    let contains_first_item = if first_moved_is_chip {
-                        current_floor.microchips
-                    } else {
-                        current_floor.generators
-                    } & (1 << first_offset)
-                        != 0;
-                    if !contains_first_item {
-                        continue;
-                    }
+        let current_floor = state.floors[state.current_floor as usize];
+        for first_moved_is_chip in [true, false] {
+            for first_offset in 0..8 {
+                let contains_first_item = if first_moved_is_chip {
+                    current_floor.microchips
+                } else {
+                    current_floor.generators
+                } & (1 << first_offset)
+                    != 0;
+                if !contains_first_item {
+                    continue;
+                }
 
-                    for &second_moved_is_chip in if first_moved_is_chip {
-                        [true, false].iter()
+                for &second_moved_is_chip in if first_moved_is_chip {
+                    [true, false].iter()
+                } else {
+                    [false].iter()
+                } {
+                    for second_offset in 0..=(if first_moved_is_chip == second_moved_is_chip {
+                        first_offset
                     } else {
-                        [false].iter()
-                    } {
-                        for second_offset in 0..=(if first_moved_is_chip == second_moved_is_chip {
-                            first_offset
+                        7
+                    }) {
+                        let contains_second_item = if second_moved_is_chip {
+                            current_floor.microchips
                         } else {
-                            7
-                        }) {
-                            let contains_second_item = if second_moved_is_chip {
-                                current_floor.microchips
-                            } else {
-                                current_floor.generators
-                            } & (1 << second_offset)
-                                != 0;
-                            if !contains_second_item {
-                                continue;
-                            }
-
-                            let mut new_floors = visited_state.floors;
-
-                            new_floors[visited_state.current_floor as usize]
-                                .remove_item(first_moved_is_chip, first_offset);
+                            current_floor.generators
+                        } & (1 << second_offset)
+                            != 0;
+                        if !contains_second_item {
+                            continue;
+                        }
+
+                        let mut new_floors = state.floors;
+
+                        new_floors[state.current_floor as usize]
+                            .remove_item(first_moved_is_chip, first_offset);
+                        new_floors[new_floor as usize].add_item(first_moved_is_chip, first_offset);
+
+                        if (first_moved_is_chip, first_offset)
+                            != (second_moved_is_chip, second_offset)
+                        {
+                            new_floors[state.current_floor as usize]
+                                .remove_item(second_moved_is_chip, second_offset);
                             new_floors[new_floor as usize]
-                                .add_item(first_moved_is_chip, first_offset);
-
-                            if (first_moved_is_chip, first_offset)
-                                != (second_moved_is_chip, second_offset)
-                            {
-                                new_floors[visited_state.current_floor as usize]
-                                    .remove_item(second_moved_is_chip, second_offset);
-                                new_floors[new_floor as usize]
-                                    .add_item(second_moved_is_chip, second_offset);
-                            }
-
-                            if !new_floors.iter().all(|&floor| floor.is_valid()) {
-                                continue;
-                            }
-
-                            let new_cost = visited_state_cost + 1;
-                            let new_state = State {
-                                current_floor: new_floor,
-                                floors: new_floors,
-                            };
-
-                            let do_insert = visited_states.insert(new_state.clone());
-                            if do_insert {
-                                // Encourage moving things up:
-                                let heuristic = (new_state.floors[0].count_items() * 3) / 2
-                                    + new_state.floors[1].count_items()
-                                    + new_state.floors[2].count_items() / 2;
-                                to_visit.push(Reverse((new_cost + heuristic, new_cost, new_state)));
-                            }
+                                .add_item(second_moved_is_chip, second_offset);
                         }
+
+                        if !new_floors.iter().all(|&floor| floor.is_valid()) {
+                            continue;
+                        }
+
+                        moves.push(State {
+                            current_floor: new_floor,
+                            floors: new_floors,
+                        });
                     }
                 }
             }
         }
     }
 
-    Err(anyhow!("No solution found"))
+    moves
+}
+
+const NO_SOLUTION: SolveError =
+    SolveError::no_solution("no sequence of moves brings everything to the fourth floor");
+
+/// The fewest moves bringing everything up, searching the states with the
+/// most items already up first.
+fn best_first(initial_floors: [Floor; 4]) -> Result<usize, SolveError> {
+    search::astar(
+        initial_state(initial_floors),
+        |state| moves(state).into_iter().map(|new_state| (new_state, 1)),
+        |state| {
+            // A move takes at most two items up a floor, so it takes at least
+            // half as many moves as the floors the items have left to go up.
+            // That never overestimates, which the search relies on.
+            (state.floors[0].count_items() * 3
+                + state.floors[1].count_items() * 2
+                + state.floors[2].count_items())
+            .div_ceil(2)
+        },
+        is_done,
+    )
+    .map(|path| path.cost)
+    .ok_or(NO_SOLUTION)
+}
+
+/// The fewest moves bringing everything up, searching the states in the
+/// order of how many moves they are away.
+fn breadth_first(initial_floors: [Floor; 4]) -> Result<usize, SolveError> {
+    search::bfs(initial_state(initial_floors), moves, is_done)
+        .map(|path| path.cost)
+        .ok_or(NO_SOLUTION)
 }
 
-/// # Panics
+/// The first floor with the elerium and dilithium generators and microchips
+/// found there in part 2.
+fn with_extra_items(facility: &Facility) -> [Floor; 4] {
+    let mut floors = facility.floors;
+    let elerium_id = facility.isotopes + 1;
+    let dilithium_id = facility.isotopes + 2;
+    floors[0].add_item(true, elerium_id);
+    floors[0].add_item(false, elerium_id);
+    floors[0].add_item(true, dilithium_id);
+    floors[0].add_item(false, dilithium_id);
+    floors
+}
+
+/// # Errors
 ///
-/// Panics if `input` is empty.
-#[must_use]
-pub fn part1(input: &str) -> u32 {
-    solve(input, false).unwrap()
+/// Returns an error if the items can't all be moved up.
+pub fn part1(facility: &Facility) -> Result<usize, SolveError> {
+    best_first(facility.floors)
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if `input` is empty.
+/// Returns an error if the items can't all be moved up.
+pub fn part2(facility: &Facility) -> Result<usize, SolveError> {
+    best_first(with_extra_items(facility))
+}
+
+/// Generates a facility of two to four isotopes shaped like the puzzle inputs.
+///
+/// Each generator is on one of the first three floors, with its microchip on
+/// the same floor or the one above. There is something on the first floor for
+/// the elevator to carry, and no microchip starts out fried, even once part 2
+/// adds more generators to the first floor.
 #[must_use]
-pub fn part2(input: &str) -> u32 {
-    solve(input, true).unwrap()
+pub fn generate(seed: u64) -> String {
+    const ISOTOPES: [&str; 4] = ["hydrogen", "lithium", "cobalt", "curium"];
+    const FLOORS: [&str; 4] = ["first", "second", "third", "fourth"];
+
+    let mut rng = Rng::new(seed);
+    let isotopes = &ISOTOPES[..2 + rng.below(3)];
+    let (generators, microchips) = loop {
+        let generators = isotopes
+            .iter()
+            .map(|_| rng.below(FLOORS.len() - 1))
+            .collect::<Vec<_>>();
+        let microchips = generators
+            .iter()
+            .map(|&floor| (floor + rng.below(2)).min(FLOORS.len() - 2))
+            .collect::<Vec<_>>();
+        let fried = |(isotope, &floor): (usize, &usize)| {
+            generators[isotope] != floor && generators.contains(&floor)
+        };
+        if generators.contains(&0) && !microchips.iter().enumerate().any(fried) {
+            break (generators, microchips);
+        }
+    };
+
+    FLOORS
+        .iter()
+        .enumerate()
+        .map(|(floor, name)| {
+            let items = isotopes
+                .iter()
+                .enumerate()
+                .flat_map(|(isotope, element)| {
+                    [
+                        (generators[isotope] == floor).then(|| format!("a {element} generator")),
+                        (microchips[isotope] == floor)
+                            .then(|| format!("a {element}-compatible microchip")),
+                    ]
+                })
+                .flatten()
+                .collect::<Vec<_>>();
+            match items.as_slice() {
+                [] => format!("The {name} floor contains nothing relevant.\n"),
+                [item] => format!("The {name} floor contains {item}.\n"),
+                [items @ .., last] => {
+                    format!(
+                        "The {name} floor contains {}, and {last}.\n",
+                        items.join(", ")
+                    )
+                }
+            }
+        })
+        .collect()
+}
+
+/// Solves both parts with a plain breadth-first search, to check the
+/// best-first search of [`Puzzle`] against.
+#[derive(Debug)]
+pub struct BreadthFirst;
+
+impl Solution for BreadthFirst {
+    const YEAR: u16 = Puzzle::YEAR;
+    const DAY: u8 = Puzzle::DAY;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Facility;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        breadth_first(parsed.floors).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        breadth_first(with_extra_items(parsed)).map(Answer::from)
+    }
 }
 
 #[cfg(test)]
@@ -290,12 +451,37 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 47);
+        let input = crate::input::for_test(2016, 11);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 47);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 71);
+        let input = crate::input::for_test(2016, 11);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 71);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = "The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.\n\
+            The second floor contains a hydrogen generator.\n\
+            The third floor contains a lithium generator.\n\
+            The fourth floor contains nothing relevant.\n";
+        let facility = parse(input).unwrap();
+        assert_eq!(best_first(facility.floors), Ok(11));
+        assert_eq!(breadth_first(facility.floors), Ok(11));
+
+        for seed in 0..20 {
+            let facility = parse(&generate(seed)).unwrap();
+            assert!(facility.floors.iter().all(|floor| floor.is_valid()));
+            assert_eq!(
+                best_first(facility.floors),
+                breadth_first(facility.floors),
+                "seed {seed}"
+            );
+        }
     }
 }
@@ -1,11 +1,39 @@
 use std::collections::HashMap;
 
 use algoritmer::hashmap;
-use itertools::Itertools;
+
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
 
 pub const TITLE: &str = "Leonardo's Monorail";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 12;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Instruction<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
 #[derive(Debug, Default, Clone)]
 struct Computer {
@@ -24,6 +52,13 @@ impl Computer {
         }
     }
 
+    fn value(&self, operand: Operand) -> isize {
+        match operand {
+            Operand::Value(value) => value,
+            Operand::Register(register) => self.registers[register],
+        }
+    }
+
     fn copy_value(&mut self, value: isize, register: &str) {
         self.registers
             .entry(register.to_string())
@@ -43,97 +78,95 @@ impl Computer {
             .and_modify(|inner| *inner += 1)
             .or_default();
     }
-}
 
-#[derive(Debug)]
-struct Solver<'s> {
-    computer: &'s mut Computer,
-    instructions: Vec<&'s str>,
-}
-
-impl<'s> Solver<'s> {
-    fn new(computer: &'s mut Computer, instructions: Vec<&'s str>) -> Self {
-        Self {
-            computer,
-            instructions,
-        }
-    }
-
-    fn solve(&mut self) {
+    fn run(&mut self, instructions: &[Instruction]) {
         let mut i: isize = 0;
 
-        while i < self.instructions.len() as isize {
-            let components = self.instructions[i as usize].split(' ').collect::<Vec<_>>();
-
-            if self.instructions[i as usize].starts_with("cpy") {
-                if let Ok(value) = components[1].parse::<isize>() {
-                    self.computer.copy_value(value, components[2]);
-                } else {
-                    let value = self.computer.registers[components[1]];
-                    self.computer.copy_value(value, components[2]);
-                }
-            } else if self.instructions[i as usize].starts_with("jnz") {
-                let value = components
-                    .get(1)
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or_else(|| {
-                        self.computer
-                            .registers
-                            .get(components[1])
-                            .copied()
-                            .unwrap_or_default()
-                    });
-                if value != 0 {
-                    let jump = components[2].parse::<isize>().unwrap();
-                    i += jump;
-                    continue;
+        while i < instructions.len() as isize {
+            match instructions[i as usize] {
+                Instruction::Copy(from, to) => self.copy_value(self.value(from), to),
+                Instruction::JumpIfNotZero(check, jump) => {
+                    if self.value(check) != 0 {
+                        i += jump;
+                        continue;
+                    }
                 }
-            } else if self.instructions[i as usize].starts_with("inc") {
-                self.computer.increment(components[1]);
-            } else if self.instructions[i as usize].starts_with("dec") {
-                self.computer.decrement(components[1]);
-            } else {
-                panic!("illegal instruction: {}", self.instructions[i as usize]);
+                Instruction::Increment(register) => self.increment(register),
+                Instruction::Decrement(register) => self.decrement(register),
             }
             i += 1;
         }
     }
 }
 
-/// # Panics
+/// Either a number or the name of a register.
+#[derive(Debug, Clone, Copy)]
+pub enum Operand<'a> {
+    Value(isize),
+    Register(&'a str),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction<'a> {
+    Copy(Operand<'a>, &'a str),
+    JumpIfNotZero(Operand<'a>, isize),
+    Increment(&'a str),
+    Decrement(&'a str),
+}
+
+/// # Errors
 ///
-/// Panics if unable to parse input
+/// Returns an error if unable to parse input
+pub fn parse<'a>(input: &'a str) -> Result<Vec<Instruction<'a>>, SolveError> {
+    let register = |name: &'a str| {
+        Some(name)
+            .filter(|name| matches!(*name, "a" | "b" | "c" | "d"))
+            .or_malformed(input, name, "expected a register")
+    };
+    let operand = |operand: &'a str| {
+        operand.parse::<isize>().map_or_else(
+            |_| register(operand).map(Operand::Register),
+            |value| Ok(Operand::Value(value)),
+        )
+    };
+
+    input
+        .lines()
+        .map(|instruction| {
+            let components = instruction.split(' ').collect::<Vec<_>>();
+            match components.as_slice() {
+                ["cpy", from, to] => Ok(Instruction::Copy(operand(from)?, register(to)?)),
+                ["jnz", check, jump] => Ok(Instruction::JumpIfNotZero(
+                    operand(check)?,
+                    jump.parse()
+                        .or_malformed(input, jump, "expected a jump offset")?,
+                )),
+                ["inc", name] => Ok(Instruction::Increment(register(name)?)),
+                ["dec", name] => Ok(Instruction::Decrement(register(name)?)),
+                _ => Err(SolveError::malformed(
+                    input,
+                    instruction,
+                    "illegal instruction",
+                )),
+            }
+        })
+        .collect()
+}
+
 #[must_use]
-pub fn part1(input: &str) -> isize {
+pub fn part1(instructions: &[Instruction]) -> isize {
     let mut computer = Computer::new(0, 0, 0, 0);
-    Solver::new(&mut computer, input.lines().collect_vec()).solve();
-
-    computer
-        .registers
-        .into_iter()
-        .sorted()
-        .collect::<Vec<_>>()
-        .first()
-        .unwrap()
-        .1
+    computer.run(instructions);
+
+    computer.registers["a"]
 }
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part2(input: &str) -> isize {
+pub fn part2(instructions: &[Instruction]) -> isize {
     let mut computer = Computer::new(0, 0, 1, 0);
-    Solver::new(&mut computer, input.lines().collect_vec()).solve();
-
-    computer
-        .registers
-        .into_iter()
-        .sorted()
-        .collect::<Vec<_>>()
-        .first()
-        .unwrap()
-        .1
+    computer.run(instructions);
+
+    computer.registers["a"]
 }
 
 #[cfg(test)]
@@ -141,12 +174,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 318_077);
+        let input = crate::input::for_test(2016, 12);
+        assert_eq!(part1(&parse(&input).unwrap()), 318_077);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 9_227_731);
+        let input = crate::input::for_test(2016, 12);
+        assert_eq!(part2(&parse(&input).unwrap()), 9_227_731);
     }
 }
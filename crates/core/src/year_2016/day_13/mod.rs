@@ -1,33 +1,75 @@
-use std::collections::{HashMap, HashSet};
-
-use itertools::Itertools;
+use crate::{
+    answer::Answer,
+    error::SolveError,
+    input::embedded,
+    shared::{parse::OrMalformed, search},
+    solution::Solution,
+};
 
 pub const TITLE: &str = "A Maze of Twisty Little Cubicles";
 
-pub const INPUT: &str = "1362";
+pub const INPUT: Option<&str> = embedded!("1362");
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 13;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = isize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(*parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(*parsed).into())
+    }
+}
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part1(input: &str) -> isize {
+/// Returns an error if the target can't be reached
+pub fn part1(favourite_number: isize) -> Result<isize, SolveError> {
     let target = Location { x: 31, y: 39 };
 
-    let distance = dijkstra(input, Location { x: 1, y: 1 }, Some(target), isize::MAX);
+    let path = search::bfs(
+        Location { x: 1, y: 1 },
+        |location| location.neighbours(favourite_number),
+        |&location| location == target,
+    )
+    .ok_or(SolveError::no_solution("the target can't be reached"))?;
 
-    distance[&target]
+    Ok(path.cost as isize)
 }
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part2(input: &str) -> usize {
+pub fn part2(favourite_number: isize) -> usize {
     let max_distance = 50;
 
-    let distance = dijkstra(input, Location { x: 1, y: 1 }, None, max_distance);
+    search::reachable_within(Location { x: 1, y: 1 }, max_distance, |location| {
+        location.neighbours(favourite_number)
+    })
+    .len()
+}
 
-    distance.len()
+/// # Errors
+///
+/// Returns an error if unable to parse input
+pub fn parse(input: &str) -> Result<isize, SolveError> {
+    let number = input.trim();
+    number.parse().or_malformed(
+        input,
+        number,
+        "expected the office designer's favourite number",
+    )
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -65,10 +107,10 @@ impl Location {
         }
     }
 
-    fn is_wall(&self, input: &str) -> bool {
+    fn is_wall(&self, favourite_number: isize) -> bool {
         let multiplied =
             (self.x * self.x) + (3 * self.x) + (2 * self.x * self.y) + self.y + (self.y * self.y);
-        let sum = multiplied + input.parse::<isize>().unwrap();
+        let sum = multiplied + favourite_number;
 
         let as_bit_string = format!("{sum:b}");
         let count_ones = as_bit_string
@@ -82,73 +124,13 @@ impl Location {
     const fn within_bounds(&self) -> bool {
         self.x >= 0 && self.y >= 0
     }
-}
 
-fn dijkstra(
-    input: &str,
-    source: Location,
-    target: Option<Location>,
-    max_distance: isize,
-) -> HashMap<Location, isize> {
-    let neighbors_of = |location: Location| {
-        [
-            location.up(),
-            location.down(),
-            location.left(),
-            location.right(),
-        ]
-        .iter()
-        .filter(|location| location.within_bounds() && !location.is_wall(input))
-        .copied()
-        .collect_vec()
-    };
-
-    let mut distance = HashMap::new();
-    let mut previous = HashMap::new();
-    let mut unvisited = HashSet::new();
-    let mut visited = HashSet::new();
-
-    distance.entry(source).insert_entry(0);
-    unvisited.insert(source);
-
-    while !unvisited.is_empty() {
-        let current = *distance
-            .iter()
-            .filter(|(key, _)| unvisited.contains(key))
-            .sorted_by(|a, b| a.1.cmp(b.1))
-            .next()
-            .unwrap()
-            .0;
-
-        if target.is_some_and(|value| value == current) {
-            break;
-        }
-
-        visited.insert(unvisited.take(&current).unwrap());
-
-        if distance[&current] == max_distance {
-            continue;
-        }
-
-        for neighbor in neighbors_of(current) {
-            if !visited.contains(&neighbor) {
-                unvisited.insert(neighbor);
-            }
-
-            let current_distance = distance[&current];
-
-            if let Some(neighbor_distance) = distance.get(&neighbor).copied() {
-                if current_distance + 1 < neighbor_distance {
-                    distance.entry(neighbor).insert_entry(current_distance + 1);
-                    previous.entry(neighbor).insert_entry(current);
-                }
-            } else {
-                distance.entry(neighbor).insert_entry(current_distance + 1);
-                previous.entry(neighbor).insert_entry(current);
-            }
-        }
+    /// The open locations next to this one.
+    fn neighbours(self, favourite_number: isize) -> impl Iterator<Item = Self> {
+        [self.up(), self.down(), self.left(), self.right()]
+            .into_iter()
+            .filter(move |location| location.within_bounds() && !location.is_wall(favourite_number))
     }
-    distance
 }
 
 #[cfg(test)]
@@ -156,12 +138,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 82);
+        let input = crate::input::for_test(2016, 13);
+        assert_eq!(part1(parse(&input).unwrap()).unwrap(), 82);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 138);
+        let input = crate::input::for_test(2016, 13);
+        assert_eq!(part2(parse(&input).unwrap()), 138);
     }
 }
@@ -1,15 +1,49 @@
 use md5::{Digest, Md5};
 
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
+
 pub const TITLE: &str = "One-Time Pad";
 
-pub const INPUT: &str = "ihaygndm";
+pub const INPUT: Option<&str> = embedded!("ihaygndm");
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 14;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
 
-/// # Panics
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+/// # Errors
 ///
-/// Panics if unable to parse input
+/// Returns an error if the salt is empty
+pub fn parse(input: &str) -> Result<&str, SolveError> {
+    let salt = input.trim();
+    if salt.is_empty() {
+        return Err(SolveError::malformed(input, salt, "expected a salt"));
+    }
+    Ok(salt)
+}
+
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    let mut finder = HashFinder::new(input.trim(), 1000, 0);
+pub fn part1(salt: &str) -> usize {
+    let mut finder = HashFinder::new(salt, 1000, 0);
     let mut index = 0usize;
     for _ in 0..64 {
         index = finder.next();
@@ -17,12 +51,9 @@ pub fn part1(input: &str) -> usize {
     index
 }
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part2(input: &str) -> usize {
-    let mut finder = HashFinder::new(input.trim(), 1000, 2016);
+pub fn part2(salt: &str) -> usize {
+    let mut finder = HashFinder::new(salt, 1000, 2016);
     let mut index = 0usize;
     for _ in 0..64 {
         index = finder.next();
@@ -151,12 +182,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 15035);
+        let input = crate::input::for_test(2016, 14);
+        assert_eq!(part1(parse(&input).unwrap()), 15035);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 19968);
+        let input = crate::input::for_test(2016, 14);
+        assert_eq!(part2(parse(&input).unwrap()), 19968);
     }
 }
@@ -1,27 +1,42 @@
 use regex::Regex;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Timing is Everything";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
-///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part1(input: &str) -> i32 {
-    let mut discs = Vec::new();
-    let re =
-        Regex::new(r"(?i)Disc #(\d+) has (\d+) positions; at time=0, it is at position (\d+)\.")
-            .unwrap();
+#[derive(Debug)]
+pub struct Puzzle;
 
-    for capture in re.captures_iter(input) {
-        let number_of_positions: usize = capture[2].parse().unwrap();
-        let position_at_time: usize = capture[3].parse().unwrap();
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 15;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
 
-        let disc = Disc::new(number_of_positions, position_at_time);
-        discs.push(disc);
+    type Parsed<'a> = Vec<Disc>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
     }
 
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
+
+#[must_use]
+pub fn part1(discs: &[Disc]) -> i32 {
+    let mut discs = discs.to_vec();
+
     let mut time = -1;
 
     while !discs.iter().all(|disc| disc.current_position == 0) {
@@ -35,23 +50,9 @@ pub fn part1(input: &str) -> i32 {
     time
 }
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part2(input: &str) -> i32 {
-    let mut discs = Vec::new();
-    let re =
-        Regex::new(r"(?i)Disc #(\d+) has (\d+) positions; at time=0, it is at position (\d+)\.")
-            .unwrap();
-
-    for capture in re.captures_iter(input) {
-        let number_of_positions: usize = capture[2].parse().unwrap();
-        let position_at_time: usize = capture[3].parse().unwrap();
-
-        let disc = Disc::new(number_of_positions, position_at_time);
-        discs.push(disc);
-    }
+pub fn part2(discs: &[Disc]) -> i32 {
+    let mut discs = discs.to_vec();
 
     discs.push(Disc::new(11, 0));
 
@@ -68,13 +69,50 @@ pub fn part2(input: &str) -> i32 {
     time
 }
 
-struct Disc {
+/// # Errors
+///
+/// Returns an error if unable to parse input
+///
+/// # Panics
+/// * Panics if an invalid pattern is given.
+pub fn parse(input: &str) -> Result<Vec<Disc>, SolveError> {
+    let re =
+        Regex::new(r"(?i)^Disc #(\d+) has (\d+) positions; at time=0, it is at position (\d+)\.$")
+            .unwrap();
+
+    input
+        .lines()
+        .map(|line| {
+            let capture =
+                re.captures(line)
+                    .or_malformed(input, line, "expected a disc description")?;
+            let number_of_positions = &capture[2];
+            let number_of_positions: usize = number_of_positions
+                .parse()
+                .ok()
+                .filter(|&positions| positions > 0)
+                .or_malformed(input, number_of_positions, "expected a number of positions")?;
+            let position_at_time = &capture[3];
+            let position_at_time: usize = position_at_time.parse().or_malformed(
+                input,
+                position_at_time,
+                "expected a position",
+            )?;
+
+            Ok(Disc::new(number_of_positions, position_at_time))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Disc {
     number_of_positions: usize,
     position_at_time: usize,
     current_position: usize,
 }
 
 impl Disc {
+    #[must_use]
     pub const fn new(number_of_positions: usize, position_at_time: usize) -> Self {
         let current_position = position_at_time;
 
@@ -95,12 +133,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 16824);
+        let input = crate::input::for_test(2016, 15);
+        assert_eq!(part1(&parse(&input).unwrap()), 16824);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 3_543_984);
+        let input = crate::input::for_test(2016, 15);
+        assert_eq!(part2(&parse(&input).unwrap()), 3_543_984);
     }
 }
@@ -1,20 +1,42 @@
 use itertools::Itertools;
 
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
+
 pub const TITLE: &str = "Dragon Checksum";
 
-pub const INPUT: &str = "10001001100000001";
+pub const INPUT: Option<&str> = embedded!("10001001100000001");
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 16;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<bool>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
 /// # Panics
 ///
-/// Panics if unable to parse input
+/// Panics if the checksum doesn't fit in a `usize`
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    let input = input
-        .chars()
-        .map(|character| character == '1')
-        .collect_vec();
-
-    input
+pub fn part1(state: &[bool]) -> usize {
+    state
+        .to_vec()
         .dragon_curve(272)
         .checksum()
         .iter()
@@ -26,15 +48,11 @@ pub fn part1(input: &str) -> usize {
 
 /// # Panics
 ///
-/// Panics if unable to parse input
+/// Panics if the checksum doesn't fit in a `usize`
 #[must_use]
-pub fn part2(input: &str) -> usize {
-    let input = input
-        .chars()
-        .map(|character| character == '1')
-        .collect_vec();
-
-    input
+pub fn part2(state: &[bool]) -> usize {
+    state
+        .to_vec()
         .dragon_curve(35_651_584)
         .checksum()
         .iter()
@@ -44,6 +62,32 @@ pub fn part2(input: &str) -> usize {
         .unwrap()
 }
 
+/// # Errors
+///
+/// Returns an error if the initial state isn't a string of `0`s and `1`s
+pub fn parse(input: &str) -> Result<Vec<bool>, SolveError> {
+    let state = input.trim();
+    if state.is_empty() {
+        return Err(SolveError::malformed(
+            input,
+            state,
+            "expected an initial state",
+        ));
+    }
+    state
+        .char_indices()
+        .map(|(i, character)| match character {
+            '0' => Ok(false),
+            '1' => Ok(true),
+            _ => Err(SolveError::malformed(
+                input,
+                &state[i..],
+                "expected `0` or `1`",
+            )),
+        })
+        .collect()
+}
+
 trait DragonCurve {
     fn dragon_curve(&self, length: usize) -> Vec<bool>;
     fn checksum(&self) -> Vec<bool>;
@@ -82,12 +126,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 10_101_001_010_100_001);
+        let input = crate::input::for_test(2016, 16);
+        assert_eq!(part1(&parse(&input).unwrap()), 10_101_001_010_100_001);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 10_100_001_110_101_001);
+        let input = crate::input::for_test(2016, 16);
+        assert_eq!(part2(&parse(&input).unwrap()), 10_100_001_110_101_001);
     }
 }
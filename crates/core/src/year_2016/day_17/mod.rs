@@ -3,24 +3,72 @@ use std::{collections::VecDeque, ops::Range, sync::LazyLock};
 use itertools::Itertools;
 use md5::{Digest, Md5};
 
+use crate::{
+    answer::Answer,
+    error::SolveError,
+    input::embedded,
+    shared::search::{self, Keep},
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Two Steps Forward";
 
-pub const INPUT: &str = "ioramepc";
+pub const INPUT: Option<&str> = embedded!("ioramepc");
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 17;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
+
+/// # Errors
+///
+/// Returns an error if the passcode is empty
+pub fn parse(input: &str) -> Result<&str, SolveError> {
+    let passcode = input.trim();
+    if passcode.is_empty() {
+        return Err(SolveError::malformed(
+            input,
+            passcode,
+            "expected a passcode",
+        ));
+    }
+    Ok(passcode)
+}
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part1(input: &str) -> String {
-    find_shortest_path(*GOAL, input, &BOUNDS)
+/// Returns an error if no path reaches the vault
+pub fn part1(passcode: &str) -> Result<String, SolveError> {
+    find_shortest_path(*GOAL, passcode, &BOUNDS)
+        .ok_or(SolveError::no_solution("no path reaches the vault"))
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part2(input: &str) -> usize {
-    find_longest_path(*GOAL, input, &BOUNDS).len()
+/// Returns an error if no path reaches the vault
+pub fn part2(passcode: &str) -> Result<usize, SolveError> {
+    find_longest_path(*GOAL, passcode, &BOUNDS)
+        .map(|path| path.len())
+        .ok_or(SolveError::no_solution("no path reaches the vault"))
 }
 
 static GOAL: LazyLock<Coordinate> = LazyLock::new(|| Coordinate::new(3, 3));
@@ -76,11 +124,11 @@ impl Bounds {
     }
 }
 
-fn find_shortest_path(goal: Coordinate, passcode: &str, bounds: &Bounds) -> String {
+fn find_shortest_path(goal: Coordinate, passcode: &str, bounds: &Bounds) -> Option<String> {
     let mut queue = VecDeque::from([(Coordinate::default(), String::new())]);
     while let Some((coordinate, path)) = queue.pop_front() {
         if coordinate == goal {
-            return path;
+            return Some(path);
         }
 
         for (coordinate, next_direction_char) in
@@ -90,27 +138,26 @@ fn find_shortest_path(goal: Coordinate, passcode: &str, bounds: &Bounds) -> Stri
         }
     }
 
-    unreachable!()
+    None
 }
 
-fn find_longest_path(goal: Coordinate, passcode: &str, bounds: &Bounds) -> String {
-    let mut queue = VecDeque::from([(Coordinate::default(), String::new())]);
-    let mut longest_path = String::new();
-
-    while let Some((coordinate, path)) = queue.pop_front() {
-        if coordinate == goal {
-            longest_path.clone_from(&path);
-            continue;
-        }
-
-        for (coordinate, next_direction_char) in
-            get_valid_adjacent(coordinate, bounds, passcode, &path)
-        {
-            queue.push_back((coordinate, format!("{path}{next_direction_char}")));
-        }
-    }
-
-    longest_path
+fn find_longest_path(goal: Coordinate, passcode: &str, bounds: &Bounds) -> Option<String> {
+    search::dfs_paths(
+        [((Coordinate::default(), String::new()), 0)],
+        Keep::Max,
+        |path: &[(Coordinate, String)]| {
+            let (coordinate, path) = path.last().unwrap();
+            get_valid_adjacent(*coordinate, bounds, passcode, path)
+                .into_iter()
+                .map(|(coordinate, direction)| ((coordinate, format!("{path}{direction}")), 1))
+                .collect::<Vec<_>>()
+        },
+        |length, step| length + step,
+        |path| path.last().unwrap().0 == goal,
+        |_, _, _| false,
+    )
+    .pop()
+    .map(|path| path.nodes.last().unwrap().1.clone())
 }
 
 fn get_valid_adjacent(
@@ -169,12 +216,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), "RDDRULDDRR");
+        let input = crate::input::for_test(2016, 17);
+        assert_eq!(part1(parse(&input).unwrap()).unwrap(), "RDDRULDDRR");
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 766);
+        let input = crate::input::for_test(2016, 17);
+        assert_eq!(part2(parse(&input).unwrap()).unwrap(), 766);
     }
 }
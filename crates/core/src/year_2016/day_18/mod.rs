@@ -1,37 +1,71 @@
-use itertools::Itertools;
+use crate::{answer::Answer, error::SolveError, input::embedded, solution::Solution};
 
 pub const TITLE: &str = "Like a Rogue";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 18;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<bool>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    expand_map(&input.chars().map(|item| item == TRAP).collect_vec(), 40)
+pub fn part1(first_row: &[bool]) -> usize {
+    expand_map(first_row, 40)
         .iter()
         .flatten()
         .filter(|flag| !*flag)
         .count()
 }
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part2(input: &str) -> usize {
-    expand_map(
-        &input.chars().map(|item| item == TRAP).collect_vec(),
-        400_000,
-    )
-    .iter()
-    .flatten()
-    .filter(|flag| !*flag)
-    .count()
+pub fn part2(first_row: &[bool]) -> usize {
+    expand_map(first_row, 400_000)
+        .iter()
+        .flatten()
+        .filter(|flag| !*flag)
+        .count()
 }
 
 const TRAP: char = '^';
+const SAFE: char = '.';
+
+/// # Errors
+///
+/// Returns an error if the first row isn't made of `.` and `^` tiles
+pub fn parse(input: &str) -> Result<Vec<bool>, SolveError> {
+    let row = input.trim();
+    row.char_indices()
+        .map(|(i, item)| match item {
+            TRAP => Ok(true),
+            SAFE => Ok(false),
+            _ => Err(SolveError::malformed(
+                input,
+                &row[i..],
+                "expected `.` or `^`",
+            )),
+        })
+        .collect()
+}
 
 const fn is_trap(position: usize, previous_row: &[bool]) -> bool {
     if position >= previous_row.len() {
@@ -66,12 +100,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 1951);
+        let input = crate::input::for_test(2016, 18);
+        assert_eq!(part1(&parse(&input).unwrap()), 1951);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 20_002_936);
+        let input = crate::input::for_test(2016, 18);
+        assert_eq!(part2(&parse(&input).unwrap()), 20_002_936);
     }
 }
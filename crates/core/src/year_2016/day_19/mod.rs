@@ -1,15 +1,43 @@
 use std::collections::VecDeque;
 
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "An Elephant Named Joseph";
 
-pub const INPUT: &str = "3005290";
+pub const INPUT: Option<&str> = embedded!("3005290");
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 19;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(*parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(*parsed).into())
+    }
+}
 
 /// # Panics
 ///
-/// Panics if unable to parse input
+/// Panics if there are no elves, which `parse` rules out
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    let amount = input.parse().unwrap();
+pub fn part1(amount: usize) -> usize {
     let mut elves = (0..amount).map(|v| v + 1).collect::<VecDeque<_>>();
 
     while elves.len() > 1 {
@@ -19,15 +47,14 @@ pub fn part1(input: &str) -> usize {
         elves.pop_front();
     }
 
-    *elves.front().unwrap()
+    elves[0]
 }
 
 /// # Panics
 ///
-/// Panics if unable to parse input
+/// Panics if the circle runs out of elves, which can't happen
 #[must_use]
-pub fn part2(input: &str) -> usize {
-    let amount: usize = input.parse().unwrap();
+pub fn part2(amount: usize) -> usize {
     // do not track the middle element, but divide the elements in two different lists
     let mut left = (0..amount / 2).map(|v| v + 1).collect::<VecDeque<_>>();
     let mut right = (amount / 2..amount).map(|v| v + 1).collect::<VecDeque<_>>();
@@ -46,7 +73,19 @@ pub fn part2(input: &str) -> usize {
         left.push_back(right.pop_front().unwrap());
     }
 
-    *left.front().unwrap()
+    *left.front().or_else(|| right.front()).unwrap()
+}
+
+/// # Errors
+///
+/// Returns an error if the input isn't a positive number of elves
+pub fn parse(input: &str) -> Result<usize, SolveError> {
+    let amount = input.trim();
+    amount
+        .parse()
+        .ok()
+        .filter(|&amount| amount > 0)
+        .or_malformed(input, amount, "expected a positive number of elves")
 }
 
 #[cfg(test)]
@@ -54,12 +93,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 1_816_277);
+        let input = crate::input::for_test(2016, 19);
+        assert_eq!(part1(parse(&input).unwrap()), 1_816_277);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 1_410_967);
+        let input = crate::input::for_test(2016, 19);
+        assert_eq!(part2(parse(&input).unwrap()), 1_410_967);
     }
 }
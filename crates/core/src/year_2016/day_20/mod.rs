@@ -1,23 +1,44 @@
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Firewall Rules";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 20;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<(usize, usize)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part1(input: &str) -> usize {
-    let ranges = parse(input);
-    find_lowest_matching(&ranges)
+pub fn part1(ranges: &[(usize, usize)]) -> usize {
+    find_lowest_matching(ranges)
 }
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part2(input: &str) -> usize {
-    let ranges = parse(input);
-    count_allowed(&ranges)
+pub fn part2(ranges: &[(usize, usize)]) -> usize {
+    count_allowed(ranges)
 }
 
 fn find_lowest_matching(ranges: &[(usize, usize)]) -> usize {
@@ -52,17 +73,27 @@ fn count_allowed(ranges: &[(usize, usize)]) -> usize {
     allowed_count
 }
 
-fn parse(input: &str) -> Vec<(usize, usize)> {
+/// # Errors
+///
+/// Returns an error if unable to parse input
+pub fn parse(input: &str) -> Result<Vec<(usize, usize)>, SolveError> {
     let mut ranges: Vec<(usize, usize)> = input
         .lines()
         .map(|line| {
-            let (low, high) = line.split_once('-').unwrap();
-            (low.parse().unwrap(), high.parse().unwrap())
+            let (low, high) =
+                line.split_once('-')
+                    .or_malformed(input, line, "expected `low-high`")?;
+            Ok((
+                low.parse()
+                    .or_malformed(input, low, "expected an address")?,
+                high.parse()
+                    .or_malformed(input, high, "expected an address")?,
+            ))
         })
-        .collect();
+        .collect::<Result<_, SolveError>>()?;
     ranges.sort_unstable();
 
-    ranges
+    Ok(ranges)
 }
 
 #[cfg(test)]
@@ -70,12 +101,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 17_348_574);
+        let input = crate::input::for_test(2016, 20);
+        assert_eq!(part1(&parse(&input).unwrap()), 17_348_574);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 104);
+        let input = crate::input::for_test(2016, 20);
+        assert_eq!(part2(&parse(&input).unwrap()), 104);
     }
 }
@@ -1,136 +1,201 @@
-use std::string::ToString;
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
 
 pub const TITLE: &str = "Scrambled Letters and Hash";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 21;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Operation>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
+
+/// # Errors
 ///
-/// Panics if unable to parse input
+/// Returns an error if unable to parse input
+pub fn parse(input: &str) -> Result<Vec<Operation>, SolveError> {
+    let position = |word: &str| {
+        word.parse::<usize>()
+            .ok()
+            .filter(|&position| position < PASSWORD.len())
+            .or_malformed(input, word, "expected a position within the password")
+    };
+    let letter = |word: &str| {
+        Some(word.as_bytes())
+            .filter(|bytes| bytes.len() == 1 && PASSWORD.contains(&bytes[0]))
+            .map(|bytes| bytes[0])
+            .or_malformed(input, word, "expected a letter of the password")
+    };
+
+    input
+        .lines()
+        .map(|line| {
+            let words = line.split(' ').collect::<Vec<_>>();
+            Ok(match words.as_slice() {
+                ["swap", "position", x, "with", "position", y] => {
+                    Operation::SwapPosition(position(x)?, position(y)?)
+                }
+                ["swap", "letter", x, "with", "letter", y] => {
+                    Operation::SwapLetter(letter(x)?, letter(y)?)
+                }
+                ["rotate", "based", "on", "position", "of", "letter", word] => {
+                    Operation::RotateBasedOn(letter(word)?)
+                }
+                ["rotate", direction @ ("left" | "right"), steps, "step" | "steps"] => {
+                    let steps = steps.parse::<usize>().or_malformed(
+                        input,
+                        steps,
+                        "expected a number of steps",
+                    )? % PASSWORD.len();
+                    if *direction == "left" {
+                        Operation::RotateLeft(steps)
+                    } else {
+                        Operation::RotateRight(steps)
+                    }
+                }
+                ["reverse", "positions", x, "through", y] => {
+                    let (x, y) = (position(x)?, position(y)?);
+                    if x > y {
+                        return Err(SolveError::malformed(
+                            input,
+                            line,
+                            "expected positions in ascending order",
+                        ));
+                    }
+                    Operation::Reverse(x, y)
+                }
+                ["move", "position", x, "to", "position", y] => {
+                    Operation::Move(position(x)?, position(y)?)
+                }
+                _ => {
+                    return Err(SolveError::malformed(input, line, "unknown operation"));
+                }
+            })
+        })
+        .collect()
+}
+
 #[must_use]
-pub fn part1(input: &str) -> String {
-    let mut password = [b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h'];
-    scramble(input, &mut password).unwrap();
+pub fn part1(operations: &[Operation]) -> String {
+    let mut password = PASSWORD;
+    scramble(operations, &mut password);
     password.iter().map(|&b| b as char).collect::<String>()
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part2(input: &str) -> String {
-    let mut password = [b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h'];
+/// Returns an error if no password scrambles to `fbgdceah`
+pub fn part2(operations: &[Operation]) -> Result<String, SolveError> {
+    let mut password = PASSWORD;
     let desired = [b'f', b'b', b'g', b'd', b'c', b'e', b'a', b'h'];
     let mut answer = None;
     all_permutations(&mut password, &mut |permutation| {
         let mut copy = [0, 0, 0, 0, 0, 0, 0, 0];
         copy.copy_from_slice(permutation);
-        scramble(input, &mut copy).unwrap();
+        scramble(operations, &mut copy);
         if copy == desired {
             answer = Some(permutation.iter().map(|&b| b as char).collect::<String>());
         }
-        Ok(())
-    })
-    .unwrap();
+    });
 
-    answer
-        .ok_or_else(|| "No solution found".to_string())
-        .unwrap()
+    answer.ok_or(SolveError::no_solution(
+        "no password scrambles to `fbgdceah`",
+    ))
 }
 
-fn scramble(input: &str, password: &mut [u8]) -> Result<(), String> {
-    let error_mapper = |_| "Invalid input";
-    for line in input.lines() {
-        let words = line.split(' ').collect::<Vec<_>>();
-        match words.first() {
-            Some(&"swap") => {
-                if words[1] == "position" {
-                    let x = words[2].parse::<usize>().map_err(error_mapper).unwrap();
-                    let y = words[5].parse::<usize>().map_err(error_mapper).unwrap();
-                    password.swap(x, y);
-                } else {
-                    // Swap letters
-                    let x = words[2].as_bytes()[0];
-                    let y = words[5].as_bytes()[0];
-                    for c in password.iter_mut() {
-                        let orig = *c;
-                        *c = if orig == x {
-                            y
-                        } else if orig == y {
-                            x
-                        } else {
-                            orig
-                        };
-                    }
-                }
-            }
-            Some(&"rotate") => {
-                let rotation = if words[1] == "based" {
-                    let letter = words[6].as_bytes()[0];
-                    if let Some((idx, _)) =
-                        password.iter().enumerate().find(|&(_idx, &c)| c == letter)
-                    {
-                        i32::try_from((1 + idx + usize::from(idx >= 4)) % password.len()).unwrap()
+const PASSWORD: [u8; 8] = [b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h'];
+
+/// A single step of the scrambling function.
+#[derive(Debug, Clone, Copy)]
+pub enum Operation {
+    SwapPosition(usize, usize),
+    SwapLetter(u8, u8),
+    RotateBasedOn(u8),
+    RotateLeft(usize),
+    RotateRight(usize),
+    Reverse(usize, usize),
+    Move(usize, usize),
+}
+
+fn scramble(operations: &[Operation], password: &mut [u8]) {
+    let len = password.len();
+
+    for operation in operations {
+        match *operation {
+            Operation::SwapPosition(x, y) => password.swap(x, y),
+            Operation::SwapLetter(x, y) => {
+                for c in password.iter_mut() {
+                    let orig = *c;
+                    *c = if orig == x {
+                        y
+                    } else if orig == y {
+                        x
                     } else {
-                        return Err(format!(
-                            "Unable to find letter for rotation: '{}'",
-                            letter as char
-                        ));
-                    }
-                } else {
-                    words[2].parse::<i32>().map_err(error_mapper).unwrap()
-                        * if words[1] == "left" { -1 } else { 1 }
-                };
-
-                if rotation < 0 {
-                    password.rotate_left((-rotation) as usize);
-                } else {
-                    password.rotate_right(rotation as usize);
+                        orig
+                    };
                 }
             }
-            Some(&"reverse") => {
-                let x = words[2].parse::<usize>().map_err(error_mapper).unwrap();
-                let y = words[4].parse::<usize>().map_err(error_mapper).unwrap();
-                password[x..=y].reverse();
+            Operation::RotateBasedOn(letter) => {
+                // `parse` only accepts letters of the password
+                let idx = password
+                    .iter()
+                    .position(|&c| c == letter)
+                    .unwrap_or_default();
+                password.rotate_right((1 + idx + usize::from(idx >= 4)) % len);
             }
-            Some(&"move") => {
-                let x = words[2].parse::<usize>().map_err(error_mapper).unwrap();
-                let y = words[5].parse::<usize>().map_err(error_mapper).unwrap();
+            Operation::RotateLeft(steps) => password.rotate_left(steps),
+            Operation::RotateRight(steps) => password.rotate_right(steps),
+            Operation::Reverse(x, y) => password[x..=y].reverse(),
+            Operation::Move(x, y) => {
                 let mut buffer: Vec<u8> = password.to_vec();
                 let removed_letter = buffer.remove(x);
                 buffer.insert(y, removed_letter);
                 password.clone_from_slice(&buffer);
             }
-            _ => {
-                return Err("Invalid input".to_string());
-            }
         }
     }
-    Ok(())
 }
 
-fn all_permutations<F, T>(sequence: &mut [T], on_permutation: &mut F) -> Result<(), String>
+fn all_permutations<F, T>(sequence: &mut [T], on_permutation: &mut F)
 where
-    F: FnMut(&[T]) -> Result<(), String>,
+    F: FnMut(&[T]),
 {
     let size = sequence.len();
-    all_permutations_internal(sequence, size, on_permutation)
+    all_permutations_internal(sequence, size, on_permutation);
 }
 
-fn all_permutations_internal<F, T>(
-    sequence: &mut [T],
-    size: usize,
-    on_permutation: &mut F,
-) -> Result<(), String>
+fn all_permutations_internal<F, T>(sequence: &mut [T], size: usize, on_permutation: &mut F)
 where
-    F: FnMut(&[T]) -> Result<(), String>,
+    F: FnMut(&[T]),
 {
     if size == 1 {
-        return on_permutation(sequence);
+        on_permutation(sequence);
+        return;
     }
 
     for i in 0..size {
-        all_permutations_internal(sequence, size - 1, on_permutation)?;
+        all_permutations_internal(sequence, size - 1, on_permutation);
 
         if size % 2 == 1 {
             // If size is odd, swap first and last element.
@@ -140,8 +205,6 @@ where
             sequence.swap(i, size - 1);
         }
     }
-
-    Ok(())
 }
 
 #[cfg(test)]
@@ -149,12 +212,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), "gbhcefad");
+        let input = crate::input::for_test(2016, 21);
+        assert_eq!(part1(&parse(&input).unwrap()), "gbhcefad");
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), "gahedfcb");
+        let input = crate::input::for_test(2016, 21);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), "gahedfcb");
     }
 }
@@ -1,18 +1,44 @@
-use std::collections::VecDeque;
+use crate::{
+    answer::Answer,
+    error::SolveError,
+    input::embedded,
+    shared::{parse::OrMalformed, search},
+    solution::Solution,
+};
 
 pub const TITLE: &str = "Grid Computing";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 22;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Vec<Node>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
 
-/// # Panics
-///
-/// Panics if unable to parse input
 #[must_use]
-pub fn part1(input: &str) -> i32 {
-    let nodes = get_nodes(input);
+pub fn part1(nodes: &[Node]) -> i32 {
     let mut sum = 0;
-    for a in &nodes {
-        for b in &nodes {
+    for a in nodes {
+        for b in nodes {
             if a.path != b.path && a.used != 0 && a.used <= b.avail {
                 sum += 1;
             }
@@ -22,30 +48,25 @@ pub fn part1(input: &str) -> i32 {
     sum
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part2(input: &str) -> usize {
-    let nodes = get_nodes(input);
-
+/// Returns an error if the data can't be moved to the top left node
+pub fn part2(nodes: &[Node]) -> Result<usize, SolveError> {
     let bottom_right = nodes
         .iter()
         .map(|node| node.position)
         .max()
-        .ok_or("No bottom right node")
-        .unwrap();
+        .ok_or(SolveError::no_solution("no bottom right node"))?;
     let dimensions = (bottom_right.0 + 1, bottom_right.1 + 1);
     let wall_threshold = nodes
         .iter()
         .filter(|node| node.position.1 == 0)
         .map(|node| node.size)
         .max()
-        .ok_or("No wall threshold")
-        .unwrap();
+        .ok_or(SolveError::no_solution("no wall threshold"))?;
     let mut grid = vec![true; usize::from(dimensions.0) * usize::from(dimensions.1)];
 
-    for node in &nodes {
+    for node in nodes {
         grid[usize::from(node.position.1) * usize::from(dimensions.0)
             + usize::from(node.position.0)] = node.size > wall_threshold;
     }
@@ -53,17 +74,20 @@ pub fn part2(input: &str) -> usize {
     let empty_pos = nodes
         .iter()
         .find(|node| node.used == 0 && node.size > 0)
-        .ok_or("No empty node")
-        .unwrap()
+        .ok_or(SolveError::no_solution("no empty node"))?
         .position;
     let payload_pos = (dimensions.0 - 1, 0);
-    let dist_to_payload = dist(dimensions, grid.clone(), empty_pos, payload_pos).unwrap();
-    let dist_to_home = dist(dimensions, grid, (payload_pos.0 - 1, payload_pos.1), (0, 0)).unwrap();
-    dist_to_payload + 5 * dist_to_home
+    let before_payload = payload_pos
+        .0
+        .checked_sub(1)
+        .ok_or(SolveError::no_solution("the payload is already home"))?;
+    let dist_to_payload = dist(dimensions, &grid, empty_pos, payload_pos)?;
+    let dist_to_home = dist(dimensions, &grid, (before_payload, payload_pos.1), (0, 0))?;
+    Ok(dist_to_payload + 5 * dist_to_home)
 }
 
-#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
-struct Node {
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd)]
+pub struct Node {
     path: String,
     position: (u8, u8),
     size: i32,
@@ -72,93 +96,82 @@ struct Node {
     percentage_used: i32,
 }
 
-fn get_nodes(input: &str) -> Vec<Node> {
+/// # Errors
+///
+/// Returns an error if unable to parse input
+///
+/// # Panics
+/// * Panics if an invalid pattern is given.
+pub fn parse(input: &str) -> Result<Vec<Node>, SolveError> {
     let re = regex::Regex::new(r"(?P<path>[a-zA-Z\/\-0-9]+)\s+(?P<size>\d+)T\s+(?P<used>\d+)T\s+(?P<avail>\d+)T\s+(?P<percentageUsed>\d+)%").unwrap();
+    let coordinate = |part: Option<&str>, prefix: char| {
+        part.and_then(|part| part.strip_prefix(prefix))
+            .and_then(|number| number.parse::<u8>().ok())
+    };
 
     let mut nodes = Vec::<Node>::new();
     for line in input.lines().skip(2) {
-        if let Some(captures) = re.captures(line) {
-            let path = captures.name("path").unwrap().as_str().to_string();
-            let size = captures
-                .name("size")
-                .unwrap()
-                .as_str()
-                .parse::<i32>()
-                .unwrap();
-            let used = captures
-                .name("used")
-                .unwrap()
-                .as_str()
-                .parse::<i32>()
-                .unwrap();
-            let avail = captures
-                .name("avail")
-                .unwrap()
-                .as_str()
+        let captures = re
+            .captures(line)
+            .or_malformed(input, line, "expected a node")?;
+        let number = |name: &str| {
+            let value = &captures[name];
+            value
                 .parse::<i32>()
-                .unwrap();
-            let percentage_used = captures
-                .name("percentageUsed")
-                .unwrap()
-                .as_str()
-                .parse::<i32>()
-                .unwrap();
-
-            let name_parts = path.split('-').collect::<Vec<_>>();
-            let x = name_parts[1][1..].parse::<u8>().unwrap();
-            let y = name_parts[2][1..].parse::<u8>().unwrap();
-
-            nodes.push(Node {
-                path,
-                position: (x, y),
-                size,
-                used,
-                avail,
-                percentage_used,
-            });
-        }
+                .or_malformed(input, value, "expected a number")
+        };
+        let size = number("size")?;
+        let used = number("used")?;
+        let avail = number("avail")?;
+        let percentage_used = number("percentageUsed")?;
+
+        let path = &captures["path"];
+        let mut name_parts = path.split('-').skip(1);
+        let position = coordinate(name_parts.next(), 'x')
+            .zip(coordinate(name_parts.next(), 'y'))
+            .or_malformed(input, path, "expected a `node-xX-yY` path")?;
+
+        nodes.push(Node {
+            path: path.to_string(),
+            position,
+            size,
+            used,
+            avail,
+            percentage_used,
+        });
     }
 
-    nodes
+    Ok(nodes)
 }
 
 fn dist(
     dimensions: (u8, u8),
-    mut grid: Vec<bool>,
+    grid: &[bool],
     start: (u8, u8),
     destination: (u8, u8),
-) -> Result<usize, String> {
-    let mut queue = VecDeque::new();
-    queue.push_back((0, start));
-
-    while let Some((path_length, position)) = queue.pop_front() {
-        let grid_idx =
-            usize::from(position.1) * usize::from(dimensions.0) + usize::from(position.0);
-
-        if grid[grid_idx] {
-            continue;
-        }
-        if position == destination {
-            return Ok(path_length);
-        }
-
-        grid[grid_idx] = true;
-
-        if position.0 != 0 {
-            queue.push_back((path_length + 1, (position.0 - 1, position.1)));
-        }
-        if position.0 != dimensions.0 - 1 {
-            queue.push_back((path_length + 1, (position.0 + 1, position.1)));
-        }
-        if position.1 != 0 {
-            queue.push_back((path_length + 1, (position.0, position.1 - 1)));
-        }
-        if position.1 != dimensions.1 - 1 {
-            queue.push_back((path_length + 1, (position.0, position.1 + 1)));
-        }
-    }
-
-    Err(format!("No path found from {start:?} to {destination:?}"))
+) -> Result<usize, SolveError> {
+    let is_wall = |position: (u8, u8)| {
+        grid[usize::from(position.1) * usize::from(dimensions.0) + usize::from(position.0)]
+    };
+    let neighbours = |&position: &(u8, u8)| {
+        [
+            (position.0 != 0).then(|| (position.0 - 1, position.1)),
+            (position.0 != dimensions.0 - 1).then(|| (position.0 + 1, position.1)),
+            (position.1 != 0).then(|| (position.0, position.1 - 1)),
+            (position.1 != dimensions.1 - 1).then(|| (position.0, position.1 + 1)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|&next| !is_wall(next))
+    };
+
+    (!is_wall(start))
+        .then(|| search::bfs(start, neighbours, |&position| position == destination))
+        .flatten()
+        .map(|path| path.cost)
+        .ok_or(SolveError::no_solution(
+            "no path through the grid reaches the destination",
+        ))
 }
 
 #[cfg(test)]
@@ -166,12 +179,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 1003);
+        let input = crate::input::for_test(2016, 22);
+        assert_eq!(part1(&parse(&input).unwrap()), 1003);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 192);
+        let input = crate::input::for_test(2016, 22);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 192);
     }
 }
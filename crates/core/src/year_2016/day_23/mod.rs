@@ -1,33 +1,60 @@
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Safe Cracking";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 23;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = Program;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
+    }
+}
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part1(input: &str) -> i64 {
-    let mut prog = read_program(input);
+/// Returns an error if the program jumps before its first instruction
+pub fn part1(program: &[Instr]) -> Result<i64, SolveError> {
+    let mut prog = program.to_vec();
     let mut state = CpuState {
         regs: [7, 0, 0, 0],
         ..Default::default()
     };
-    run_program(&mut prog, &mut state);
-    state.regs[0]
+    run_program(&mut prog, &mut state)?;
+    Ok(state.regs[0])
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part2(input: &str) -> i64 {
-    let mut prog = read_program(input);
+/// Returns an error if the program jumps before its first instruction
+pub fn part2(program: &[Instr]) -> Result<i64, SolveError> {
+    let mut prog = program.to_vec();
     let mut state = CpuState {
         regs: [12, 0, 0, 0],
         ..Default::default()
     };
-    run_program(&mut prog, &mut state);
-    state.regs[0]
+    run_program(&mut prog, &mut state)?;
+    Ok(state.regs[0])
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -45,8 +72,9 @@ enum InstrOp {
     TglR,
 }
 
+/// A single instruction of the assembunny program.
 #[derive(Debug, Clone, Copy)]
-struct Instr {
+pub struct Instr {
     op: InstrOp,
     args: [i64; 2],
 }
@@ -57,9 +85,9 @@ struct CpuState {
     regs: [i64; 4],
 }
 
-type Program = Vec<Instr>;
+pub type Program = Vec<Instr>;
 
-fn toggle_instr(prog: &mut Program, instr: usize) {
+fn toggle_instr(prog: &mut Program, instr: i64) {
     static NEXT: [InstrOp; 11] = [
         InstrOp::Nop,
         InstrOp::DecR,
@@ -73,172 +101,156 @@ fn toggle_instr(prog: &mut Program, instr: usize) {
         InstrOp::Nop,
         InstrOp::IncR,
     ];
-    if instr < prog.len() {
-        prog[instr].op = NEXT[prog[instr].op as usize];
+    if let Some(instr) = usize::try_from(instr)
+        .ok()
+        .and_then(|instr| prog.get_mut(instr))
+    {
+        instr.op = NEXT[instr.op as usize];
     }
 }
 
-fn run_program(prog: &mut Program, state: &mut CpuState) {
+fn jump(pc: usize, offset: i64) -> Result<usize, SolveError> {
+    usize::try_from(pc as i64 + offset)
+        .map_err(|_| SolveError::no_solution("the program jumps before its first instruction"))
+}
+
+// Register arguments are validated by `parse`, and toggling never turns an
+// immediate into a register, so indexing `regs` with them can't go out of bounds.
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+fn run_program(prog: &mut Program, state: &mut CpuState) -> Result<(), SolveError> {
     while state.pc < prog.len() {
         let instr = prog[state.pc];
+        let [first, second] = instr.args;
         match instr.op {
             InstrOp::Nop => {}
-            InstrOp::IncR => state.regs[usize::try_from(instr.args[0]).unwrap()] += 1,
-            InstrOp::DecR => state.regs[usize::try_from(instr.args[0]).unwrap()] -= 1,
-            InstrOp::CpyRr => {
-                state.regs[usize::try_from(instr.args[1]).unwrap()] =
-                    state.regs[usize::try_from(instr.args[0]).unwrap()];
-            }
-            InstrOp::CpyIr => state.regs[usize::try_from(instr.args[1]).unwrap()] = instr.args[0],
+            InstrOp::IncR => state.regs[first as usize] += 1,
+            InstrOp::DecR => state.regs[first as usize] -= 1,
+            InstrOp::CpyRr => state.regs[second as usize] = state.regs[first as usize],
+            InstrOp::CpyIr => state.regs[second as usize] = first,
             InstrOp::JnzIi => {
-                if instr.args[0] != 0 {
-                    state.pc = usize::try_from(state.pc as i64 + instr.args[1] - 1).unwrap();
+                if first != 0 {
+                    state.pc = jump(state.pc, second)?;
+                    continue;
                 }
             }
             InstrOp::JnzIr => {
-                if instr.args[0] != 0 {
-                    state.pc = usize::try_from(
-                        state.pc as i64 + state.regs[usize::try_from(instr.args[1]).unwrap()] - 1,
-                    )
-                    .unwrap();
+                if first != 0 {
+                    state.pc = jump(state.pc, state.regs[second as usize])?;
+                    continue;
                 }
             }
             InstrOp::JnzRi => {
-                if state.regs[usize::try_from(instr.args[0]).unwrap()] != 0 {
-                    state.pc = usize::try_from(state.pc as i64 + instr.args[1] - 1).unwrap();
+                if state.regs[first as usize] != 0 {
+                    state.pc = jump(state.pc, second)?;
+                    continue;
                 }
             }
             InstrOp::JnzRr => {
-                if state.regs[usize::try_from(instr.args[0]).unwrap()] != 0 {
-                    state.pc = usize::try_from(
-                        state.pc as i64 + state.regs[usize::try_from(instr.args[1]).unwrap()] - 1,
-                    )
-                    .unwrap();
+                if state.regs[first as usize] != 0 {
+                    state.pc = jump(state.pc, state.regs[second as usize])?;
+                    continue;
                 }
             }
-            InstrOp::TglI => toggle_instr(prog, state.pc + usize::try_from(instr.args[0]).unwrap()),
-            InstrOp::TglR => toggle_instr(
-                prog,
-                state.pc
-                    + usize::try_from(state.regs[usize::try_from(instr.args[0]).unwrap()]).unwrap(),
-            ),
+            InstrOp::TglI => toggle_instr(prog, state.pc as i64 + first),
+            InstrOp::TglR => toggle_instr(prog, state.pc as i64 + state.regs[first as usize]),
         }
         state.pc += 1;
     }
+
+    Ok(())
 }
 
-fn read_program(input: &str) -> Program {
+/// # Errors
+///
+/// Returns an error if unable to parse input
+pub fn parse(input: &str) -> Result<Program, SolveError> {
     let mut program = Vec::new();
     for line in input.lines() {
-        let mut words = line.split_whitespace();
-        let instr = match words.next() {
-            Some("cpy") => cpy(&mut words),
-            Some("jnz") => jnz(&mut words),
-            Some("inc") => inc(&mut words),
-            Some("dec") => dec(&mut words),
-            Some("tgl") => tgl(words),
-            _ => continue,
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let instr = match words.as_slice() {
+            ["cpy", arg1, arg2] => cpy(input, arg1, arg2)?,
+            ["jnz", arg1, arg2] => jnz(input, arg1, arg2)?,
+            ["inc", reg] => Instr {
+                op: InstrOp::IncR,
+                args: [register(input, reg)?, 0],
+            },
+            ["dec", reg] => Instr {
+                op: InstrOp::DecR,
+                args: [register(input, reg)?, 0],
+            },
+            ["tgl", arg] => tgl(input, arg)?,
+            _ => return Err(SolveError::malformed(input, line, "unknown instruction")),
         };
         program.push(instr);
     }
-    program
+    Ok(program)
 }
 
-fn tgl(mut words: std::str::SplitWhitespace) -> Instr {
-    let arg = words.next().unwrap();
-    if arg.chars().all(|c| c.is_ascii_alphabetic()) {
-        let arg = arg.chars().next().unwrap() as i64 - 'a' as i64;
-        Instr {
-            op: InstrOp::TglR,
-            args: [arg, 0],
-        }
-    } else {
-        let arg = arg.parse().unwrap();
-        Instr {
-            op: InstrOp::TglI,
-            args: [arg, 0],
-        }
-    }
+fn is_register(word: &str) -> bool {
+    word.chars().all(|c| c.is_ascii_alphabetic())
 }
 
-fn dec(words: &mut std::str::SplitWhitespace) -> Instr {
-    let reg = words.next().unwrap().chars().next().unwrap() as i64 - 'a' as i64;
-    Instr {
-        op: InstrOp::DecR,
-        args: [reg, 0],
+fn register(input: &str, word: &str) -> Result<i64, SolveError> {
+    match word {
+        "a" | "b" | "c" | "d" => Ok(i64::from(word.as_bytes()[0] - b'a')),
+        _ => Err(SolveError::malformed(input, word, "expected a register")),
     }
 }
 
-fn inc(words: &mut std::str::SplitWhitespace) -> Instr {
-    let reg = words.next().unwrap().chars().next().unwrap() as i64 - 'a' as i64;
-    Instr {
-        op: InstrOp::IncR,
-        args: [reg, 0],
-    }
+fn immediate(input: &str, word: &str) -> Result<i64, SolveError> {
+    word.parse().or_malformed(input, word, "expected a number")
 }
 
-fn jnz(words: &mut std::str::SplitWhitespace) -> Instr {
-    let arg1 = words.next().unwrap();
-    let arg2 = words.next().unwrap();
-    if arg1.chars().all(|c| c.is_ascii_alphabetic()) {
-        let arg1 = arg1.chars().next().unwrap() as i64 - 'a' as i64;
-        if arg2.chars().all(|c| c.is_ascii_alphabetic()) {
-            let arg2 = arg2.chars().next().unwrap() as i64 - 'a' as i64;
-            Instr {
-                op: InstrOp::JnzRr,
-                args: [arg1, arg2],
-            }
-        } else {
-            let arg2 = arg2.parse().unwrap();
-            Instr {
-                op: InstrOp::JnzRi,
-                args: [arg1, arg2],
-            }
+fn tgl(input: &str, arg: &str) -> Result<Instr, SolveError> {
+    Ok(if is_register(arg) {
+        Instr {
+            op: InstrOp::TglR,
+            args: [register(input, arg)?, 0],
         }
     } else {
-        let arg1 = arg1.parse().unwrap();
-        if arg2.chars().all(|c| c.is_ascii_alphabetic()) {
-            let arg2 = arg2.chars().next().unwrap() as i64 - 'a' as i64;
-            Instr {
-                op: InstrOp::JnzIr,
-                args: [arg1, arg2],
-            }
-        } else {
-            let arg2 = arg2.parse().unwrap();
-            Instr {
-                op: InstrOp::JnzIi,
-                args: [arg1, arg2],
-            }
+        Instr {
+            op: InstrOp::TglI,
+            args: [immediate(input, arg)?, 0],
         }
-    }
+    })
 }
 
-fn cpy(words: &mut std::str::SplitWhitespace) -> Instr {
-    let arg1 = words.next().unwrap();
-    let arg2 = words.next().unwrap();
-    if arg1.chars().all(|c| c.is_ascii_alphabetic()) {
-        let arg1 = arg1.chars().next().unwrap() as i64 - 'a' as i64;
-        if arg2.chars().all(|c| c.is_ascii_alphabetic()) {
-            let arg2 = arg2.chars().next().unwrap() as i64 - 'a' as i64;
-            Instr {
-                op: InstrOp::CpyRr,
-                args: [arg1, arg2],
-            }
-        } else {
-            let arg2 = arg2.parse().unwrap();
-            Instr {
-                op: InstrOp::CpyIr,
-                args: [arg1, arg2],
-            }
+fn jnz(input: &str, arg1: &str, arg2: &str) -> Result<Instr, SolveError> {
+    let op = match (is_register(arg1), is_register(arg2)) {
+        (true, true) => InstrOp::JnzRr,
+        (true, false) => InstrOp::JnzRi,
+        (false, true) => InstrOp::JnzIr,
+        (false, false) => InstrOp::JnzIi,
+    };
+    let arg1 = if is_register(arg1) {
+        register(input, arg1)?
+    } else {
+        immediate(input, arg1)?
+    };
+    let arg2 = if is_register(arg2) {
+        register(input, arg2)?
+    } else {
+        immediate(input, arg2)?
+    };
+    Ok(Instr {
+        op,
+        args: [arg1, arg2],
+    })
+}
+
+fn cpy(input: &str, arg1: &str, arg2: &str) -> Result<Instr, SolveError> {
+    let arg2 = register(input, arg2)?;
+    Ok(if is_register(arg1) {
+        Instr {
+            op: InstrOp::CpyRr,
+            args: [register(input, arg1)?, arg2],
         }
     } else {
-        let arg1 = arg1.parse().unwrap();
-        let arg2 = arg2.chars().next().unwrap() as i64 - 'a' as i64;
         Instr {
             op: InstrOp::CpyIr,
-            args: [arg1, arg2],
+            args: [immediate(input, arg1)?, arg2],
         }
-    }
+    })
 }
 
 #[cfg(test)]
@@ -246,12 +258,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 12330);
+        let input = crate::input::for_test(2016, 23);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 12330);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 479_008_890);
+        let input = crate::input::for_test(2016, 23);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 479_008_890);
     }
 }
@@ -1,102 +1,123 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
+
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::search, solution::Solution,
+};
 
 pub const TITLE: &str = "Air Duct Spelunking";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
 
-/// # Panics
-///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part1(input: &str) -> usize {
-    let mut walls = Vec::new();
-    let mut goals = Vec::new();
-    for (j, row) in input.lines().enumerate() {
-        let mut row_vec = Vec::new();
-        for (i, c) in row.chars().enumerate() {
-            if c == '#' {
-                row_vec.push(true);
-            } else if c.is_ascii_digit() {
-                goals.push((j, i));
-                row_vec.push(false);
-            } else {
-                row_vec.push(false);
-            }
-        }
-        walls.push(row_vec);
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 24;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = (Vec<Vec<bool>>, Vec<Pos>);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part2(parsed).map(Answer::from)
     }
-    let dists = get_dists_map(&walls, &goals);
+}
+
+/// # Errors
+///
+/// Returns an error if some location can't be reached
+pub fn part1((walls, goals): &(Vec<Vec<bool>>, Vec<Pos>)) -> Result<usize, SolveError> {
+    let dists = get_dists_map(walls, goals);
 
     shortest_path_wrapper(&dists, true)
 }
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part2(input: &str) -> usize {
+/// Returns an error if some location can't be reached
+pub fn part2((walls, goals): &(Vec<Vec<bool>>, Vec<Pos>)) -> Result<usize, SolveError> {
+    let dists = get_dists_map(walls, goals);
+
+    shortest_path_wrapper(&dists, false)
+}
+
+pub type Pos = (usize, usize);
+
+/// # Errors
+///
+/// Returns an error if unable to parse input
+pub fn parse(input: &str) -> Result<(Vec<Vec<bool>>, Vec<Pos>), SolveError> {
     let mut walls = Vec::new();
     let mut goals = Vec::new();
     for (j, row) in input.lines().enumerate() {
         let mut row_vec = Vec::new();
-        for (i, c) in row.chars().enumerate() {
-            if c == '#' {
-                row_vec.push(true);
-            } else if c.is_ascii_digit() {
-                goals.push((j, i));
-                row_vec.push(false);
-            } else {
-                row_vec.push(false);
+        for (i, c) in row.char_indices() {
+            match c {
+                '#' => row_vec.push(true),
+                '.' => row_vec.push(false),
+                '0'..='9' => {
+                    goals.push((j, i));
+                    row_vec.push(false);
+                }
+                _ => {
+                    return Err(SolveError::malformed(
+                        input,
+                        &row[i..],
+                        "expected `#`, `.` or a digit",
+                    ));
+                }
             }
         }
+        if walls
+            .first()
+            .is_some_and(|first: &Vec<bool>| first.len() != row_vec.len())
+        {
+            return Err(SolveError::malformed(
+                input,
+                row,
+                "expected rows of equal width",
+            ));
+        }
         walls.push(row_vec);
     }
-    let dists = get_dists_map(&walls, &goals);
+    if goals.is_empty() {
+        return Err(SolveError::malformed(
+            input,
+            input,
+            "expected at least one location",
+        ));
+    }
 
-    shortest_path_wrapper(&dists, false)
+    Ok((walls, goals))
 }
 
-type Pos = (usize, usize);
-
-fn get_dists_to_all(walls: &[Vec<bool>], from: Pos, to: &Vec<Pos>) -> Vec<usize> {
-    let mut left = to.len();
-    let mut dist = vec![vec![usize::MAX; walls[0].len()]; walls.len()];
-    let mut todo = VecDeque::new();
-    dist[from.0][from.1] = 0;
-    todo.push_back(from);
-    while left > 0 && !todo.is_empty() {
-        if let Some(t) = todo.pop_front() {
-            if to.contains(&t) {
-                left -= 1;
-            }
-            let (x, y) = t;
-            let d = dist[x][y] + 1;
-            if x > 0 && !walls[x - 1][y] && dist[x - 1][y] > d {
-                dist[x - 1][y] = d;
-                todo.push_back((x - 1, y));
-            }
-            if x + 1 < walls.len() && !walls[x + 1][y] && dist[x + 1][y] > d {
-                dist[x + 1][y] = d;
-                todo.push_back((x + 1, y));
-            }
-            if y > 0 && !walls[x][y - 1] && dist[x][y - 1] > d {
-                dist[x][y - 1] = d;
-                todo.push_back((x, y - 1));
-            }
-            if y + 1 < walls[0].len() && !walls[x][y + 1] && dist[x][y + 1] > d {
-                dist[x][y + 1] = d;
-                todo.push_back((x, y + 1));
-            }
-        }
-    }
-    let mut res = Vec::new();
-    for dst in to {
-        res.push(dist[dst.0][dst.1]);
-    }
-    res
+fn get_dists_to_all(walls: &[Vec<bool>], from: Pos, to: &[Pos]) -> Vec<usize> {
+    let dist = search::distances(from, |&(x, y): &Pos| {
+        [
+            x.checked_sub(1).map(|x| (x, y)),
+            Some((x + 1, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+            Some((x, y + 1)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|&(x, y)| walls.get(x).and_then(|row| row.get(y)) == Some(&false))
+    });
+    to.iter()
+        .map(|dst| dist.get(dst).copied().unwrap_or(usize::MAX))
+        .collect()
 }
 
-fn get_dists_map(walls: &[Vec<bool>], goals: &Vec<Pos>) -> Vec<Vec<usize>> {
+fn get_dists_map(walls: &[Vec<bool>], goals: &[Pos]) -> Vec<Vec<usize>> {
     let mut ret = Vec::new();
     for &start in goals {
         ret.push(get_dists_to_all(walls, start, goals));
@@ -137,9 +158,12 @@ fn shortest_path(
     }
 }
 
-fn shortest_path_wrapper(dists: &Vec<Vec<usize>>, is_part1: bool) -> usize {
+fn shortest_path_wrapper(dists: &Vec<Vec<usize>>, is_part1: bool) -> Result<usize, SolveError> {
+    if dists.iter().flatten().any(|&dist| dist == usize::MAX) {
+        return Err(SolveError::no_solution("some location can't be reached"));
+    }
     let mut mem = vec![HashMap::new(); dists.len()];
-    shortest_path(dists, 0, 1, &mut mem, is_part1)
+    Ok(shortest_path(dists, 0, 1, &mut mem, is_part1))
 }
 
 #[cfg(test)]
@@ -147,12 +171,16 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 412);
+        let input = crate::input::for_test(2016, 24);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 412);
     }
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part2_puzzle() {
-        assert_eq!(part2(INPUT), 664);
+        let input = crate::input::for_test(2016, 24);
+        assert_eq!(part2(&parse(&input).unwrap()).unwrap(), 664);
     }
 }
@@ -1,37 +1,75 @@
+use crate::{
+    answer::Answer, error::SolveError, input::embedded, shared::parse::OrMalformed,
+    solution::Solution,
+};
+
 pub const TITLE: &str = "Clock Signal";
 
-pub const INPUT: &str = include_str!("input.txt");
+pub const INPUT: Option<&str> = embedded!(include_str!("input.txt"));
+
+#[derive(Debug)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2016;
+    const DAY: u8 = 25;
+    const TITLE: &'static str = TITLE;
+    const INPUT: Option<&'static str> = INPUT;
+
+    type Parsed<'a> = (u32, u32);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(parsed).into())
+    }
+}
 
-/// # Panics
+/// # Errors
 ///
-/// Panics if unable to parse input
-#[must_use]
-pub fn part1(input: &str) -> u32 {
+/// Returns an error if unable to parse input
+pub fn parse(input: &str) -> Result<(u32, u32), SolveError> {
     let lines: Vec<_> = input.lines().collect();
-    let first: u32 = unsigned(lines[1]);
-    let second: u32 = unsigned(lines[2]);
-    let offset = first * second;
+    let operand = |index: usize| {
+        let line = lines.get(index).copied().unwrap_or_default();
+        unsigned(line).or_malformed(input, line, "expected an instruction with a number")
+    };
+
+    Ok((operand(1)?, operand(2)?))
+}
+
+/// # Errors
+///
+/// Returns an error if the offset overflows
+pub fn part1(&(a, b): &(u32, u32)) -> Result<u32, SolveError> {
+    let offset = a
+        .checked_mul(b)
+        .ok_or(SolveError::no_solution("the offset overflows"))?;
     let mut result = 0;
 
     while result < offset {
         result = (result << 2) | 2;
     }
 
-    result - offset
+    Ok(result - offset)
 }
 
-/// # Panics
-///
-/// Panics if unable to parse input
+/// Day 25 has no second puzzle.
 #[must_use]
-pub const fn part2(_: &str) -> usize {
+pub const fn part2(_: &(u32, u32)) -> usize {
     0
 }
 
-fn unsigned(value: &str) -> u32 {
+fn unsigned(value: &str) -> Option<u32> {
     let mut bytes = value.bytes();
     let mut n = loop {
-        let byte = bytes.next().unwrap();
+        let byte = bytes.next()?;
         let digit = byte.wrapping_sub(b'0');
 
         if digit < 10 {
@@ -41,14 +79,14 @@ fn unsigned(value: &str) -> u32 {
 
     loop {
         let Some(byte) = bytes.next() else {
-            break n;
+            break Some(n);
         };
         let digit = byte.wrapping_sub(b'0');
 
         if digit < 10 {
-            n = 10 * n + u32::from(digit);
+            n = n.checked_mul(10)?.checked_add(u32::from(digit))?;
         } else {
-            break n;
+            break Some(n);
         }
     }
 }
@@ -58,7 +96,9 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(not(feature = "embed-inputs"), ignore = "needs AOC_INPUT_DIR")]
     fn test_part1_puzzle() {
-        assert_eq!(part1(INPUT), 180);
+        let input = crate::input::for_test(2016, 25);
+        assert_eq!(part1(&parse(&input).unwrap()).unwrap(), 180);
     }
 }
@@ -1,3 +1,5 @@
+use crate::solution::{Entry, Variant};
+
 pub mod day_01;
 pub mod day_02;
 pub mod day_03;
@@ -23,3 +25,34 @@ pub mod day_22;
 pub mod day_23;
 pub mod day_24;
 pub mod day_25;
+
+/// Every day of 2016, in order.
+pub const SOLUTIONS: &[Entry] = &[
+    Entry::new::<day_01::Puzzle>(),
+    Entry::new::<day_02::Puzzle>(),
+    Entry::new::<day_03::Puzzle>(),
+    Entry::new::<day_04::Puzzle>(),
+    Entry::new::<day_05::Puzzle>(),
+    Entry::new::<day_06::Puzzle>(),
+    Entry::new::<day_07::Puzzle>(),
+    Entry::new::<day_08::Puzzle>(),
+    Entry::new::<day_09::Puzzle>(),
+    Entry::new::<day_10::Puzzle>(),
+    Entry::new::<day_11::Puzzle>()
+        .with_variants(&[Variant::new::<day_11::BreadthFirst>("breadth-first")])
+        .with_generator(day_11::generate),
+    Entry::new::<day_12::Puzzle>(),
+    Entry::new::<day_13::Puzzle>(),
+    Entry::new::<day_14::Puzzle>(),
+    Entry::new::<day_15::Puzzle>(),
+    Entry::new::<day_16::Puzzle>(),
+    Entry::new::<day_17::Puzzle>(),
+    Entry::new::<day_18::Puzzle>(),
+    Entry::new::<day_19::Puzzle>(),
+    Entry::new::<day_20::Puzzle>(),
+    Entry::new::<day_21::Puzzle>(),
+    Entry::new::<day_22::Puzzle>(),
+    Entry::new::<day_23::Puzzle>(),
+    Entry::new::<day_24::Puzzle>(),
+    Entry::new::<day_25::Puzzle>(),
+];